@@ -0,0 +1,376 @@
+// Strategy chart derivation.
+//
+// Every candidate hold of every canonical hand in a strategy file is classified with
+// classify_hold, using the classes of the paytable's game family. A chart is an
+// ordered list of classes, played by holding the best hold of the highest listed
+// class present (or discarding everything if none is). The derived chart is the
+// order that loses the least return against the strategy file's optimal holds; the
+// hands it still misplays are reported as penalty-card cases and exceptions.
+
+use super::*;
+use crate::family::resolve_family;
+use crate::strategy::{read_strategy, StrategyEntry, StrategyFile};
+use std::fs;
+use std::path::Path;
+
+/// Exception groups listed in the Markdown report (the JSON has all of them)
+const MARKDOWN_EXCEPTION_LIMIT: usize = 40;
+const EXAMPLES_PER_GROUP: usize = 3;
+
+/// The best hold of each class present in a hand: (class, hold mask, EV)
+pub fn class_options(entry: &StrategyEntry, scheme: ClassScheme) -> Vec<(HandClass, u8, f64)> {
+    let mut options: Vec<(HandClass, u8, f64)> = Vec::new();
+    for mask in 0..32u8 {
+        let Some(class) = classify_hold(&entry.held(mask), scheme) else { continue };
+        let ev = entry.evs[mask as usize];
+        match options.iter_mut().find(|(c, _, _)| *c == class) {
+            Some(option) if ev > option.2 => *option = (class, mask, ev),
+            Some(_) => {}
+            None => options.push((class, mask, ev)),
+        }
+    }
+    options.sort_by_key(|(c, _, _)| *c);
+    options
+}
+
+/// The hold a chart plays: the highest listed class present, else discard everything
+pub fn chart_hold(positions: &HashMap<HandClass, usize>, options: &[(HandClass, u8, f64)], entry: &StrategyEntry)
+    -> (HandClass, u8, f64)
+{
+    options.iter()
+        .filter(|(c, _, _)| positions.contains_key(c))
+        .min_by_key(|(c, _, _)| positions[c])
+        .cloned()
+        .unwrap_or((HandClass::Garbage, 0, entry.evs[0]))
+}
+
+pub fn positions_of(order: &[HandClass]) -> HashMap<HandClass, usize> {
+    order.iter().enumerate().map(|(i, c)| (*c, i)).collect()
+}
+
+/// Hands that share a set of classes, with the dealt-weighted EV lost by playing each
+struct ClassSetCost {
+    classes: Vec<HandClass>,
+    loss: Vec<f64>,
+}
+
+fn order_cost(order: &[HandClass], sets: &[ClassSetCost]) -> f64 {
+    let positions = positions_of(order);
+    sets.iter()
+        .map(|set| {
+            set.classes.iter().enumerate()
+                .filter_map(|(i, c)| positions.get(c).map(|p| (*p, set.loss[i])))
+                .min_by_key(|(p, _)| *p)
+                .map(|(_, loss)| loss)
+                .unwrap_or(0.0)
+        })
+        .sum()
+}
+
+/// Order the classes to minimise the return lost against the optimal holds. Which class
+/// a hand plays depends only on the set of classes it offers, so hands are first pooled
+/// by class set. The start order ranks each class by its EV advantage over the classes
+/// it competes with, then single-class moves are applied while any lowers the loss.
+/// Classes whose relative order costs nothing either way end up in `rank` order.
+fn derive_order(strategy: &StrategyFile, options: &[Vec<(HandClass, u8, f64)>]) -> Vec<HandClass> {
+    let mut pooled: HashMap<Vec<HandClass>, Vec<f64>> = HashMap::new();
+    let mut advantage: HashMap<HandClass, f64> = HashMap::new();
+    for (entry, opts) in strategy.entries.iter().zip(options) {
+        let m = entry.multiplicity as f64;
+        let optimal = entry.evs[entry.best_hold as usize];
+        let classes: Vec<HandClass> = opts.iter().map(|(c, _, _)| *c).collect();
+        let loss = pooled.entry(classes).or_insert_with(|| vec![0.0; opts.len()]);
+        for (i, (class, _, ev)) in opts.iter().enumerate() {
+            loss[i] += m * (optimal - ev);
+            for (_, _, other) in opts {
+                *advantage.entry(*class).or_insert(0.0) += m * (ev - other);
+            }
+        }
+    }
+    let sets: Vec<ClassSetCost> = pooled.into_iter()
+        .map(|(classes, loss)| ClassSetCost { classes, loss })
+        .collect();
+
+    let mut order: Vec<HandClass> = advantage.keys().cloned().collect();
+    order.sort_by(|a, b| advantage[b].total_cmp(&advantage[a]).then(a.cmp(b)));
+
+    let mut cost = order_cost(&order, &sets);
+    loop {
+        let mut best: Option<(usize, usize, f64)> = None;
+        for from in 0..order.len() {
+            for to in 0..order.len() {
+                if from == to {
+                    continue;
+                }
+                let mut candidate = order.clone();
+                let class = candidate.remove(from);
+                candidate.insert(to, class);
+                let c = order_cost(&candidate, &sets);
+                if c < best.map_or(cost, |(_, _, b)| b) - 1e-9 {
+                    best = Some((from, to, c));
+                }
+            }
+        }
+        let Some((from, to, c)) = best else { break };
+        let class = order.remove(from);
+        order.insert(to, class);
+        cost = c;
+    }
+
+    let mut tidied = true;
+    while tidied {
+        tidied = false;
+        for i in 1..order.len() {
+            if order[i] < order[i - 1] {
+                order.swap(i, i - 1);
+                if order_cost(&order, &sets) <= cost + 1e-9 {
+                    tidied = true;
+                } else {
+                    order.swap(i, i - 1);
+                }
+            }
+        }
+    }
+    order
+}
+
+/// Why a chart hold loses to the optimal one: cards it throws away that would have
+/// helped it (same suit as a suited hold, or filling a straight around it)
+fn penalty_kind(entry: &StrategyEntry, mask: u8) -> &'static str {
+    let held = entry.held(mask);
+    let discards: Vec<Card> = entry.held(!mask & 0x1f);
+    if held.is_empty() {
+        return "exception";
+    }
+    let flush = held.iter().all(|c| c.suit == held[0].suit)
+        && discards.iter().any(|c| c.suit == held[0].suit);
+
+    let values = |v: u8| if v == 14 { vec![1, 14] } else { vec![v] };
+    let held_values: Vec<u8> = held.iter().map(|c| c.rank.value()).collect();
+    let distinct = held_values.iter().collect::<HashSet<_>>().len() == held_values.len();
+    let straight = distinct && held.len() < 5 && discards.iter().any(|d| {
+        let d_value = d.rank.value();
+        !held_values.contains(&d_value) && (1..=10u8).any(|low| {
+            let in_window = |v: u8| values(v).into_iter().any(|x| x >= low && x < low + 5);
+            in_window(d_value) && held_values.iter().all(|&v| in_window(v))
+        })
+    });
+
+    match (flush, straight) {
+        (true, true) => "flush and straight penalty",
+        (true, false) => "flush penalty",
+        (false, true) => "straight penalty",
+        (false, false) => "exception",
+    }
+}
+
+#[derive(Serialize)]
+pub struct ChartLine {
+    pub position: usize,
+    pub class: HandClass,
+    pub name: String,
+    /// Share of dealt hands played by this line
+    pub frequency: f64,
+}
+
+#[derive(Serialize)]
+pub struct ExceptionExample {
+    pub key: String,
+    pub hand: String,
+    pub chart_hold: String,
+    pub optimal_hold: String,
+    pub ev_loss: f64,
+}
+
+/// Hands where the chart's hold of one class loses to an optimal hold of another
+#[derive(Serialize)]
+pub struct ExceptionGroup {
+    pub chart_class: HandClass,
+    /// None when the optimal hold fits no class
+    pub optimal_class: Option<HandClass>,
+    pub kind: &'static str,
+    pub canonical_hands: usize,
+    pub frequency: f64,
+    /// Return lost to these hands, as a fraction of the bet
+    pub return_cost: f64,
+    pub examples: Vec<ExceptionExample>,
+}
+
+#[derive(Serialize)]
+pub struct ChartReport {
+    pub paytable_id: String,
+    pub family: GameFamily,
+    pub optimal_return: f64,
+    pub chart_return: f64,
+    pub chart: Vec<ChartLine>,
+    pub exceptions: Vec<ExceptionGroup>,
+}
+
+/// Play every hand of the strategy by the chart and collect the result
+pub fn play_chart(strategy: &StrategyFile, family: GameFamily, scheme: ClassScheme, options: &[Vec<(HandClass, u8, f64)>],
+    order: &[HandClass]) -> ChartReport
+{
+    let positions = positions_of(order);
+    let total = strategy.total_hands() as f64;
+    let mut played = vec![0.0; order.len()];
+    let mut chart_total = 0.0;
+    let mut groups: HashMap<(HandClass, Option<HandClass>, &'static str), ExceptionGroup> = HashMap::new();
+
+    for (entry, opts) in strategy.entries.iter().zip(options) {
+        let m = entry.multiplicity as f64;
+        let (class, mask, ev) = chart_hold(&positions, opts, entry);
+        if let Some(&p) = positions.get(&class) {
+            played[p] += m;
+        }
+        chart_total += m * ev;
+
+        let optimal = entry.evs[entry.best_hold as usize];
+        let loss = optimal - ev;
+        if loss <= entry.step / 2.0 {
+            continue;
+        }
+        let optimal_class = classify_hold(&entry.held(entry.best_hold), scheme);
+        let kind = penalty_kind(entry, mask);
+        let group = groups.entry((class, optimal_class, kind)).or_insert_with(|| ExceptionGroup {
+            chart_class: class,
+            optimal_class,
+            kind,
+            canonical_hands: 0,
+            frequency: 0.0,
+            return_cost: 0.0,
+            examples: Vec::new(),
+        });
+        group.canonical_hands += 1;
+        group.frequency += m / total;
+        group.return_cost += m * loss / total;
+        group.examples.push(ExceptionExample {
+            key: entry.key.clone(),
+            hand: entry.describe_hold(entry.best_hold),
+            chart_hold: entry.held(mask).iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "),
+            optimal_hold: entry.held(entry.best_hold).iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "),
+            ev_loss: loss,
+        });
+    }
+
+    let mut exceptions: Vec<ExceptionGroup> = groups.into_values()
+        .map(|mut group| {
+            group.examples.sort_by(|a, b| b.ev_loss.total_cmp(&a.ev_loss).then(a.key.cmp(&b.key)));
+            group.examples.truncate(EXAMPLES_PER_GROUP);
+            group
+        })
+        .collect();
+    exceptions.sort_by(|a, b| b.return_cost.total_cmp(&a.return_cost)
+        .then(a.chart_class.cmp(&b.chart_class))
+        .then(a.optimal_class.cmp(&b.optimal_class))
+        .then(a.kind.cmp(b.kind)));
+
+    ChartReport {
+        paytable_id: strategy.paytable_id.clone(),
+        family,
+        optimal_return: strategy.optimal_return(),
+        chart_return: chart_total / total,
+        chart: order.iter().zip(&played).enumerate()
+            .map(|(i, (class, m))| ChartLine { position: i + 1, class: *class, name: class.name(), frequency: m / total })
+            .collect(),
+        exceptions,
+    }
+}
+
+pub fn class_label(class: Option<HandClass>) -> String {
+    class.map_or_else(|| "(unclassified hold)".to_string(), |c| c.name())
+}
+
+fn exception_table(md: &mut String, groups: &[&ExceptionGroup], with_kind: bool) {
+    if with_kind {
+        md.push_str("| Chart says | Hold instead | Penalty | Hands | Frequency | Return cost | Example |\n");
+        md.push_str("|------------|--------------|---------|------:|----------:|------------:|---------|\n");
+    } else {
+        md.push_str("| Chart says | Hold instead | Hands | Frequency | Return cost | Example |\n");
+        md.push_str("|------------|--------------|------:|----------:|------------:|---------|\n");
+    }
+    for g in groups.iter().take(MARKDOWN_EXCEPTION_LIMIT) {
+        let example = g.examples.first().map_or(String::new(), |e| e.hand.clone());
+        let kind = if with_kind { format!(" {} |", g.kind) } else { String::new() };
+        md.push_str(&format!("| {} | {} |{} {} | {:.6}% | {:.6}% | {} |\n",
+            g.chart_class.name(), class_label(g.optimal_class), kind, g.canonical_hands,
+            g.frequency * 100.0, g.return_cost * 100.0, example));
+    }
+    if groups.len() > MARKDOWN_EXCEPTION_LIMIT {
+        md.push_str(&format!("\n{} smaller groups omitted; see the JSON report.\n", groups.len() - MARKDOWN_EXCEPTION_LIMIT));
+    }
+}
+
+pub fn chart_markdown(report: &ChartReport, title: &str) -> String {
+    let mut md = format!("# {}: {}\n\n", title, report.paytable_id);
+    md.push_str(&format!("Game family: {:?}  \n", report.family));
+    md.push_str(&format!("Optimal return: {:.4}%  \nChart return: {:.4}% (costs {:.4}%)\n\n",
+        report.optimal_return * 100.0, report.chart_return * 100.0,
+        (report.optimal_return - report.chart_return) * 100.0));
+    md.push_str("Hold the first line that applies; examples show the optimal hold in brackets.\n\n");
+
+    md.push_str("## Chart\n\n| # | Hold | Played |\n|--:|------|-------:|\n");
+    for line in &report.chart {
+        md.push_str(&format!("| {} | {} | {:.4}% |\n", line.position, line.name, line.frequency * 100.0));
+    }
+
+    let (penalties, others): (Vec<&ExceptionGroup>, Vec<&ExceptionGroup>) =
+        report.exceptions.iter().partition(|g| g.kind != "exception");
+    md.push_str("\n## Penalty cards\n\n");
+    if penalties.is_empty() {
+        md.push_str("None.\n");
+    } else {
+        exception_table(&mut md, &penalties, true);
+    }
+    md.push_str("\n## Exceptions\n\n");
+    if others.is_empty() {
+        md.push_str("None.\n");
+    } else {
+        exception_table(&mut md, &others, false);
+    }
+    md
+}
+
+pub fn write_report(report: &impl Serialize, paytable_id: &str, markdown: &str, output_dir: &str, prefix: &str) -> Result<(String, String), String> {
+    fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create {}: {}", output_dir, e))?;
+    let stem = format!("{}_{}", prefix, paytable_id.replace('-', "_"));
+    let md_path = Path::new(output_dir).join(format!("{}.md", stem));
+    let json_path = Path::new(output_dir).join(format!("{}.json", stem));
+    let json = serde_json::to_string_pretty(report).map_err(|e| format!("Failed to serialize report: {}", e))?;
+    fs::write(&md_path, markdown).map_err(|e| format!("Failed to write {}: {}", md_path.display(), e))?;
+    fs::write(&json_path, json).map_err(|e| format!("Failed to write {}: {}", json_path.display(), e))?;
+    Ok((md_path.to_string_lossy().to_string(), json_path.to_string_lossy().to_string()))
+}
+
+pub fn run_chart(strategy_path: &str, family_name: Option<&str>, output_dir: &str) {
+    let (strategy, family, scheme) = match read_strategy(strategy_path)
+        .and_then(|s| resolve_family(&s, family_name).map(|(f, scheme)| (s, f, scheme)))
+    {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("Loaded {} canonical hands for {} ({:?})", strategy.entries.len(), strategy.paytable_id, family);
+
+    let options: Vec<Vec<(HandClass, u8, f64)>> = strategy.entries.iter().map(|e| class_options(e, scheme)).collect();
+    let order = derive_order(&strategy, &options);
+    let mut report = play_chart(&strategy, family, scheme, &options, &order);
+    // Lines that never get played don't change the chart
+    report.chart.retain(|line| line.frequency > 0.0);
+    for (i, line) in report.chart.iter_mut().enumerate() {
+        line.position = i + 1;
+    }
+
+    println!("Optimal return: {:.4}%  Chart return: {:.4}%\n", report.optimal_return * 100.0, report.chart_return * 100.0);
+    println!("{}", report.chart.iter().map(|l| l.name.as_str()).collect::<Vec<_>>().join(" > "));
+
+    let markdown = chart_markdown(&report, "Strategy chart");
+    match write_report(&report, &report.paytable_id, &markdown, output_dir, "chart") {
+        Ok((md, json)) => println!("\nWrote {} and {}", md, json),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
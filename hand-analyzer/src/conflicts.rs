@@ -0,0 +1,212 @@
+// Strategy conflicts from hold EVs.
+//
+// A hand's decision is between the best holds of its two best classes (see
+// chart::class_options). Hands are pooled by that class pair, and each pair reports how
+// often either class wins, by how much, and how often it is dealt. A pair where one
+// class always wins by more than the close margin is dominated: the choice never
+// needs thought, so it is left out of the Markdown report.
+
+use super::*;
+use crate::chart::{class_options, write_report};
+use crate::family::resolve_family;
+use crate::strategy::{read_strategy, StrategyEntry};
+
+/// EV difference under which a decision counts as close
+const DEFAULT_CLOSE_MARGIN: f64 = 0.02;
+const EXAMPLES_PER_PAIR: usize = 3;
+
+/// Dealt-weighted percentiles of `first` EV minus `second` EV
+#[derive(Serialize)]
+pub struct MarginSpread {
+    pub min: f64,
+    pub p10: f64,
+    pub median: f64,
+    pub p90: f64,
+    pub max: f64,
+}
+
+#[derive(Serialize)]
+pub struct ConflictExample {
+    pub key: String,
+    /// The hand with the optimal hold in brackets
+    pub hand: String,
+    pub first_hold: String,
+    pub second_hold: String,
+    pub margin: f64,
+}
+
+#[derive(Serialize)]
+pub struct ClassConflict {
+    /// The class that wins more of the dealt hands
+    pub first: HandClass,
+    pub second: HandClass,
+    pub canonical_hands: usize,
+    /// Share of dealt hands decided between these two classes
+    pub frequency: f64,
+    /// Shares of this pair's dealt hands each class wins, and ties within the EV step
+    pub first_wins: f64,
+    pub second_wins: f64,
+    pub ties: f64,
+    /// Share of this pair's dealt hands decided by less than the close margin
+    pub close: f64,
+    pub margin: MarginSpread,
+    pub dominated: bool,
+    /// The closest decisions
+    pub examples: Vec<ConflictExample>,
+}
+
+#[derive(Serialize)]
+pub struct ConflictReport {
+    pub paytable_id: String,
+    pub family: GameFamily,
+    pub close_margin: f64,
+    pub conflicts: Vec<ClassConflict>,
+}
+
+/// One hand's decision: (first class EV - second class EV, dealt weight, entry, masks)
+type Decision<'a> = (f64, f64, &'a StrategyEntry, u8, u8);
+
+fn weighted_percentile(sorted: &[(f64, f64)], total: f64, q: f64) -> f64 {
+    let mut seen = 0.0;
+    for &(value, weight) in sorted {
+        seen += weight;
+        if seen >= q * total {
+            return value;
+        }
+    }
+    sorted.last().map_or(0.0, |&(value, _)| value)
+}
+
+fn summarize(first: HandClass, second: HandClass, decisions: &mut [Decision], dealt: f64, close_margin: f64) -> ClassConflict {
+    let weight: f64 = decisions.iter().map(|d| d.1).sum();
+    let share = |f: &dyn Fn(&Decision) -> bool| decisions.iter().filter(|d| f(d)).fold(0.0, |sum, d| sum + d.1) / weight;
+    let first_wins = share(&|d| d.0 > d.2.step / 2.0);
+    let second_wins = share(&|d| d.0 < -d.2.step / 2.0);
+    let close = share(&|d| d.0.abs() < close_margin);
+
+    let mut margins: Vec<(f64, f64)> = decisions.iter().map(|d| (d.0, d.1)).collect();
+    margins.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let margin = MarginSpread {
+        min: margins[0].0,
+        p10: weighted_percentile(&margins, weight, 0.1),
+        median: weighted_percentile(&margins, weight, 0.5),
+        p90: weighted_percentile(&margins, weight, 0.9),
+        max: margins[margins.len() - 1].0,
+    };
+    let dominated = second_wins == 0.0 && margin.min >= close_margin;
+
+    decisions.sort_by(|a, b| a.0.abs().total_cmp(&b.0.abs()).then(a.2.key.cmp(&b.2.key)));
+    let examples = decisions.iter().take(EXAMPLES_PER_PAIR)
+        .map(|&(margin, _, entry, first_mask, second_mask)| ConflictExample {
+            key: entry.key.clone(),
+            hand: entry.describe_hold(entry.best_hold),
+            first_hold: entry.held(first_mask).iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "),
+            second_hold: entry.held(second_mask).iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "),
+            margin,
+        })
+        .collect();
+
+    ClassConflict {
+        first,
+        second,
+        canonical_hands: decisions.len(),
+        frequency: weight / dealt,
+        first_wins,
+        second_wins,
+        ties: (1.0 - first_wins - second_wins).max(0.0),
+        close,
+        margin,
+        dominated,
+        examples,
+    }
+}
+
+fn conflicts_markdown(report: &ConflictReport) -> String {
+    let mut md = format!("# Strategy conflicts: {}\n\n", report.paytable_id);
+    md.push_str(&format!("Game family: {:?}  \nClose decision: EV margin under {}\n\n", report.family, report.close_margin));
+    md.push_str("Each hand is decided between the best holds of its two best hand classes. Margins are the\n");
+    md.push_str("first class's EV minus the second's, over the dealt hands (negative when the second wins).\n\n");
+
+    let (contested, dominated): (Vec<&ClassConflict>, Vec<&ClassConflict>) =
+        report.conflicts.iter().partition(|c| !c.dominated);
+    md.push_str("## Contested decisions\n\n");
+    md.push_str("| First | Second | Hands | Frequency | First wins | Second wins | Ties | Close | Margin p10 / median / p90 | Closest example |\n");
+    md.push_str("|-------|--------|------:|----------:|-----------:|------------:|-----:|------:|---------------------------|-----------------|\n");
+    for c in &contested {
+        let example = c.examples.first().map_or(String::new(), |e| e.hand.clone());
+        md.push_str(&format!("| {} | {} | {} | {:.4}% | {:.1}% | {:.1}% | {:.1}% | {:.1}% | {:.4} / {:.4} / {:.4} | {} |\n",
+            c.first.name(), c.second.name(), c.canonical_hands, c.frequency * 100.0, c.first_wins * 100.0,
+            c.second_wins * 100.0, c.ties * 100.0, c.close * 100.0, c.margin.p10, c.margin.median, c.margin.p90, example));
+    }
+
+    let dominated_frequency: f64 = dominated.iter().map(|c| c.frequency).sum();
+    md.push_str(&format!("\n{} dominated pairs ({:.4}% of dealt hands) are in the JSON report.\n",
+        dominated.len(), dominated_frequency * 100.0));
+    md
+}
+
+pub fn run_conflicts(strategy_path: &str, family_name: Option<&str>, close_margin: Option<f64>, output_dir: &str) {
+    let (strategy, family, scheme) = match read_strategy(strategy_path)
+        .and_then(|s| resolve_family(&s, family_name).map(|(f, scheme)| (s, f, scheme)))
+    {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("Loaded {} canonical hands for {} ({:?})", strategy.entries.len(), strategy.paytable_id, family);
+    let close_margin = close_margin.unwrap_or(DEFAULT_CLOSE_MARGIN);
+
+    let mut pairs: HashMap<(HandClass, HandClass), Vec<Decision>> = HashMap::new();
+    for entry in &strategy.entries {
+        let mut options = class_options(entry, scheme);
+        if options.len() < 2 {
+            continue;
+        }
+        options.sort_by(|a, b| b.2.total_cmp(&a.2).then(a.0.cmp(&b.0)));
+        let (a, b) = (options[0], options[1]);
+        // Pool by the unordered pair, with the margin taken from the lower class's side
+        let ((first, first_mask, first_ev), (second, second_mask, second_ev)) = if a.0 < b.0 { (a, b) } else { (b, a) };
+        pairs.entry((first, second)).or_default()
+            .push((first_ev - second_ev, entry.multiplicity as f64, entry, first_mask, second_mask));
+    }
+
+    let dealt = strategy.total_hands() as f64;
+    let mut conflicts: Vec<ClassConflict> = pairs.into_iter()
+        .map(|((first, second), mut decisions)| {
+            // Lead with the class that wins more of the dealt hands
+            let first_weight: f64 = decisions.iter().filter(|d| d.0 >= 0.0).map(|d| d.1).sum();
+            let second_weight: f64 = decisions.iter().filter(|d| d.0 < 0.0).map(|d| d.1).sum();
+            if second_weight > first_weight {
+                for d in decisions.iter_mut() {
+                    *d = (-d.0, d.1, d.2, d.4, d.3);
+                }
+                summarize(second, first, &mut decisions, dealt, close_margin)
+            } else {
+                summarize(first, second, &mut decisions, dealt, close_margin)
+            }
+        })
+        .collect();
+    conflicts.sort_by(|a, b| (b.frequency * b.close).total_cmp(&(a.frequency * a.close))
+        .then(b.frequency.total_cmp(&a.frequency))
+        .then((a.first, a.second).cmp(&(b.first, b.second))));
+
+    let report = ConflictReport { paytable_id: strategy.paytable_id.clone(), family, close_margin, conflicts };
+    let contested: Vec<&ClassConflict> = report.conflicts.iter().filter(|c| !c.dominated).collect();
+    println!("{} class pairs decide hands; {} are contested (close margin {})\n",
+        report.conflicts.len(), contested.len(), close_margin);
+    for c in contested.iter().take(15) {
+        println!("  {:<32} vs {:<32} {:.4}% dealt, {:.1}% close",
+            c.first.name(), c.second.name(), c.frequency * 100.0, c.close * 100.0);
+    }
+
+    let markdown = conflicts_markdown(&report);
+    match write_report(&report, &report.paytable_id, &markdown, output_dir, "conflicts") {
+        Ok((md, json)) => println!("\nWrote {} and {}", md, json),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
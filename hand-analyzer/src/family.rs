@@ -0,0 +1,150 @@
+// Game families, as in vp_calculator's GameFamily, and the hand classes each uses.
+
+use super::*;
+use crate::strategy::StrategyFile;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum GameFamily {
+    // Standard 52-card games
+    JacksOrBetter,
+    TensOrBetter,
+    BonusPoker,
+    BonusPokerDeluxe,
+    DoubleBonus,
+    SuperDoubleBonus,
+    DoubleDoubleBonus,
+    TripleDoubleBonus,
+    WhiteHotAces,
+    AcesAndFaces,
+    AcesAndEights,
+    TripleBonus,
+    SuperAces,
+    BonusPokerPlus,
+    DoubleJackpot,
+    DoubleDoubleJackpot,
+    SuperDoubleDoubleBonus,
+    TripleBonusPlus,
+    AllAmerican,
+    TripleTripleBonus,
+    RoyalAcesBonus,
+    AcesBonus,
+    BonusAcesAndFaces,
+    DDBonusAcesAndFaces,
+    DoubleDoubleBonusPlus,
+    // Wild card games (52-card with deuces wild)
+    DeucesWild,
+    LooseDeuces,
+    DoubleDeuces,
+    TripleDeuces,
+    DeucesWildBonusPoker,
+    DoubleBonusDeucesWild,
+    SuperBonusDeucesWild,
+    DeluxeDeucesWild,
+    DeucesWild44,
+    DeucesJokerWild,
+    // Joker games (53-card deck)
+    JokerPokerKings,
+    JokerPokerTwoPair,
+    DoubleJoker,
+}
+
+/// Catalog id prefixes, most specific first
+const ID_PREFIXES: &[(&str, GameFamily)] = &[
+    ("jacks-or-better-", GameFamily::JacksOrBetter),
+    ("tens-or-better-", GameFamily::TensOrBetter),
+    ("bonus-poker-deluxe-", GameFamily::BonusPokerDeluxe),
+    ("bonus-poker-plus-", GameFamily::BonusPokerPlus),
+    ("bonus-poker-", GameFamily::BonusPoker),
+    ("double-bonus-deuces-", GameFamily::DoubleBonusDeucesWild),
+    ("double-bonus-", GameFamily::DoubleBonus),
+    ("super-double-double-bonus-", GameFamily::SuperDoubleDoubleBonus),
+    ("super-double-bonus-", GameFamily::SuperDoubleBonus),
+    ("double-double-bonus-", GameFamily::DoubleDoubleBonus),
+    ("triple-double-bonus-", GameFamily::TripleDoubleBonus),
+    ("triple-triple-bonus-", GameFamily::TripleTripleBonus),
+    ("white-hot-aces-", GameFamily::WhiteHotAces),
+    ("aces-and-faces-", GameFamily::AcesAndFaces),
+    ("aces-and-eights-", GameFamily::AcesAndEights),
+    ("triple-bonus-plus-", GameFamily::TripleBonusPlus),
+    ("triple-bonus-", GameFamily::TripleBonus),
+    ("super-aces-", GameFamily::SuperAces),
+    ("double-double-jackpot-", GameFamily::DoubleDoubleJackpot),
+    ("double-jackpot-", GameFamily::DoubleJackpot),
+    ("all-american-", GameFamily::AllAmerican),
+    ("royal-aces-bonus-", GameFamily::RoyalAcesBonus),
+    ("aces-bonus-", GameFamily::AcesBonus),
+    ("bonus-aces-faces-", GameFamily::BonusAcesAndFaces),
+    ("ddb-aces-faces-", GameFamily::DDBonusAcesAndFaces),
+    ("ddb-plus-", GameFamily::DoubleDoubleBonusPlus),
+    ("deuces-wild-44-", GameFamily::DeucesWild44),
+    ("deuces-wild-bonus-", GameFamily::DeucesWildBonusPoker),
+    ("deuces-wild-", GameFamily::DeucesWild),
+    ("loose-deuces-", GameFamily::LooseDeuces),
+    ("double-deuces-wild-", GameFamily::DoubleDeuces),
+    ("triple-deuces-wild-", GameFamily::TripleDeuces),
+    ("super-bonus-deuces-", GameFamily::SuperBonusDeucesWild),
+    ("deluxe-deuces-wild-", GameFamily::DeluxeDeucesWild),
+    ("deuces-joker-wild-", GameFamily::DeucesJokerWild),
+    ("joker-poker-kings-", GameFamily::JokerPokerKings),
+    ("joker-poker-two-pair-", GameFamily::JokerPokerTwoPair),
+    ("double-joker-", GameFamily::DoubleJoker),
+];
+
+/// How a family's holds are classified
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClassScheme {
+    /// Jacks or Better classes, with the lowest paying pair
+    JacksOrBetter(Rank),
+    /// Jacks or Better classes split where quad bonuses and kickers change the play
+    Bonus,
+    Deuces,
+    /// Joker classes; kings or aces pay when `high_pairs` is set
+    Joker { high_pairs: bool },
+}
+
+impl GameFamily {
+    /// Family of a catalog paytable id (the strategy file's name without "strategy_")
+    pub fn from_paytable_id(id: &str) -> Option<GameFamily> {
+        let id = id.strip_prefix("www-").unwrap_or(id);
+        ID_PREFIXES.iter().find(|(prefix, _)| id.starts_with(prefix)).map(|(_, family)| *family)
+    }
+
+    /// Family by its vp_calculator name ("DoubleDoubleBonus"), ignoring case
+    pub fn from_name(name: &str) -> Option<GameFamily> {
+        ID_PREFIXES.iter()
+            .map(|(_, family)| *family)
+            .find(|family| format!("{:?}", family).eq_ignore_ascii_case(name))
+    }
+
+    pub fn scheme(self) -> Option<ClassScheme> {
+        use GameFamily::*;
+        match self {
+            JacksOrBetter => Some(ClassScheme::JacksOrBetter(Rank::Jack)),
+            TensOrBetter => Some(ClassScheme::JacksOrBetter(Rank::Ten)),
+            DeucesWild | LooseDeuces | DoubleDeuces | TripleDeuces | DeucesWildBonusPoker
+                | DoubleBonusDeucesWild | SuperBonusDeucesWild | DeluxeDeucesWild | DeucesWild44 => Some(ClassScheme::Deuces),
+            JokerPokerKings => Some(ClassScheme::Joker { high_pairs: true }),
+            JokerPokerTwoPair | DoubleJoker => Some(ClassScheme::Joker { high_pairs: false }),
+            // Deuces and jokers both wild: no classes yet
+            DeucesJokerWild => None,
+            _ => Some(ClassScheme::Bonus),
+        }
+    }
+}
+
+/// The family named on the command line, else the one the strategy's paytable id
+/// belongs to, checked against the strategy's deck
+pub fn resolve_family(strategy: &StrategyFile, name: Option<&str>) -> Result<(GameFamily, ClassScheme), String> {
+    let family = match name {
+        Some(name) => GameFamily::from_name(name).ok_or_else(|| format!("Unknown game family '{}'", name))?,
+        None => GameFamily::from_paytable_id(&strategy.paytable_id).ok_or_else(|| {
+            format!("Can't tell the game family of '{}'; pass --family", strategy.paytable_id)
+        })?,
+    };
+    let scheme = family.scheme().ok_or_else(|| format!("No hand classes for {:?} yet", family))?;
+    let joker_scheme = matches!(scheme, ClassScheme::Joker { .. });
+    if joker_scheme != (strategy.jokers > 0) {
+        return Err(format!("{:?} doesn't match {} ({} jokers in the deck)", family, strategy.paytable_id, strategy.jokers));
+    }
+    Ok((family, scheme))
+}
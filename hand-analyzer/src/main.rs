@@ -1,8 +1,23 @@
 use std::collections::{HashMap, HashSet, BTreeSet};
+use std::fmt;
+
+use serde::Serialize;
+
+mod chart;
+mod conflicts;
+mod family;
+mod score;
+mod strategy;
+mod wild;
+
+use family::{ClassScheme, GameFamily};
+use wild::{Wilds, WildClass};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum Rank {
     Two = 2, Three, Four, Five, Six, Seven, Eight, Nine, Ten, Jack, Queen, King, Ace,
+    /// Joker of a joker poker deck; not one of `Rank::all`
+    Joker,
 }
 
 impl Rank {
@@ -22,29 +37,30 @@ impl Rank {
         *self as u8
     }
 
-    fn to_char(&self) -> char {
+    fn to_char(self) -> char {
         match self {
             Rank::Two => '2', Rank::Three => '3', Rank::Four => '4', Rank::Five => '5',
             Rank::Six => '6', Rank::Seven => '7', Rank::Eight => '8', Rank::Nine => '9',
             Rank::Ten => 'T', Rank::Jack => 'J', Rank::Queen => 'Q', Rank::King => 'K',
-            Rank::Ace => 'A',
+            Rank::Ace => 'A', Rank::Joker => 'W',
         }
     }
 }
 
-// Canonical suits: a, b, c, d (assigned in order of first appearance)
+// Canonical suits: a, b, c, d (assigned in order of first appearance); jokers are "Ww"
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum CanonicalSuit {
-    A = 0, B, C, D,
+    A = 0, B, C, D, W,
 }
 
 impl CanonicalSuit {
-    fn to_char(&self) -> char {
+    fn to_char(self) -> char {
         match self {
             CanonicalSuit::A => 'a',
             CanonicalSuit::B => 'b',
             CanonicalSuit::C => 'c',
             CanonicalSuit::D => 'd',
+            CanonicalSuit::W => 'w',
         }
     }
 }
@@ -60,8 +76,11 @@ impl Card {
         Card { rank, suit }
     }
 
-    fn to_string(&self) -> String {
-        format!("{}{}", self.rank.to_char(), self.suit.to_char())
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.rank.to_char(), self.suit.to_char())
     }
 }
 
@@ -77,10 +96,6 @@ impl Hand {
         Hand { cards }
     }
 
-    fn to_string(&self) -> String {
-        self.cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" ")
-    }
-
     fn ranks(&self) -> Vec<Rank> {
         self.cards.iter().map(|c| c.rank).collect()
     }
@@ -106,20 +121,37 @@ impl Hand {
     }
 }
 
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.cards.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 enum HandClass {
+    RoyalFlush,            // 1
+    StraightFlush,         // 2
+    /// Bonus games: quads with the kicker kept
+    FourOfAKindWithKicker,
     FourOfAKind,           // 3
     FourToRoyalFlush,      // 4
     FullHouse,             // 5
     Flush,                 // 6
+    /// Bonus games: trips split by the quad bonus they draw to
+    ThreeAcesWithKicker,
+    Three2To4WithKicker,
+    ThreeAces,
+    Three2To4,
     ThreeOfAKind,          // 7
     Straight,              // 8
     FourToStraightFlush,   // 9
     TwoPair,               // 10
+    PairOfAces,
     HighPair,              // 11
     ThreeToRoyalFlush,     // 12
     FourToFlush,           // 13
     UnsuitedTJQK,          // 14
+    LowPair2To4,
     LowPair,               // 15
     FourToOutsideStraight, // 16
     ThreeToSFType1,        // 17
@@ -128,6 +160,9 @@ enum HandClass {
     SuitedKQOrKJ,          // 20
     SuitedAKAQAJ,          // 21
     FourToInsideStraight3High, // 22
+    /// Bonus games, where straights and flushes pay more
+    FourToInsideStraightLow,
+    ThreeToFlush,
     ThreeToSFType2,        // 23
     UnsuitedJQK,           // 24
     UnsuitedJQ,            // 25
@@ -135,18 +170,83 @@ enum HandClass {
     TwoUnsuitedHighKing,   // 27
     SuitedTQ,              // 28
     TwoUnsuitedHighAce,    // 29
+    /// Tens or Better, where tens are high cards
+    SuitedTA,
+    TwoUnsuitedHighQueen,
+    TOnly,
     JOnly,                 // 30
     SuitedTK,              // 31
     QOnly,                 // 32
     KOnly,                 // 33
     AOnly,                 // 34
     ThreeToSFType3,        // 35
+    /// Deuces wild: deuces held and what the other cards make with them
+    Deuces(Wilds, WildClass),
+    /// Joker poker: jokers held and what the other cards make with them
+    Joker(Wilds, WildClass),
     Garbage,               // 36
 }
 
 impl HandClass {
+    /// Every class: the Jacks or Better classes in `rank` order, then the bonus, deuces
+    /// and joker classes
+    fn all() -> Vec<HandClass> {
+        use HandClass::*;
+        let mut classes = vec![
+            RoyalFlush, StraightFlush, FourOfAKind, FourToRoyalFlush, FullHouse, Flush,
+            ThreeOfAKind, Straight, FourToStraightFlush, TwoPair, HighPair, ThreeToRoyalFlush,
+            FourToFlush, UnsuitedTJQK, LowPair, FourToOutsideStraight, ThreeToSFType1, SuitedQJ,
+            FourToInsideStraight4High, SuitedKQOrKJ, SuitedAKAQAJ, FourToInsideStraight3High,
+            ThreeToSFType2, UnsuitedJQK, UnsuitedJQ, SuitedTJ, TwoUnsuitedHighKing, SuitedTQ,
+            TwoUnsuitedHighAce, JOnly, SuitedTK, QOnly, KOnly, AOnly, ThreeToSFType3, Garbage,
+            FourOfAKindWithKicker, ThreeAcesWithKicker, Three2To4WithKicker, ThreeAces, Three2To4,
+            PairOfAces, LowPair2To4, FourToInsideStraightLow, ThreeToFlush, SuitedTA,
+            TwoUnsuitedHighQueen, TOnly,
+        ];
+        for wilds in Wilds::all() {
+            for class in WildClass::all() {
+                classes.push(Deuces(wilds, class));
+                classes.push(Joker(wilds, class));
+            }
+        }
+        classes
+    }
+
+    /// The classes a scheme's classifier produces, in `Ord` order (`rank` order for
+    /// Jacks or Better)
+    fn of_scheme(scheme: ClassScheme) -> Vec<HandClass> {
+        let mut classes: Vec<HandClass> = HandClass::all().into_iter()
+            .filter(|c| match (scheme, c) {
+                (_, HandClass::Garbage) => true,
+                (ClassScheme::Deuces, c) => matches!(c, HandClass::Deuces(..)),
+                (ClassScheme::Joker { .. }, c) => matches!(c, HandClass::Joker(..)),
+                (ClassScheme::JacksOrBetter(min_pair), c) => c.rank() != u8::MAX || (min_pair == Rank::Ten
+                    && matches!(c, HandClass::SuitedTA | HandClass::TwoUnsuitedHighQueen | HandClass::TOnly)),
+                (ClassScheme::Bonus, c) => !matches!(c, HandClass::Deuces(..) | HandClass::Joker(..)),
+            })
+            .collect();
+        classes.sort();
+        classes
+    }
+
+    /// Class by identifier ("SuitedQJ", "Deuces(Two, FourToRoyalFlush)") or display name
+    /// ("Suited QJ", "Two deuces: 4 to a royal flush"), ignoring case
+    fn from_name(name: &str) -> Option<HandClass> {
+        let name = name.trim();
+        HandClass::all().into_iter().find(|c| {
+            c.id().eq_ignore_ascii_case(name) || c.name().eq_ignore_ascii_case(name)
+        })
+    }
+
+    fn id(&self) -> String {
+        format!("{:?}", self)
+    }
+
+    /// Position in the 9/6 Jacks or Better chart; u8::MAX for classes it doesn't use
     fn rank(&self) -> u8 {
         match self {
+            HandClass::RoyalFlush => 1,
+            HandClass::StraightFlush => 2,
             HandClass::FourOfAKind => 3,
             HandClass::FourToRoyalFlush => 4,
             HandClass::FullHouse => 5,
@@ -181,23 +281,33 @@ impl HandClass {
             HandClass::AOnly => 34,
             HandClass::ThreeToSFType3 => 35,
             HandClass::Garbage => 36,
+            _ => u8::MAX,
         }
     }
 
-    fn name(&self) -> &'static str {
-        match self {
+    fn name(&self) -> String {
+        let name = match self {
+            HandClass::RoyalFlush => "Royal flush",
+            HandClass::StraightFlush => "Straight flush",
+            HandClass::FourOfAKindWithKicker => "Four of a kind with kicker",
             HandClass::FourOfAKind => "Four of a kind",
             HandClass::FourToRoyalFlush => "4 to a royal flush",
             HandClass::FullHouse => "Full house",
             HandClass::Flush => "Flush",
+            HandClass::ThreeAcesWithKicker => "Three aces with a 2-4 kicker",
+            HandClass::Three2To4WithKicker => "Three 2s-4s with an A-4 kicker",
+            HandClass::ThreeAces => "Three aces",
+            HandClass::Three2To4 => "Three 2s, 3s or 4s",
             HandClass::ThreeOfAKind => "Three of a kind",
             HandClass::Straight => "Straight",
             HandClass::FourToStraightFlush => "4 to a straight flush",
             HandClass::TwoPair => "Two pair",
+            HandClass::PairOfAces => "Pair of aces",
             HandClass::HighPair => "High pair",
             HandClass::ThreeToRoyalFlush => "3 to a royal flush",
             HandClass::FourToFlush => "4 to a flush",
             HandClass::UnsuitedTJQK => "Unsuited TJQK",
+            HandClass::LowPair2To4 => "Pair of 2s, 3s or 4s",
             HandClass::LowPair => "Low pair",
             HandClass::FourToOutsideStraight => "4 to outside straight (0-2 high)",
             HandClass::ThreeToSFType1 => "3 to SF type 1",
@@ -206,6 +316,8 @@ impl HandClass {
             HandClass::SuitedKQOrKJ => "Suited KQ or KJ",
             HandClass::SuitedAKAQAJ => "Suited AK, AQ, or AJ",
             HandClass::FourToInsideStraight3High => "4 to inside straight (3 high)",
+            HandClass::FourToInsideStraightLow => "4 to inside straight (0-2 high)",
+            HandClass::ThreeToFlush => "3 to a flush",
             HandClass::ThreeToSFType2 => "3 to SF type 2",
             HandClass::UnsuitedJQK => "Unsuited JQK",
             HandClass::UnsuitedJQ => "Unsuited JQ",
@@ -213,14 +325,29 @@ impl HandClass {
             HandClass::TwoUnsuitedHighKing => "2 unsuited high (K highest)",
             HandClass::SuitedTQ => "Suited TQ",
             HandClass::TwoUnsuitedHighAce => "2 unsuited high (A highest)",
+            HandClass::SuitedTA => "Suited TA",
+            HandClass::TwoUnsuitedHighQueen => "2 unsuited high (Q or J highest)",
+            HandClass::TOnly => "T only",
             HandClass::JOnly => "J only",
             HandClass::SuitedTK => "Suited TK",
             HandClass::QOnly => "Q only",
             HandClass::KOnly => "K only",
             HandClass::AOnly => "A only",
             HandClass::ThreeToSFType3 => "3 to SF type 3",
+            HandClass::Deuces(wilds, WildClass::WildsOnly) => wilds.deuces_label(),
+            HandClass::Deuces(wilds, class) => return format!("{}: {}", wilds.deuces_label(), class.name()),
+            HandClass::Joker(wilds, WildClass::WildsOnly) => wilds.joker_label(),
+            HandClass::Joker(wilds, class) => return format!("{}: {}", wilds.joker_label(), class.name()),
             HandClass::Garbage => "Garbage",
-        }
+        };
+        name.to_string()
+    }
+}
+
+/// Classes are written by identifier, e.g. "SuitedQJ" or "Deuces(Two, FourToRoyalFlush)"
+impl Serialize for HandClass {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.id())
     }
 }
 
@@ -281,7 +408,8 @@ fn is_inside_straight_draw(ranks: &[Rank]) -> Option<usize> {
         return None;
     }
 
-    if values == vec![2, 3, 4, 14] {
+    // Ace-low: A with three of 2-5
+    if values[3] == 14 && values[2] <= 5 {
         let high_count = ranks.iter().filter(|r| r.is_high()).count();
         return Some(high_count);
     }
@@ -363,7 +491,27 @@ fn sf_draw_type(gaps: usize, high_cards: usize, ranks: &[Rank]) -> Option<u8> {
     None
 }
 
-fn get_hand_classes(hand: &Hand) -> Vec<HandClass> {
+/// Every class a dealt hand offers. Jacks or Better games use the pattern rules below;
+/// the other families collect the classes of the hand's 32 holds.
+fn get_hand_classes(hand: &Hand, family: GameFamily) -> Vec<HandClass> {
+    match family.scheme() {
+        Some(ClassScheme::JacksOrBetter(min_pair)) => jacks_or_better_classes(hand, min_pair),
+        Some(scheme) => {
+            let mut classes: Vec<HandClass> = (0..32u8)
+                .filter_map(|mask| {
+                    let held: Vec<Card> = (0..5).filter(|i| mask & (1 << i) != 0).map(|i| hand.cards[i]).collect();
+                    classify_hold(&held, scheme)
+                })
+                .collect();
+            classes.sort();
+            classes.dedup();
+            classes
+        }
+        None => vec![HandClass::Garbage],
+    }
+}
+
+fn jacks_or_better_classes(hand: &Hand, min_pair: Rank) -> Vec<HandClass> {
     let mut classes = Vec::new();
     let rank_counts = hand.rank_counts();
     let suit_counts = hand.suit_counts();
@@ -413,7 +561,7 @@ fn get_hand_classes(hand: &Hand) -> Vec<HandClass> {
 
     if pairs == 1 && trips == 0 {
         let pair_rank = pair_ranks[0];
-        if pair_rank.is_high() && pair_rank != Rank::Ten {
+        if pair_rank >= min_pair {
             classes.push(HandClass::HighPair);
         } else {
             classes.push(HandClass::LowPair);
@@ -435,7 +583,7 @@ fn get_hand_classes(hand: &Hand) -> Vec<HandClass> {
             let is_ace_low_sf = sorted_values == vec![2, 3, 4, 14];
 
             if span <= 4 || is_ace_low_sf {
-                let royal_ranks = vec![10, 11, 12, 13, 14];
+                let royal_ranks = [10, 11, 12, 13, 14];
                 let is_royal_draw = sorted_values.iter().all(|v| royal_ranks.contains(v));
 
                 if is_royal_draw {
@@ -474,14 +622,14 @@ fn get_hand_classes(hand: &Hand) -> Vec<HandClass> {
                             continue;
                         }
 
-                        if let Some((gaps, highs)) = sf_draw_gaps_and_highs(&three_ranks) {
-                            if let Some(sf_type) = sf_draw_type(gaps, highs, &three_ranks) {
-                                match sf_type {
-                                    1 => { if !classes.contains(&HandClass::ThreeToSFType1) { classes.push(HandClass::ThreeToSFType1); } }
-                                    2 => { if !classes.contains(&HandClass::ThreeToSFType2) { classes.push(HandClass::ThreeToSFType2); } }
-                                    3 => { if !classes.contains(&HandClass::ThreeToSFType3) { classes.push(HandClass::ThreeToSFType3); } }
-                                    _ => {}
-                                }
+                        if let Some((gaps, highs)) = sf_draw_gaps_and_highs(&three_ranks)
+                            && let Some(sf_type) = sf_draw_type(gaps, highs, &three_ranks)
+                        {
+                            match sf_type {
+                                1 if !classes.contains(&HandClass::ThreeToSFType1) => classes.push(HandClass::ThreeToSFType1),
+                                2 if !classes.contains(&HandClass::ThreeToSFType2) => classes.push(HandClass::ThreeToSFType2),
+                                3 if !classes.contains(&HandClass::ThreeToSFType3) => classes.push(HandClass::ThreeToSFType3),
+                                _ => {}
                             }
                         }
                     }
@@ -515,24 +663,21 @@ fn get_hand_classes(hand: &Hand) -> Vec<HandClass> {
     for idx in &indices {
         let four_cards: Vec<Rank> = idx.iter().map(|&i| hand.cards[i].rank).collect();
 
-        if let Some(high_count) = is_outside_straight_draw(&four_cards) {
-            if high_count <= 2 {
-                if !classes.contains(&HandClass::FourToOutsideStraight) {
-                    classes.push(HandClass::FourToOutsideStraight);
-                }
-            }
+        if let Some(high_count) = is_outside_straight_draw(&four_cards)
+            && high_count <= 2
+            && !classes.contains(&HandClass::FourToOutsideStraight)
+        {
+            classes.push(HandClass::FourToOutsideStraight);
         }
 
-        if let Some(high_count) = is_inside_straight_draw(&four_cards) {
-            if high_count == 4 {
-                if !classes.contains(&HandClass::FourToInsideStraight4High) {
-                    classes.push(HandClass::FourToInsideStraight4High);
-                }
-            } else if high_count == 3 {
-                if !classes.contains(&HandClass::FourToInsideStraight3High) {
-                    classes.push(HandClass::FourToInsideStraight3High);
-                }
+        match is_inside_straight_draw(&four_cards) {
+            Some(4) if !classes.contains(&HandClass::FourToInsideStraight4High) => {
+                classes.push(HandClass::FourToInsideStraight4High);
+            }
+            Some(3) if !classes.contains(&HandClass::FourToInsideStraight3High) => {
+                classes.push(HandClass::FourToInsideStraight3High);
             }
+            _ => {}
         }
     }
 
@@ -593,6 +738,159 @@ fn get_hand_classes(hand: &Hand) -> Vec<HandClass> {
     classes
 }
 
+/// Class of a single hold: the cards kept, in any order. Holds that no chart line
+/// describes (e.g. a lone ten, or a kicker kept with a pair) have no class.
+fn classify_hold(held: &[Card], scheme: ClassScheme) -> Option<HandClass> {
+    if held.is_empty() {
+        return Some(HandClass::Garbage);
+    }
+    match scheme {
+        ClassScheme::JacksOrBetter(min_pair) => classify_jacks_or_better_hold(held, min_pair),
+        ClassScheme::Bonus => classify_bonus_hold(held),
+        ClassScheme::Deuces => wild::classify_wild_hold(held, |c| c.rank == Rank::Two, |_| false)
+            .map(|(wilds, class)| HandClass::Deuces(wilds, class)),
+        ClassScheme::Joker { high_pairs } => wild::classify_wild_hold(
+            held,
+            |c| c.rank == Rank::Joker,
+            |r| high_pairs && r >= Rank::King,
+        ).map(|(wilds, class)| HandClass::Joker(wilds, class)),
+    }
+}
+
+/// Jacks or Better classes, split where the bonus games pay more for aces or low quads:
+/// pairs and trips of aces or 2s-4s, trips with the kicker that pays the quad bonus,
+/// and quads with their kicker. Inside straights with few high cards get a class too.
+fn classify_bonus_hold(held: &[Card]) -> Option<HandClass> {
+    let class = classify_jacks_or_better_hold(held, Rank::Jack);
+    let mut ranks: Vec<Rank> = held.iter().map(|c| c.rank).collect();
+    ranks.sort();
+    let low = |r: Rank| r <= Rank::Four;
+    // The set is the middle card once sorted; the kicker is whichever end differs
+    let set = ranks[ranks.len() / 2];
+    let kicker = if ranks[0] == set { ranks[ranks.len() - 1] } else { ranks[0] };
+    let bonus_kicker = (set == Rank::Ace && low(kicker)) || (low(set) && (low(kicker) || kicker == Rank::Ace));
+    match (class, held.len()) {
+        (Some(HandClass::FourOfAKind), 5) if bonus_kicker => Some(HandClass::FourOfAKindWithKicker),
+        (Some(HandClass::HighPair), _) if set == Rank::Ace => Some(HandClass::PairOfAces),
+        (Some(HandClass::LowPair), _) if low(set) => Some(HandClass::LowPair2To4),
+        (Some(HandClass::ThreeOfAKind), _) if set == Rank::Ace => Some(HandClass::ThreeAces),
+        (Some(HandClass::ThreeOfAKind), _) if low(set) => Some(HandClass::Three2To4),
+        (None, 4) if bonus_kicker && ranks.iter().filter(|&&r| r == set).count() == 3 => {
+            if set == Rank::Ace {
+                Some(HandClass::ThreeAcesWithKicker)
+            } else {
+                Some(HandClass::Three2To4WithKicker)
+            }
+        }
+        (None, 4) if is_inside_straight_draw(&ranks).is_some() => Some(HandClass::FourToInsideStraightLow),
+        (None, 3) if held.iter().all(|c| c.suit == held[0].suit) => Some(HandClass::ThreeToFlush),
+        _ => class,
+    }
+}
+
+fn classify_jacks_or_better_hold(held: &[Card], min_pair: Rank) -> Option<HandClass> {
+    let held_hand = Hand::new(held.to_vec());
+    let rank_counts = held_hand.rank_counts();
+    let ranks = held_hand.ranks();
+    let mut counts: Vec<usize> = rank_counts.values().cloned().collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let suited = held_hand.suit_counts().len() == 1;
+    let all_royal = ranks.iter().all(|r| r.is_high());
+
+    match held.len() {
+        0 => Some(HandClass::Garbage),
+        5 => {
+            let straight = counts.len() == 5 && is_straight_ranks(&ranks);
+            match (straight, suited, counts.as_slice()) {
+                (true, true, _) if all_royal => Some(HandClass::RoyalFlush),
+                (true, true, _) => Some(HandClass::StraightFlush),
+                (_, _, [4, 1]) => Some(HandClass::FourOfAKind),
+                (_, _, [3, 2]) => Some(HandClass::FullHouse),
+                (_, true, _) => Some(HandClass::Flush),
+                (true, _, _) => Some(HandClass::Straight),
+                _ => None,
+            }
+        }
+        4 => match counts.as_slice() {
+            [4] => Some(HandClass::FourOfAKind),
+            [2, 2] => Some(HandClass::TwoPair),
+            [1, 1, 1, 1] => {
+                let mut values: Vec<u8> = ranks.iter().map(|r| r.value()).collect();
+                values.sort();
+                if suited {
+                    let span = values[3] - values[0];
+                    if all_royal {
+                        Some(HandClass::FourToRoyalFlush)
+                    } else if span <= 4 || values == [2, 3, 4, 14] || values == [2, 3, 5, 14]
+                        || values == [2, 4, 5, 14] || values == [3, 4, 5, 14] {
+                        Some(HandClass::FourToStraightFlush)
+                    } else {
+                        Some(HandClass::FourToFlush)
+                    }
+                } else if values == [10, 11, 12, 13] {
+                    Some(HandClass::UnsuitedTJQK)
+                } else if matches!(is_outside_straight_draw(&ranks), Some(highs) if highs <= 2) {
+                    Some(HandClass::FourToOutsideStraight)
+                } else {
+                    match is_inside_straight_draw(&ranks) {
+                        Some(4) => Some(HandClass::FourToInsideStraight4High),
+                        Some(3) => Some(HandClass::FourToInsideStraight3High),
+                        _ => None,
+                    }
+                }
+            }
+            _ => None,
+        },
+        3 => match counts.as_slice() {
+            [3] => Some(HandClass::ThreeOfAKind),
+            [1, 1, 1] if suited && all_royal => Some(HandClass::ThreeToRoyalFlush),
+            [1, 1, 1] if suited => {
+                let (gaps, highs) = sf_draw_gaps_and_highs(&ranks)?;
+                match sf_draw_type(gaps, highs, &ranks)? {
+                    1 => Some(HandClass::ThreeToSFType1),
+                    2 => Some(HandClass::ThreeToSFType2),
+                    _ => Some(HandClass::ThreeToSFType3),
+                }
+            }
+            [1, 1, 1] if ranks == [Rank::Jack, Rank::Queen, Rank::King] => Some(HandClass::UnsuitedJQK),
+            _ => None,
+        },
+        2 => {
+            if counts == [2] {
+                return if ranks[0] >= min_pair {
+                    Some(HandClass::HighPair)
+                } else {
+                    Some(HandClass::LowPair)
+                };
+            }
+            match (ranks[0], ranks[1], suited) {
+                (Rank::Jack, Rank::Queen, true) => Some(HandClass::SuitedQJ),
+                (Rank::Jack | Rank::Queen, Rank::King, true) => Some(HandClass::SuitedKQOrKJ),
+                (Rank::Jack | Rank::Queen | Rank::King, Rank::Ace, true) => Some(HandClass::SuitedAKAQAJ),
+                (Rank::Ten, Rank::Jack, true) => Some(HandClass::SuitedTJ),
+                (Rank::Ten, Rank::Queen, true) => Some(HandClass::SuitedTQ),
+                (Rank::Ten, Rank::King, true) => Some(HandClass::SuitedTK),
+                (Rank::Ten, Rank::Ace, true) if min_pair == Rank::Ten => Some(HandClass::SuitedTA),
+                (Rank::Ten, Rank::Jack | Rank::Queen, false) if min_pair == Rank::Ten => Some(HandClass::TwoUnsuitedHighQueen),
+                (Rank::Ten, Rank::King, false) if min_pair == Rank::Ten => Some(HandClass::TwoUnsuitedHighKing),
+                (Rank::Ten, Rank::Ace, false) if min_pair == Rank::Ten => Some(HandClass::TwoUnsuitedHighAce),
+                (Rank::Jack, Rank::Queen, false) => Some(HandClass::UnsuitedJQ),
+                (Rank::Jack | Rank::Queen, Rank::King, false) => Some(HandClass::TwoUnsuitedHighKing),
+                (Rank::Jack | Rank::Queen | Rank::King, Rank::Ace, false) => Some(HandClass::TwoUnsuitedHighAce),
+                _ => None,
+            }
+        }
+        _ => match ranks[0] {
+            Rank::Ten if min_pair == Rank::Ten => Some(HandClass::TOnly),
+            Rank::Jack => Some(HandClass::JOnly),
+            Rank::Queen => Some(HandClass::QOnly),
+            Rank::King => Some(HandClass::KOnly),
+            Rank::Ace => Some(HandClass::AOnly),
+            _ => None,
+        },
+    }
+}
+
 // Generate all canonical 5-card hands
 // Canonical means we assign suits in order of first appearance: a, b, c, d
 fn generate_canonical_hands() -> Vec<Hand> {
@@ -690,7 +988,7 @@ fn generate_suits_recursive(
 
         if !already_used {
             current.push(suit);
-            used_suits_for_rank.entry(rank).or_insert_with(HashSet::new).insert(suit);
+            used_suits_for_rank.entry(rank).or_default().insert(suit);
             generate_suits_recursive(ranks, idx + 1, current, all_suits, patterns, used_suits_for_rank);
             used_suits_for_rank.get_mut(&rank).unwrap().remove(&suit);
             current.pop();
@@ -730,7 +1028,24 @@ struct ConflictResult {
     class2: HandClass,
 }
 
-fn main() {
+/// Hands whose classes sit within 5 places of each other in the family's class order
+/// (the 9/6 chart ranks for Jacks or Better). The order alone decides; for EVs from a
+/// strategy file see the conflicts command.
+fn run_rank_conflicts(family: GameFamily) {
+    let scheme = match family.scheme() {
+        Some(ClassScheme::Joker { .. }) => {
+            eprintln!("{:?} is dealt from a 53-card deck; use hand-analyzer conflicts with its strategy file", family);
+            std::process::exit(1);
+        }
+        Some(scheme) => scheme,
+        None => {
+            eprintln!("No hand classes for {:?} yet", family);
+            std::process::exit(1);
+        }
+    };
+    let order = HandClass::of_scheme(scheme);
+    let rank = |class: &HandClass| order.iter().position(|c| c == class).map_or(u8::MAX, |i| i as u8 + 1);
+
     println!("Generating all canonical 5-card hands...");
     let hands = generate_canonical_hands();
     println!("Total canonical hands: {}", hands.len());
@@ -740,16 +1055,16 @@ fn main() {
     let mut conflicts: Vec<ConflictResult> = Vec::new();
 
     for hand in &hands {
-        let classes = get_hand_classes(hand);
+        let classes = get_hand_classes(hand, family);
 
         // Find the best two classes that are within 5 ranks
         let mut dominated_classes: BTreeSet<HandClass> = BTreeSet::new();
 
         for i in 0..classes.len() {
             for j in (i+1)..classes.len() {
-                let r1 = classes[i].rank();
-                let r2 = classes[j].rank();
-                let diff = if r1 > r2 { r1 - r2 } else { r2 - r1 };
+                let r1 = rank(&classes[i]);
+                let r2 = rank(&classes[j]);
+                let diff = r1.abs_diff(r2);
 
                 if diff <= 5 && diff > 0 {
                     let (c1, c2) = if r1 < r2 { (classes[i], classes[j]) } else { (classes[j], classes[i]) };
@@ -777,8 +1092,8 @@ fn main() {
 
     // Sort by class1 rank, then class2 rank, then hand
     conflicts.sort_by(|a, b| {
-        a.class1.rank().cmp(&b.class1.rank())
-            .then(a.class2.rank().cmp(&b.class2.rank()))
+        rank(&a.class1).cmp(&rank(&b.class1))
+            .then(rank(&a.class2).cmp(&rank(&b.class2)))
             .then(a.hand.cmp(&b.hand))
     });
 
@@ -790,8 +1105,8 @@ fn main() {
         println!("{:<20} {:<6} {:<35} {:<35}",
             conflict.hand,
             conflict.rank_diff,
-            format!("({}) {}", conflict.class1.rank(), conflict.class1.name()),
-            format!("({}) {}", conflict.class2.rank(), conflict.class2.name()),
+            format!("({}) {}", rank(&conflict.class1), conflict.class1.name()),
+            format!("({}) {}", rank(&conflict.class2), conflict.class2.name()),
         );
     }
 
@@ -800,7 +1115,7 @@ fn main() {
 
     let mut summary: HashMap<(u8, u8), usize> = HashMap::new();
     for conflict in &conflicts {
-        let key = (conflict.class1.rank(), conflict.class2.rank());
+        let key = (rank(&conflict.class1), rank(&conflict.class2));
         *summary.entry(key).or_insert(0) += 1;
     }
 
@@ -808,12 +1123,94 @@ fn main() {
     summary_vec.sort_by_key(|&((r1, r2), _)| (r1, r2));
 
     for ((r1, r2), count) in summary_vec {
-        let c1_name = conflicts.iter().find(|c| c.class1.rank() == r1).map(|c| c.class1.name()).unwrap_or("?");
-        let c2_name = conflicts.iter().find(|c| c.class2.rank() == r2).map(|c| c.class2.name()).unwrap_or("?");
+        let c1_name = conflicts.iter().find(|c| rank(&c.class1) == r1).map(|c| c.class1.name()).unwrap_or_else(|| "?".to_string());
+        let c2_name = conflicts.iter().find(|c| rank(&c.class2) == r2).map(|c| c.class2.name()).unwrap_or_else(|| "?".to_string());
         println!("({}) {} vs ({}) {}: {} hands", r1, c1_name, r2, c2_name, count);
     }
 }
 
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+
+    let flag = |name: &str| args.iter().position(|a| a == name).and_then(|i| args.get(i + 1));
+
+    if args.get(1).map(|s| s.as_str()) == Some("chart") {
+        let Some(path) = args.get(2) else {
+            eprintln!("Usage: hand-analyzer chart <strategy.vpstrat2> [--family NAME] [--output DIR]");
+            std::process::exit(1);
+        };
+        chart::run_chart(path, flag("--family").map(|s| s.as_str()), flag("--output").map_or(".", |s| s.as_str()));
+        return;
+    }
+
+    if args.get(1).map(|s| s.as_str()) == Some("score") {
+        let Some(path) = args.get(2) else {
+            eprintln!("Usage: hand-analyzer score <strategy.vpstrat2> [--chart FILE] [--family NAME] [--top N] [--output DIR]");
+            std::process::exit(1);
+        };
+        let top = match flag("--top").map(|n| n.parse::<usize>()) {
+            Some(Ok(n)) => Some(n),
+            Some(Err(_)) => {
+                eprintln!("--top expects a number of hands");
+                std::process::exit(1);
+            }
+            None => None,
+        };
+        score::run_score(path, flag("--chart").map(|s| s.as_str()), flag("--family").map(|s| s.as_str()), top,
+            flag("--output").map_or(".", |s| s.as_str()));
+        return;
+    }
+
+    if args.get(1).map(|s| s.as_str()) == Some("conflicts") {
+        let Some(path) = args.get(2) else {
+            eprintln!("Usage: hand-analyzer conflicts <strategy.vpstrat2> [--family NAME] [--close EV] [--output DIR]");
+            std::process::exit(1);
+        };
+        let close = match flag("--close").map(|n| n.parse::<f64>()) {
+            Some(Ok(margin)) => Some(margin),
+            Some(Err(_)) => {
+                eprintln!("--close expects an EV margin");
+                std::process::exit(1);
+            }
+            None => None,
+        };
+        conflicts::run_conflicts(path, flag("--family").map(|s| s.as_str()), close, flag("--output").map_or(".", |s| s.as_str()));
+        return;
+    }
+
+    if args.get(1).map(|s| s.as_str()) == Some("help") {
+        println!("Usage:");
+        println!("  hand-analyzer [--family NAME]      Report close class conflicts by class order (9/6 Jacks");
+        println!("                                     or Better ranks by default; 52-card families only)");
+        println!("  hand-analyzer conflicts <file> [--family NAME] [--close EV] [--output DIR]");
+        println!("                                     Decisions between hand classes from a .vpstrat2 file's EVs:");
+        println!("                                     win shares, EV margins and dealt frequency per class pair");
+        println!("  hand-analyzer chart <file> [--family NAME] [--output DIR]");
+        println!("                                     Derive a strategy chart from a .vpstrat2 file's EVs,");
+        println!("                                     written as chart_<id>.md and chart_<id>.json");
+        println!("  hand-analyzer score <file> [--chart FILE] [--family NAME] [--top N] [--output DIR]");
+        println!("                                     Return and error cost of playing by a chart (default: the");
+        println!("                                     family's classes in order; FILE is a chart JSON or class list)");
+        println!();
+        println!("The game family (e.g. DeucesWild, JokerPokerKings) picks the hand classes. It is");
+        println!("inferred from the strategy file name; --family overrides it.");
+        return;
+    }
+
+    if args.len() > 1 && args[1] != "--family" {
+        eprintln!("Unknown command '{}'; see hand-analyzer help", args[1]);
+        std::process::exit(1);
+    }
+    let family = match flag("--family") {
+        Some(name) => GameFamily::from_name(name).unwrap_or_else(|| {
+            eprintln!("Unknown game family '{}'", name);
+            std::process::exit(1);
+        }),
+        None => GameFamily::JacksOrBetter,
+    };
+    run_rank_conflicts(family);
+}
+
 // Check if class2 is dominated by class1 (trivially worse)
 fn is_dominated(c1: &HandClass, c2: &HandClass) -> bool {
     use HandClass::*;
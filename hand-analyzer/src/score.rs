@@ -0,0 +1,224 @@
+// Strategy chart scoring.
+//
+// Plays every canonical hand of a strategy file by a given chart (an ordered class
+// list, see chart.rs) and measures what the simplification costs: the chart's return,
+// its loss against the optimal holds, and the misplays that account for it, ranked by
+// dealt frequency x EV loss.
+
+use super::*;
+use crate::chart::{chart_hold, class_label, class_options, positions_of, write_report};
+use crate::family::resolve_family;
+use crate::strategy::{read_strategy, StrategyFile};
+use std::fs;
+
+const DEFAULT_TOP: usize = 25;
+
+/// Where a chart line's misplays cost return
+#[derive(Serialize)]
+pub struct LineScore {
+    pub position: usize,
+    pub class: HandClass,
+    pub name: String,
+    /// Share of dealt hands played by this line
+    pub frequency: f64,
+    /// Share of dealt hands this line plays differently from optimal
+    pub misplay_frequency: f64,
+    pub return_cost: f64,
+}
+
+#[derive(Serialize)]
+pub struct Misplay {
+    pub key: String,
+    /// The hand with the optimal hold in brackets
+    pub hand: String,
+    pub chart_class: HandClass,
+    pub chart_hold: String,
+    pub optimal_class: Option<HandClass>,
+    pub optimal_hold: String,
+    pub frequency: f64,
+    pub ev_loss: f64,
+    pub return_cost: f64,
+}
+
+#[derive(Serialize)]
+pub struct ScoreReport {
+    pub paytable_id: String,
+    pub family: GameFamily,
+    pub chart_source: String,
+    pub optimal_return: f64,
+    pub chart_return: f64,
+    pub return_loss: f64,
+    pub misplay_frequency: f64,
+    pub lines: Vec<LineScore>,
+    pub top_misplays: Vec<Misplay>,
+}
+
+/// Read a chart: either a `chart` JSON report, or text with one class per line (or
+/// separated by '>'), named by identifier or display name. '#' starts a comment.
+fn read_chart(path: &str) -> Result<Vec<HandClass>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let names: Vec<String> = if path.ends_with(".json") {
+        let json: serde_json::Value = serde_json::from_str(&text).map_err(|e| format!("Invalid JSON in {}: {}", path, e))?;
+        json["chart"].as_array()
+            .ok_or_else(|| format!("{} has no \"chart\" list", path))?
+            .iter()
+            .map(|line| line["class"].as_str().or(line.as_str()).unwrap_or_default().to_string())
+            .collect()
+    } else {
+        text.lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .flat_map(|line| line.split('>'))
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect()
+    };
+
+    let mut chart = Vec::new();
+    for name in names {
+        let class = HandClass::from_name(&name).ok_or_else(|| format!("Unknown hand class '{}' in {}", name, path))?;
+        if chart.contains(&class) {
+            return Err(format!("{} lists '{}' twice", path, class.name()));
+        }
+        chart.push(class);
+    }
+    if chart.is_empty() {
+        return Err(format!("{} lists no hand classes", path));
+    }
+    Ok(chart)
+}
+
+pub fn score_chart(strategy: &StrategyFile, family: GameFamily, scheme: ClassScheme, order: &[HandClass], chart_source: &str,
+    top: usize) -> ScoreReport
+{
+    let positions = positions_of(order);
+    let total = strategy.total_hands() as f64;
+    let mut lines: Vec<LineScore> = order.iter().enumerate()
+        .map(|(i, class)| LineScore {
+            position: i + 1,
+            class: *class,
+            name: class.name(),
+            frequency: 0.0,
+            misplay_frequency: 0.0,
+            return_cost: 0.0,
+        })
+        .collect();
+    let mut chart_total = 0.0;
+    let mut misplay_frequency = 0.0;
+    let mut misplays: Vec<Misplay> = Vec::new();
+
+    for entry in &strategy.entries {
+        let m = entry.multiplicity as f64;
+        let options = class_options(entry, scheme);
+        let (class, mask, ev) = chart_hold(&positions, &options, entry);
+        chart_total += m * ev;
+        let loss = entry.evs[entry.best_hold as usize] - ev;
+        let misplayed = loss > entry.step / 2.0;
+        if let Some(&p) = positions.get(&class) {
+            let line = &mut lines[p];
+            line.frequency += m / total;
+            if misplayed {
+                line.misplay_frequency += m / total;
+                line.return_cost += m * loss / total;
+            }
+        }
+        if !misplayed {
+            continue;
+        }
+        misplay_frequency += m / total;
+        misplays.push(Misplay {
+            key: entry.key.clone(),
+            hand: entry.describe_hold(entry.best_hold),
+            chart_class: class,
+            chart_hold: entry.held(mask).iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "),
+            optimal_class: classify_hold(&entry.held(entry.best_hold), scheme),
+            optimal_hold: entry.held(entry.best_hold).iter().map(|c| c.to_string()).collect::<Vec<_>>().join(" "),
+            frequency: m / total,
+            ev_loss: loss,
+            return_cost: m * loss / total,
+        });
+    }
+
+    misplays.sort_by(|a, b| b.return_cost.total_cmp(&a.return_cost).then(a.key.cmp(&b.key)));
+    misplays.truncate(top);
+    let optimal_return = strategy.optimal_return();
+    let chart_return = chart_total / total;
+    ScoreReport {
+        paytable_id: strategy.paytable_id.clone(),
+        family,
+        chart_source: chart_source.to_string(),
+        optimal_return,
+        chart_return,
+        return_loss: optimal_return - chart_return,
+        misplay_frequency,
+        lines,
+        top_misplays: misplays,
+    }
+}
+
+fn score_markdown(report: &ScoreReport) -> String {
+    let mut md = format!("# Chart score: {}\n\n", report.paytable_id);
+    md.push_str(&format!("Game family: {:?}  \nChart: {}  \nOptimal return: {:.4}%  \nChart return: {:.4}% (loses {:.4}%)  \n",
+        report.family, report.chart_source, report.optimal_return * 100.0, report.chart_return * 100.0, report.return_loss * 100.0));
+    md.push_str(&format!("Hands played differently from optimal: {:.4}%\n\n", report.misplay_frequency * 100.0));
+
+    md.push_str("## Cost by chart line\n\n| # | Hold | Played | Misplayed | Return cost |\n|--:|------|-------:|----------:|------------:|\n");
+    for line in &report.lines {
+        md.push_str(&format!("| {} | {} | {:.4}% | {:.4}% | {:.6}% |\n",
+            line.position, line.name, line.frequency * 100.0, line.misplay_frequency * 100.0, line.return_cost * 100.0));
+    }
+
+    md.push_str("\n## Costliest misplays\n\nOrdered by dealt frequency x EV loss; the optimal hold is in brackets.\n\n");
+    md.push_str("| Hand | Chart holds | Should hold | Frequency | EV loss | Return cost |\n");
+    md.push_str("|------|-------------|-------------|----------:|--------:|------------:|\n");
+    for m in &report.top_misplays {
+        md.push_str(&format!("| {} | {} ({}) | {} | {:.6}% | {:.4} | {:.6}% |\n",
+            m.hand, if m.chart_hold.is_empty() { "nothing" } else { &m.chart_hold }, m.chart_class.name(),
+            class_label(m.optimal_class), m.frequency * 100.0, m.ev_loss, m.return_cost * 100.0));
+    }
+    md
+}
+
+pub fn run_score(strategy_path: &str, chart_path: Option<&str>, family_name: Option<&str>, top: Option<usize>, output_dir: &str) {
+    let (strategy, family, scheme) = match read_strategy(strategy_path)
+        .and_then(|s| resolve_family(&s, family_name).map(|(f, scheme)| (s, f, scheme)))
+    {
+        Ok(loaded) => loaded,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let (order, source) = match chart_path {
+        Some(path) => match read_chart(path) {
+            Ok(order) => (order, path.to_string()),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        None if family == GameFamily::JacksOrBetter => (HandClass::of_scheme(scheme), "HandClass::rank order (9/6 Jacks or Better)".to_string()),
+        None => (HandClass::of_scheme(scheme), format!("HandClass order for {:?}", family)),
+    };
+    println!("Loaded {} canonical hands for {} ({:?})", strategy.entries.len(), strategy.paytable_id, family);
+
+    let report = score_chart(&strategy, family, scheme, &order, &source, top.unwrap_or(DEFAULT_TOP));
+    println!("Chart: {}", source);
+    println!("Optimal return: {:.4}%  Chart return: {:.4}%  Loss: {:.4}%",
+        report.optimal_return * 100.0, report.chart_return * 100.0, report.return_loss * 100.0);
+    println!("Hands played differently from optimal: {:.4}%", report.misplay_frequency * 100.0);
+    if !report.top_misplays.is_empty() {
+        println!("\nCostliest misplays:");
+        for m in report.top_misplays.iter().take(10) {
+            println!("  {:<24} {:<32} {:.6}%", m.hand, m.chart_class.name(), m.return_cost * 100.0);
+        }
+    }
+
+    let markdown = score_markdown(&report);
+    match write_report(&report, &report.paytable_id, &markdown, output_dir, "score") {
+        Ok((md, json)) => println!("\nWrote {} and {}", md, json),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,179 @@
+// Reading vp_calculator's .vpstrat2 strategy files.
+//
+// Each entry is a canonical hand key ("2a3b4c5d6a": ranks ascending, suits lettered
+// by first appearance, jokers last as "Ww") with the EV of all 32 holds; hold bit i
+// is the i-th card of the key. See the BINARY FORMAT V2 notes in scripts/rust_calculator/src/main.rs.
+
+use super::*;
+use std::fs;
+use std::path::Path;
+
+const VPS2_MAGIC: &[u8; 4] = b"VPS2";
+const VPS2_HEADER_SIZE: usize = 64;
+const VPS2_DATA_ENTRY_SIZE: usize = 66;
+const VPS2_SCALES: [f64; 4] = [0.0001, 0.001, 0.01, 0.1];
+
+pub struct StrategyEntry {
+    pub key: String,
+    /// Cards in key order, so hold bit i is cards[i]
+    pub cards: Vec<Card>,
+    pub best_hold: u8,
+    pub evs: [f64; 32],
+    /// Encoding resolution of the EVs; holds within a step of each other are ties
+    pub step: f64,
+    /// Number of dealt hands this canonical hand stands for
+    pub multiplicity: u64,
+}
+
+impl StrategyEntry {
+    pub fn held(&self, mask: u8) -> Vec<Card> {
+        (0..self.cards.len()).filter(|i| mask & (1 << i) != 0).map(|i| self.cards[i]).collect()
+    }
+
+    /// Hand with the held cards in brackets, e.g. "[Ja Qa] 4b 7c 9d"
+    pub fn describe_hold(&self, mask: u8) -> String {
+        let held: Vec<String> = self.held(mask).iter().map(|c| c.to_string()).collect();
+        let rest: Vec<String> = (0..self.cards.len())
+            .filter(|i| mask & (1 << i) == 0)
+            .map(|i| self.cards[i].to_string())
+            .collect();
+        match (held.is_empty(), rest.is_empty()) {
+            (true, _) => rest.join(" "),
+            (false, true) => format!("[{}]", held.join(" ")),
+            (false, false) => format!("[{}] {}", held.join(" "), rest.join(" ")),
+        }
+    }
+}
+
+pub struct StrategyFile {
+    pub paytable_id: String,
+    /// Jokers added to the 52-card deck
+    pub jokers: usize,
+    pub entries: Vec<StrategyEntry>,
+}
+
+impl StrategyFile {
+    pub fn total_hands(&self) -> u64 {
+        self.entries.iter().map(|e| e.multiplicity).sum()
+    }
+
+    /// Return of the optimal strategy, from the stored best-hold EVs
+    pub fn optimal_return(&self) -> f64 {
+        let total: f64 = self.entries.iter()
+            .map(|e| e.multiplicity as f64 * e.evs[e.best_hold as usize])
+            .sum();
+        total / self.total_hands() as f64
+    }
+}
+
+/// "strategy_jacks_or_better_9_6.vpstrat2" -> "jacks-or-better-9-6"
+fn paytable_id_from_path(path: &str) -> String {
+    let stem = Path::new(path).file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+    stem.trim_start_matches("strategy_").replace('_', "-")
+}
+
+fn parse_key(key: &str) -> Result<Vec<Card>, String> {
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() != 10 {
+        return Err(format!("Unsupported canonical key '{}'", key));
+    }
+    chars.chunks(2).map(|pair| {
+        if pair == ['W', 'w'] {
+            return Ok(Card::new(Rank::Joker, CanonicalSuit::W));
+        }
+        let rank = Rank::all().into_iter().find(|r| r.to_char() == pair[0]);
+        let suit = [CanonicalSuit::A, CanonicalSuit::B, CanonicalSuit::C, CanonicalSuit::D]
+            .into_iter()
+            .find(|s| s.to_char() == pair[1]);
+        match (rank, suit) {
+            (Some(rank), Some(suit)) => Ok(Card::new(rank, suit)),
+            _ => Err(format!("Unsupported card '{}{}' in key '{}'", pair[0], pair[1], key)),
+        }
+    }).collect()
+}
+
+/// Dealt hands per canonical key, keyed the way vp_calculator's hand_to_canonical_key
+/// does it: cards dealt in deck order, sorted by rank (stable), suits lettered by first
+/// appearance. Jokers are cards 52 and up and sort last.
+fn canonical_multiplicities(jokers: usize) -> HashMap<String, u64> {
+    let letters = ['a', 'b', 'c', 'd'];
+    let ranks = Rank::all();
+    let deck = 52 + jokers as u8;
+    let mut counts: HashMap<String, u64> = HashMap::new();
+    let mut hand = [0u8; 5];
+    for c1 in 0..deck - 4 {
+        for c2 in (c1 + 1)..deck - 3 {
+            for c3 in (c2 + 1)..deck - 2 {
+                for c4 in (c3 + 1)..deck - 1 {
+                    for c5 in (c4 + 1)..deck {
+                        hand.copy_from_slice(&[c1, c2, c3, c4, c5]);
+                        hand.sort_by_key(|c| (*c).min(52) / 4);
+                        let mut suit_map = [None; 4];
+                        let mut next = 0;
+                        let mut key = String::with_capacity(10);
+                        for c in hand {
+                            if c >= 52 {
+                                key.push_str("Ww");
+                                continue;
+                            }
+                            key.push(ranks[(c / 4) as usize].to_char());
+                            let letter = *suit_map[(c % 4) as usize].get_or_insert_with(|| {
+                                next += 1;
+                                letters[next - 1]
+                            });
+                            key.push(letter);
+                        }
+                        *counts.entry(key).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// Load a .vpstrat2 file with every entry's dealt-hand multiplicity
+pub fn read_strategy(path: &str) -> Result<StrategyFile, String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if data.len() < VPS2_HEADER_SIZE { return Err("File too small".to_string()); }
+    if &data[0..4] != VPS2_MAGIC { return Err("Invalid magic".to_string()); }
+    let jokers = u16::from_le_bytes([data[6], data[7]]) as usize;
+    if jokers > 2 {
+        return Err(format!("{} has {} jokers; at most 2 are supported", path, jokers));
+    }
+
+    let entry_count = u32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize;
+    let key_length = data[12] as usize;
+    let data_start = VPS2_HEADER_SIZE + entry_count * key_length;
+    if data.len() < data_start + entry_count * VPS2_DATA_ENTRY_SIZE {
+        return Err("File truncated".to_string());
+    }
+
+    let multiplicities = canonical_multiplicities(jokers);
+    let mut entries = Vec::with_capacity(entry_count);
+    for i in 0..entry_count {
+        let key_offset = VPS2_HEADER_SIZE + i * key_length;
+        let key = String::from_utf8_lossy(&data[key_offset..key_offset + key_length])
+            .trim_end_matches('\0').to_string();
+        let cards = parse_key(&key)?;
+        let multiplicity = *multiplicities.get(&key)
+            .ok_or_else(|| format!("Key '{}' is not a canonical {}-card hand", key, 52 + jokers))?;
+
+        let data_offset = data_start + i * VPS2_DATA_ENTRY_SIZE;
+        let step = VPS2_SCALES[data[data_offset + 1].min(3) as usize];
+        let mut evs = [0.0; 32];
+        for (mask, ev) in evs.iter_mut().enumerate() {
+            let offset = data_offset + 2 + mask * 2;
+            *ev = u16::from_le_bytes([data[offset], data[offset + 1]]) as f64 * step;
+        }
+        entries.push(StrategyEntry { key, cards, best_hold: data[data_offset], evs, step, multiplicity });
+    }
+
+    let file = StrategyFile { paytable_id: paytable_id_from_path(path), jokers, entries };
+    let total = file.total_hands();
+    let expected = [2_598_960, 2_869_685, 3_162_510][jokers];
+    if total != expected {
+        return Err(format!("Strategy covers {} dealt hands, expected {}", total, expected));
+    }
+    Ok(file)
+}
@@ -0,0 +1,197 @@
+// Hold classes for wild card games (deuces wild, joker poker).
+//
+// Wild card strategy is organised by how many wild cards are held, so a class is the
+// wild count plus what the natural cards make or draw to with them: "Two deuces: 4 to
+// a royal flush", "Joker: straight", "No deuces: pair".
+
+use super::*;
+
+/// Wild cards held, most first so charts list four deuces before three
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Wilds {
+    Four,
+    Three,
+    Two,
+    One,
+    Zero,
+}
+
+impl Wilds {
+    pub fn all() -> Vec<Wilds> {
+        vec![Wilds::Four, Wilds::Three, Wilds::Two, Wilds::One, Wilds::Zero]
+    }
+
+    fn from_count(count: usize) -> Wilds {
+        match count {
+            0 => Wilds::Zero,
+            1 => Wilds::One,
+            2 => Wilds::Two,
+            3 => Wilds::Three,
+            _ => Wilds::Four,
+        }
+    }
+
+    pub fn deuces_label(self) -> &'static str {
+        match self {
+            Wilds::Four => "Four deuces",
+            Wilds::Three => "Three deuces",
+            Wilds::Two => "Two deuces",
+            Wilds::One => "One deuce",
+            Wilds::Zero => "No deuces",
+        }
+    }
+
+    pub fn joker_label(self) -> &'static str {
+        match self {
+            Wilds::Zero => "No joker",
+            Wilds::One => "Joker",
+            _ => "Jokers",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WildClass {
+    NaturalRoyalFlush,
+    WildRoyalFlush,
+    FiveOfAKind,
+    StraightFlush,
+    FourOfAKind,
+    FullHouse,
+    Flush,
+    Straight,
+    ThreeOfAKind,
+    TwoPair,
+    FourToRoyalFlush,
+    FourToStraightFlush,
+    HighPair,
+    ThreeToRoyalFlush,
+    ThreeToStraightFlush,
+    Pair,
+    FourToFlush,
+    FourToOutsideStraight,
+    FourToStraight,
+    FourToInsideStraight,
+    TwoToRoyalFlush,
+    HighCard,
+    /// Only the wild cards
+    WildsOnly,
+}
+
+impl WildClass {
+    pub fn all() -> Vec<WildClass> {
+        use WildClass::*;
+        vec![
+            NaturalRoyalFlush, WildRoyalFlush, FiveOfAKind, StraightFlush, FourOfAKind, FullHouse,
+            Flush, Straight, ThreeOfAKind, TwoPair, FourToRoyalFlush, FourToStraightFlush, HighPair,
+            ThreeToRoyalFlush, ThreeToStraightFlush, Pair, FourToFlush, FourToOutsideStraight,
+            FourToStraight, FourToInsideStraight, TwoToRoyalFlush, HighCard, WildsOnly,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            WildClass::NaturalRoyalFlush => "Natural royal flush",
+            WildClass::WildRoyalFlush => "Wild royal flush",
+            WildClass::FiveOfAKind => "Five of a kind",
+            WildClass::StraightFlush => "Straight flush",
+            WildClass::FourOfAKind => "Four of a kind",
+            WildClass::FullHouse => "Full house",
+            WildClass::Flush => "Flush",
+            WildClass::Straight => "Straight",
+            WildClass::ThreeOfAKind => "Three of a kind",
+            WildClass::TwoPair => "Two pair",
+            WildClass::FourToRoyalFlush => "4 to a royal flush",
+            WildClass::FourToStraightFlush => "4 to a straight flush",
+            WildClass::HighPair => "Kings or better",
+            WildClass::ThreeToRoyalFlush => "3 to a royal flush",
+            WildClass::ThreeToStraightFlush => "3 to a straight flush",
+            WildClass::Pair => "Pair",
+            WildClass::FourToFlush => "4 to a flush",
+            WildClass::FourToOutsideStraight => "4 to an outside straight",
+            WildClass::FourToStraight => "4 to a straight",
+            WildClass::FourToInsideStraight => "4 to an inside straight",
+            WildClass::TwoToRoyalFlush => "2 to a royal flush",
+            WildClass::HighCard => "King or ace",
+            WildClass::WildsOnly => "nothing else",
+        }
+    }
+}
+
+/// Whether the natural card values fit one five-rank straight window (ace high or low)
+fn fits_straight(values: &[u8]) -> bool {
+    (1..=10u8).any(|low| values.iter().all(|&v| {
+        (v >= low && v < low + 5) || (v == 14 && low == 1)
+    }))
+}
+
+/// Classify a hold of a wild card game. `is_wild` picks out the wild cards and
+/// `pays_pair` the natural pairs that pay (kings or better in Joker Poker).
+pub fn classify_wild_hold(held: &[Card], is_wild: impl Fn(&Card) -> bool, pays_pair: impl Fn(Rank) -> bool)
+    -> Option<(Wilds, WildClass)>
+{
+    let naturals: Vec<Card> = held.iter().filter(|c| !is_wild(c)).cloned().collect();
+    let wilds = held.len() - naturals.len();
+    let w = Wilds::from_count(wilds);
+    if naturals.is_empty() {
+        return if wilds > 0 { Some((w, WildClass::WildsOnly)) } else { None };
+    }
+
+    let natural_hand = Hand::new(naturals.clone());
+    let mut counts: Vec<usize> = natural_hand.rank_counts().values().cloned().collect();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    let ranks = natural_hand.ranks();
+    let mut values: Vec<u8> = ranks.iter().map(|r| r.value()).collect();
+    values.sort_unstable();
+    let distinct = counts[0] == 1;
+    let suited = natural_hand.suit_counts().len() == 1;
+    let royal = ranks.iter().all(|r| r.is_high());
+    let straight_ranks = distinct && fits_straight(&values);
+    let top = counts[0] + wilds;
+
+    if held.len() == 5 {
+        return match () {
+            _ if straight_ranks && suited && royal && wilds == 0 => Some((w, WildClass::NaturalRoyalFlush)),
+            _ if straight_ranks && suited && royal => Some((w, WildClass::WildRoyalFlush)),
+            _ if top >= 5 => Some((w, WildClass::FiveOfAKind)),
+            _ if straight_ranks && suited => Some((w, WildClass::StraightFlush)),
+            _ if top == 4 => Some((w, WildClass::FourOfAKind)),
+            _ if counts == [3, 2] || (counts == [2, 2] && wilds == 1) => Some((w, WildClass::FullHouse)),
+            _ if suited => Some((w, WildClass::Flush)),
+            _ if straight_ranks => Some((w, WildClass::Straight)),
+            _ if top == 3 => Some((w, WildClass::ThreeOfAKind)),
+            _ if counts == [2, 2, 1] => Some((w, WildClass::TwoPair)),
+            _ => None,
+        };
+    }
+
+    if counts.len() == 1 {
+        // One natural rank, filled out by the wilds
+        return match top {
+            4 => Some((w, WildClass::FourOfAKind)),
+            3 => Some((w, WildClass::ThreeOfAKind)),
+            2 if pays_pair(ranks[0]) => Some((w, WildClass::HighPair)),
+            2 if wilds == 0 => Some((w, WildClass::Pair)),
+            1 if pays_pair(ranks[0]) => Some((w, WildClass::HighCard)),
+            _ => None,
+        };
+    }
+    if counts == [2, 2] && wilds == 0 {
+        return Some((w, WildClass::TwoPair));
+    }
+    if !distinct {
+        return None;
+    }
+    match (held.len(), suited && straight_ranks) {
+        (4, true) if royal => Some((w, WildClass::FourToRoyalFlush)),
+        (4, true) => Some((w, WildClass::FourToStraightFlush)),
+        (3, true) if royal => Some((w, WildClass::ThreeToRoyalFlush)),
+        (3, true) => Some((w, WildClass::ThreeToStraightFlush)),
+        (2, true) if royal && wilds == 0 => Some((w, WildClass::TwoToRoyalFlush)),
+        (4, false) if suited => Some((w, WildClass::FourToFlush)),
+        (4, false) if straight_ranks && wilds > 0 => Some((w, WildClass::FourToStraight)),
+        (4, false) if is_outside_straight_draw(&ranks).is_some() => Some((w, WildClass::FourToOutsideStraight)),
+        (4, false) if straight_ranks => Some((w, WildClass::FourToInsideStraight)),
+        _ => None,
+    }
+}
@@ -0,0 +1,204 @@
+// ============================================================================
+// CROSS-PLAY RETURN MATRIX
+// ============================================================================
+//
+// Return of every paytable in a game family when played with another paytable's
+// optimal holds. Each cell is a full compute_distribution run of paytable B with
+// A's holds, so it is exact rather than read off the rounded EVs in B's strategy
+// file. The diagonal is each paytable's own optimal return, and the cost of a cell
+// is the diagonal entry of its column minus the cell.
+
+use super::*;
+
+#[derive(Serialize)]
+pub struct CrossPlayMatrix {
+    pub family: String,
+    /// Paytables played (columns)
+    pub paytables: Vec<String>,
+    /// Paytables whose optimal holds are used (rows): the family, then any extra strategies
+    pub strategies: Vec<String>,
+    /// returns[row][column]: return of the column's paytable with the row's holds
+    pub returns: Vec<Vec<f64>>,
+    /// Optimal return of each column's paytable
+    pub optimal: Vec<f64>,
+    /// costs[row][column]: optimal[column] - returns[row][column]
+    pub costs: Vec<Vec<f64>>,
+}
+
+/// "DoubleDoubleBonus" -> "double_double_bonus"
+fn family_file_name(family: GameFamily) -> String {
+    let mut name = String::new();
+    for (i, c) in format!("{:?}", family).chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
+
+fn strategy_path(strategies_dir: &str, id: &str) -> std::path::PathBuf {
+    Path::new(strategies_dir).join(format!("strategy_{}.vpstrat2", id.replace("-", "_")))
+}
+
+/// Paytables dealt from their family's usual deck: no custom deck, no WWW added wilds and
+/// no Pick'em, whose holds are not five-card hold masks
+fn cross_playable(paytable: &Paytable) -> bool {
+    paytable.deck.is_none() && !paytable.is_www() && paytable.game_family != GameFamily::PickEm
+}
+
+/// Families to build, each with its paytables in catalog order. `filters` are family
+/// names in any case, with or without separators ("DoubleDoubleBonus", "double-double-bonus"),
+/// or paytable ids standing for their family; empty = all.
+fn select_families(strategies_dir: &str, filters: &[String]) -> Result<Vec<(GameFamily, Vec<Paytable>)>, String> {
+    let mut wanted: Vec<GameFamily> = Vec::new();
+    for f in filters {
+        let family = match get_paytable(f) {
+            Some(pt) => pt.game_family,
+            None => get_all_paytable_ids().into_iter()
+                .filter_map(get_paytable)
+                .map(|pt| pt.game_family)
+                .find(|family| format!("{:?}", family).eq_ignore_ascii_case(&f.replace(['-', '_'], "")))
+                .ok_or_else(|| format!("Unknown paytable or game family: {}", f))?,
+        };
+        wanted.push(family);
+    }
+
+    let mut families: Vec<(GameFamily, Vec<Paytable>)> = Vec::new();
+    for id in get_all_paytable_ids() {
+        let Some(paytable) = get_paytable(id) else { continue };
+        if !cross_playable(&paytable) || !strategy_path(strategies_dir, id).exists() {
+            continue;
+        }
+        if !wanted.is_empty() && !wanted.contains(&paytable.game_family) {
+            continue;
+        }
+        match families.iter_mut().find(|(family, _)| *family == paytable.game_family) {
+            Some((_, paytables)) => paytables.push(paytable),
+            None => families.push((paytable.game_family, vec![paytable])),
+        }
+    }
+    Ok(families)
+}
+
+pub fn run_cross_play(filters: &[String], extra_ids: &[String], strategies_dir: &str, output_dir: &str) {
+    let families = match select_families(strategies_dir, filters) {
+        Ok(f) => f,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let mut extras: Vec<(Paytable, HashMap<String, u8>)> = Vec::new();
+    for id in extra_ids {
+        let Some(paytable) = get_paytable(id).filter(cross_playable) else {
+            eprintln!("Unknown or unsupported paytable: {}", id);
+            std::process::exit(1);
+        };
+        match read_vpstrat2_holds(&strategy_path(strategies_dir, id).to_string_lossy()) {
+            Ok(holds) => extras.push((paytable, holds)),
+            Err(e) => {
+                eprintln!("Failed to load strategy for {}: {}", id, e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          CROSS-PLAY RETURN MATRIX                                ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    if families.is_empty() {
+        println!("No paytables with strategy files in {}", strategies_dir);
+        return;
+    }
+    if let Err(e) = fs::create_dir_all(output_dir) {
+        eprintln!("Failed to create {}: {}", output_dir, e);
+        std::process::exit(1);
+    }
+
+    let overall_start = Instant::now();
+    let mut canonical_by_jokers: HashMap<u8, Vec<(String, Hand, u64)>> = HashMap::new();
+    for (family, paytables) in &families {
+        let num_jokers = paytables[0].num_jokers();
+        let mut strategies: Vec<(String, HashMap<String, u8>)> = Vec::new();
+        for paytable in paytables {
+            match read_vpstrat2_holds(&strategy_path(strategies_dir, &paytable.id).to_string_lossy()) {
+                Ok(holds) => strategies.push((paytable.id.clone(), holds)),
+                Err(e) => {
+                    eprintln!("Failed to load strategy for {}: {}", paytable.id, e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        for (extra, holds) in &extras {
+            if extra.num_jokers() == num_jokers && !paytables.iter().any(|pt| pt.id == extra.id) {
+                strategies.push((extra.id.clone(), holds.clone()));
+            }
+        }
+        if strategies.len() < 2 {
+            println!("{:?}: only {} has a strategy file, skipping\n", family, paytables[0].id);
+            continue;
+        }
+
+        println!("┌──────────────────────────────────────────────────────────────────┐");
+        println!("│ {:<64} │", format!("{:?}: {} paytables x {} strategies", family, paytables.len(), strategies.len()));
+        println!("└──────────────────────────────────────────────────────────────────┘");
+        let canonical = canonical_by_jokers.entry(num_jokers)
+            .or_insert_with(|| generate_canonical_hands_with_multiplicity(num_jokers, false));
+
+        let mut returns = vec![vec![0.0; paytables.len()]; strategies.len()];
+        for (col, paytable) in paytables.iter().enumerate() {
+            for (row, (id, holds)) in strategies.iter().enumerate() {
+                print!("  {} on {}... ", id, paytable.id);
+                io::stdout().flush().unwrap();
+                let calc_start = Instant::now();
+                let (_, overall_return) = compute_distribution(paytable, holds, canonical);
+                println!("{:.4}% ({:.1}s)", overall_return * 100.0, calc_start.elapsed().as_secs_f64());
+                returns[row][col] = overall_return;
+            }
+        }
+        let optimal: Vec<f64> = (0..paytables.len()).map(|col| returns[col][col]).collect();
+        let costs: Vec<Vec<f64>> = returns.iter()
+            .map(|row| row.iter().zip(&optimal).map(|(r, best)| best - r).collect())
+            .collect();
+
+        println!();
+        println!("  Cost of each strategy (rows) on each paytable (columns), % of wager:");
+        print!("  {:<36}", "");
+        for col in 0..paytables.len() {
+            print!(" {:>7}", format!("[{}]", col + 1));
+        }
+        println!();
+        for (row, (id, _)) in strategies.iter().enumerate() {
+            let label = match paytables.iter().position(|pt| &pt.id == id) {
+                Some(col) => format!("[{}] {}", col + 1, id),
+                None => format!("+ {}", id),
+            };
+            print!("  {:<36}", label);
+            for cost in &costs[row] {
+                print!(" {:>7.3}", cost * 100.0);
+            }
+            println!();
+        }
+        println!();
+
+        let matrix = CrossPlayMatrix {
+            family: format!("{:?}", family),
+            paytables: paytables.iter().map(|pt| pt.id.clone()).collect(),
+            strategies: strategies.iter().map(|(id, _)| id.clone()).collect(),
+            returns,
+            optimal,
+            costs,
+        };
+        let path = Path::new(output_dir).join(format!("crossplay_{}.json", family_file_name(*family)));
+        match serde_json::to_string_pretty(&matrix) {
+            Ok(json) => match fs::write(&path, json) {
+                Ok(_) => println!("  ✓ Matrix: {}\n", path.display()),
+                Err(e) => eprintln!("  ✗ Failed to write matrix: {}\n", e),
+            },
+            Err(e) => eprintln!("  ✗ Failed to serialize matrix: {}\n", e),
+        }
+    }
+    println!("Done in {}", format_duration(overall_start.elapsed().as_secs()));
+}
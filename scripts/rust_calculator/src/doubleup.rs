@@ -0,0 +1,215 @@
+// ============================================================================
+// DOUBLE-UP
+// ============================================================================
+//
+// After a win the player may risk the whole win on a card-vs-dealer comparison:
+// higher card doubles it, lower card loses it, a tie is replayed. The gamble is
+// even money, so the return is unchanged; what moves is the variance and the shape
+// of the payout distribution. Everything here starts from the base game's hand
+// distribution under its own optimal strategy (the double-up never changes holds).
+
+use super::*;
+
+/// When to take a double-up offer on a win of `amount` (per coin bet)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DoubleUpPolicy {
+    /// Never double (the base game)
+    Never,
+    /// Double every win until it is lost or the machine limit is reached
+    Always,
+    /// Keep doubling while the win is below this amount
+    Threshold(f64),
+    /// Double at most N times per win, then collect
+    StopAt(u32),
+}
+
+impl DoubleUpPolicy {
+    /// Parses "never", "always", "threshold:<amount>" or "stop:<N>"
+    pub fn parse(s: &str) -> Result<DoubleUpPolicy, String> {
+        match s.split_once(':') {
+            None if s == "never" => Ok(DoubleUpPolicy::Never),
+            None if s == "always" => Ok(DoubleUpPolicy::Always),
+            Some(("threshold", amount)) => amount.parse::<f64>()
+                .ok()
+                .filter(|a| *a > 0.0)
+                .map(DoubleUpPolicy::Threshold)
+                .ok_or_else(|| format!("Invalid threshold amount: {}", amount)),
+            Some(("stop", n)) => n.parse::<u32>()
+                .map(DoubleUpPolicy::StopAt)
+                .map_err(|_| format!("Invalid double count: {}", n)),
+            _ => Err(format!("Unknown double-up policy '{}' (never, always, threshold:<amount>, stop:<N>)", s)),
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            DoubleUpPolicy::Never => "never".to_string(),
+            DoubleUpPolicy::Always => "always".to_string(),
+            DoubleUpPolicy::Threshold(amount) => format!("threshold:{}", amount),
+            DoubleUpPolicy::StopAt(n) => format!("stop:{}", n),
+        }
+    }
+
+    /// Doubles attempted on a win of `amount` if every one of them is won
+    fn doubles_for(&self, amount: f64, max_doubles: u32) -> u32 {
+        let wanted = match *self {
+            DoubleUpPolicy::Never => 0,
+            DoubleUpPolicy::Always => max_doubles,
+            DoubleUpPolicy::StopAt(n) => n,
+            DoubleUpPolicy::Threshold(target) => {
+                let mut k = 0;
+                let mut current = amount;
+                while current < target && k < max_doubles {
+                    current *= 2.0;
+                    k += 1;
+                }
+                k
+            }
+        };
+        wanted.min(max_doubles)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DoubleUpRules {
+    /// Most doubles the machine allows on one win
+    pub max_doubles: u32,
+    /// Chance a single comparison ties and is replayed
+    pub tie_probability: f64,
+}
+
+impl Default for DoubleUpRules {
+    /// Player and dealer cards from a fresh 52-card deck: 3 of the 51 dealer cards tie
+    fn default() -> Self {
+        DoubleUpRules { max_doubles: 5, tie_probability: 3.0 / 51.0 }
+    }
+}
+
+#[derive(Serialize)]
+pub struct DoubleUpResult {
+    pub policy: String,
+    pub return_pct: f64,
+    pub variance: f64,
+    pub std_dev: f64,
+    /// Probability a hand ends with a positive pay
+    pub hit_frequency: f64,
+    /// Resolved doubles (ties not counted) per hand played
+    pub doubles_per_hand: f64,
+    /// Dealer comparisons, ties included, per hand played
+    pub comparisons_per_hand: f64,
+    /// Resolved doubles per winning hand
+    pub doubles_per_win: f64,
+    /// Final pay per coin -> probability, ascending by pay
+    pub distribution: Vec<(f64, f64)>,
+}
+
+/// Apply a double-up policy to a distribution from compute_distribution
+/// (hand type -> (probability, payout, order)). Ties are replayed, so each double
+/// resolves as an even-money coin flip. Pays are per coin of the base game, so the
+/// return and variance are divided by `bet_cost` to be per unit wagered.
+pub fn evaluate_double_up(
+    distribution: &HashMap<String, (f64, f64, usize)>,
+    policy: DoubleUpPolicy,
+    rules: &DoubleUpRules,
+    bet_cost: f64,
+) -> DoubleUpResult {
+    let win: f64 = 0.5;
+    let mut outcomes: HashMap<u64, (f64, f64)> = HashMap::new();
+    let mut add = |pay: f64, prob: f64| {
+        if prob > 0.0 {
+            outcomes.entry(pay.to_bits()).or_insert((pay, 0.0)).1 += prob;
+        }
+    };
+    let mut doubles = 0.0;
+    let mut win_prob = 0.0;
+
+    for &(prob, payout, _) in distribution.values() {
+        if payout <= 0.0 {
+            add(0.0, prob);
+            continue;
+        }
+        win_prob += prob;
+        let k = policy.doubles_for(payout, rules.max_doubles);
+        let survive = win.powi(k as i32);
+        add(payout * 2f64.powi(k as i32), prob * survive);
+        add(0.0, prob * (1.0 - survive));
+        // Double j+1 is attempted only after the first j are won
+        doubles += prob * (0..k).map(|j| win.powi(j as i32)).sum::<f64>();
+    }
+
+    let mut outcomes: Vec<(f64, f64)> = outcomes.into_values().collect();
+    outcomes.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mean: f64 = outcomes.iter().map(|(pay, p)| pay * p).sum::<f64>() / bet_cost;
+    let second: f64 = outcomes.iter().map(|(pay, p)| pay * pay * p).sum::<f64>() / (bet_cost * bet_cost);
+    let variance = (second - mean * mean).max(0.0);
+    let hit_frequency = outcomes.iter().filter(|(pay, _)| *pay > 0.0).map(|(_, p)| p).sum();
+
+    DoubleUpResult {
+        policy: policy.label(),
+        return_pct: mean * 100.0,
+        variance,
+        std_dev: variance.sqrt(),
+        hit_frequency,
+        doubles_per_hand: doubles,
+        comparisons_per_hand: doubles / (1.0 - rules.tie_probability),
+        doubles_per_win: if win_prob > 0.0 { doubles / win_prob } else { 0.0 },
+        distribution: outcomes,
+    }
+}
+
+pub fn run_double_up(paytable_id: &str, strategies_dir: &str, policies: &[DoubleUpPolicy], rules: &DoubleUpRules) {
+    let paytable = match get_paytable(paytable_id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown paytable: {}", paytable_id);
+            std::process::exit(1);
+        }
+    };
+
+    let filename = format!("strategy_{}.vpstrat2", paytable.id.replace("-", "_"));
+    let strat_path = Path::new(strategies_dir).join(&filename);
+    let holds = match read_vpstrat2_holds(&strat_path.to_string_lossy()) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", strat_path.to_string_lossy(), e);
+            std::process::exit(1);
+        }
+    };
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          DOUBLE-UP ANALYSIS                                      ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Paytable: {} ({})", paytable.name, paytable.id);
+    println!("Machine limit: {} doubles per win | Tie (replayed): {:.4}%",
+        rules.max_doubles, rules.tie_probability * 100.0);
+    println!();
+
+    let canonical = canonical_hands_with_multiplicity_for_paytable(&paytable);
+    print!("  Computing base distribution ({} canonical hands)... ", canonical.len());
+    io::stdout().flush().unwrap();
+    let (distribution, _) = compute_distribution(&paytable, &holds, &canonical);
+
+    let mut all = vec![DoubleUpPolicy::Never];
+    all.extend(policies.iter().filter(|p| **p != DoubleUpPolicy::Never));
+    let results: Vec<DoubleUpResult> = all.iter().map(|p| evaluate_double_up(&distribution, *p, rules, paytable.bet_cost())).collect();
+
+    println!();
+    println!("  ┌──────────────────┬───────────┬──────────┬──────────┬──────────┬──────────┐");
+    println!("  │ Policy           │ Return    │ Std dev  │ Hit freq │ Doubles  │ Per win  │");
+    println!("  ├──────────────────┼───────────┼──────────┼──────────┼──────────┼──────────┤");
+    for r in &results {
+        println!("  │ {:<16} │ {:>8.4}% │ {:>8.4} │ {:>7.3}% │ {:>8.4} │ {:>8.4} │",
+            r.policy, r.return_pct, r.std_dev, r.hit_frequency * 100.0, r.doubles_per_hand, r.doubles_per_win);
+    }
+    println!("  └──────────────────┴───────────┴──────────┴──────────┴──────────┴──────────┘");
+
+    for r in results.iter().skip(1) {
+        println!();
+        println!("  {} — final pay distribution ({:.4} dealer cards per hand):", r.policy, r.comparisons_per_hand);
+        for (pay, prob) in &r.distribution {
+            println!("    {:>10.1}  {:>12.8}", pay, prob);
+        }
+    }
+}
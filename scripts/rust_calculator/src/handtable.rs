@@ -0,0 +1,228 @@
+// ============================================================================
+// PRECOMPUTED HAND TYPES
+// ============================================================================
+//
+// evaluate_hand counts ranks, splits off wild cards and sorts small vectors on every
+// call, and the EV engine calls it millions of times per dealt hand. A HandTable
+// classifies every five-card hand of a paytable's deck once and answers later
+// lookups by index: each card maps to its position in the deck, and the sorted
+// positions p0 < p1 < … < p4 index the hand as C(p0,1) + C(p1,2) + … + C(p4,5)
+// (the combinatorial number system), one byte per hand.
+
+use super::*;
+use std::sync::Mutex;
+
+pub struct HandTable {
+    /// Position of each card in the deck, by card id (255 = not in the deck)
+    position: [u8; 256],
+    /// choose[n][k] = C(n, k) for k <= 5
+    choose: Vec<[usize; 6]>,
+    types: Vec<HandType>,
+}
+
+impl HandTable {
+    pub fn build(paytable: &Paytable) -> HandTable {
+        let deck = paytable.deck_cards();
+        let n = deck.len();
+        let start = Instant::now();
+        print!("  Building hand table ({}-card deck)... ", n);
+        io::stdout().flush().unwrap();
+
+        let mut position = [255u8; 256];
+        for (i, card) in deck.iter().enumerate() {
+            position[card.0 as usize] = i as u8;
+        }
+        let mut choose = vec![[0usize; 6]; n + 1];
+        for (m, row) in choose.iter_mut().enumerate() {
+            row[0] = 1;
+            for k in 1..6 {
+                // C(m, k) = C(m, k-1) * (m-k+1) / k
+                row[k] = row[k - 1] * (m + 1).saturating_sub(k) / k;
+            }
+        }
+
+        // Colex order: every hand whose highest card is at position c4 comes after all
+        // hands with a lower top card, so each top card fills one contiguous block
+        let blocks: Vec<Vec<HandType>> = (4..n)
+            .into_par_iter()
+            .map(|c4| {
+                let mut block = Vec::with_capacity(choose[c4][4]);
+                for c3 in 3..c4 {
+                    for c2 in 2..c3 {
+                        for c1 in 1..c2 {
+                            for c0 in 0..c1 {
+                                let hand = [deck[c0], deck[c1], deck[c2], deck[c3], deck[c4]];
+                                block.push(evaluate_hand(&hand, paytable));
+                            }
+                        }
+                    }
+                }
+                block
+            })
+            .collect();
+        let types = blocks.concat();
+
+        println!("{} hands in {:.1}s", types.len(), start.elapsed().as_secs_f64());
+        HandTable { position, choose, types }
+    }
+
+    /// Hand type of five distinct cards from the table's deck, in any order
+    pub fn hand_type(&self, hand: &[Card; 5]) -> HandType {
+        let mut p = hand.map(|c| self.position[c.0 as usize] as usize);
+        debug_assert!(p.iter().all(|&i| i != 255), "card outside the paytable's deck");
+        p.sort_unstable();
+        self.types[self.choose[p[0]][1] + self.choose[p[1]][2] + self.choose[p[2]][3]
+            + self.choose[p[3]][4] + self.choose[p[4]][5]]
+    }
+
+    pub fn hand_count(&self) -> usize {
+        self.types.len()
+    }
+}
+
+/// Tables kept for reuse, least recently used first. Batch runs move from paytable to
+/// paytable, so only a few need to stay resident (about 2.6 MB each for 52 cards).
+const HAND_TABLE_CACHE_SIZE: usize = 8;
+static HAND_TABLES: Mutex<Vec<(String, Arc<HandTable>)>> = Mutex::new(Vec::new());
+
+/// Cache key: the paytable with everything the evaluators don't read cleared. Outside
+/// custom decks a hand's type depends only on which pays are offered and whether they
+/// are positive, so paytables that differ only in amounts share a table. Custom decks
+/// pick the best-paying hand, so their amounts stay in the key.
+fn table_key(paytable: &Paytable) -> String {
+    let mut pt = paytable.clone();
+    pt.id.clear();
+    pt.name.clear();
+    if pt.deck.is_none() {
+        for (name, value) in paytable.payout_fields() {
+            if let Some(v) = value {
+                pt.put_payout_field(name, if v > 0.0 { 1.0 } else { 0.0 })
+                    .expect("payout_fields names are settable");
+            }
+        }
+    }
+    if let Some(www) = &mut pt.www {
+        // Already applied to the pay fields above
+        www.base_id.clear();
+        www.overrides.clear();
+    }
+    format!("{:?}", pt)
+}
+
+/// The paytable's hand table, built on first use
+pub fn hand_table(paytable: &Paytable) -> Arc<HandTable> {
+    let key = table_key(paytable);
+    let mut tables = HAND_TABLES.lock().unwrap();
+    if let Some(i) = tables.iter().position(|(k, _)| *k == key) {
+        let entry = tables.remove(i);
+        let table = entry.1.clone();
+        tables.push(entry);
+        return table;
+    }
+    let table = Arc::new(HandTable::build(paytable));
+    if tables.len() >= HAND_TABLE_CACHE_SIZE {
+        tables.remove(0);
+    }
+    tables.push((key, table.clone()));
+    table
+}
+
+/// Differential test: every hand of every evaluation scheme (catalog paytables, WWW wild
+/// counts, bug joker, Quick Quads and custom decks) looked up in its table must match
+/// evaluate_hand. Cards are passed high to low so lookups don't rely on deal order.
+pub fn run_hand_table_tests(filter: Option<&str>) {
+    println!("=== Hand Table Tests ===\n");
+
+    let mut paytables: Vec<Paytable> = Vec::new();
+    for id in get_all_paytable_ids() {
+        let Some(pt) = get_paytable(id) else { continue };
+        if pt.game_family == GameFamily::DeucesJokerWild {
+            // The deuces evaluator doesn't place the joker yet, so these can't be built
+            println!("? {}: skipped, Deuces Joker Wild has no evaluator", id);
+            continue;
+        }
+        if www_wild_distribution(id).is_some() {
+            for wilds in 0..=MAX_WWW_WILDS {
+                paytables.extend(get_paytable(&format!("www-{}-{}w", id, wilds)));
+            }
+        }
+        if pt.is_joker_poker() {
+            paytables.extend(pt.bug_joker().ok());
+        }
+        paytables.push(pt);
+    }
+    let decks = [
+        DeckSpec { wild_ranks: vec![5], ..Default::default() },
+        DeckSpec { wild_cards: vec![Card(9 * 4), Card(9 * 4 + 3)], ..Default::default() },
+        DeckSpec { extra_ranks: vec![12], ..Default::default() },
+        DeckSpec { stripped_ranks: vec![0, 1, 2, 3], ..Default::default() },
+        DeckSpec { jokers: 1, wild_ranks: vec![0], ..Default::default() },
+    ];
+    for base_id in ["jacks-or-better-9-6", "double-double-bonus-9-6", "double-jackpot-8-5", "deuces-wild-full-pay"] {
+        for (i, deck) in decks.iter().enumerate() {
+            let Some(mut pt) = get_paytable(base_id) else { continue };
+            pt.id = format!("{}+deck{}", base_id, i + 1);
+            pt.deck = Some(deck.clone());
+            if pt.validate().is_ok() {
+                paytables.push(pt);
+            }
+        }
+    }
+    if let Some(f) = filter {
+        paytables.retain(|pt| pt.id.contains(f));
+    }
+
+    // Paytables with the same key share a table; check each scheme once
+    let mut schemes: Vec<(String, Vec<String>, &Paytable)> = Vec::new();
+    for pt in &paytables {
+        let key = table_key(pt);
+        match schemes.iter_mut().find(|(k, _, _)| *k == key) {
+            Some((_, ids, _)) => ids.push(pt.id.clone()),
+            None => schemes.push((key, vec![pt.id.clone()], pt)),
+        }
+    }
+    println!("{} paytables, {} distinct evaluation schemes\n", paytables.len(), schemes.len());
+
+    let mut passed = 0;
+    let mut failed = 0;
+    for (_, ids, paytable) in &schemes {
+        let table = HandTable::build(paytable);
+        let deck = paytable.deck_cards();
+        let n = deck.len();
+        let mut mismatches = 0u64;
+        let mut first: Option<String> = None;
+        for a in 0..n {
+            for b in (a + 1)..n {
+                for c in (b + 1)..n {
+                    for d in (c + 1)..n {
+                        for e in (d + 1)..n {
+                            let hand = [deck[e], deck[d], deck[c], deck[b], deck[a]];
+                            let expected = evaluate_hand(&hand, paytable);
+                            let actual = table.hand_type(&hand);
+                            if actual != expected {
+                                mismatches += 1;
+                                first.get_or_insert_with(|| format!("{} = {} (expected {})",
+                                    hand_to_string(&hand), actual.name(), expected.name()));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let shared = if ids.len() > 1 { format!(" (+{} sharing)", ids.len() - 1) } else { String::new() };
+        if mismatches == 0 {
+            passed += 1;
+            println!("✓ {}{}: {} hands", ids[0], shared, table.hand_count());
+        } else {
+            failed += 1;
+            println!("✗ {}{}: {} of {} hands differ, e.g. {}",
+                ids[0], shared, mismatches, table.hand_count(), first.unwrap_or_default());
+        }
+    }
+
+    println!("\n=== Results: {} passed, {} failed ===", passed, failed);
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,396 @@
+// ============================================================================
+// PAYTABLE IDENTIFICATION
+// ============================================================================
+//
+// Matches a partial set of observed pays (as read off a machine's pay table) against
+// the catalog. Each observation names a hand, by payout field or by HandType name,
+// and is compared with what the paytable actually pays for that hand, so unset
+// tiers fall back the same way they do in Paytable::payout (four aces on Jacks or
+// Better pays 25). Catalog paytables are ranked by how many observations they
+// reproduce; when none reproduces them all, the closest ones with the observed pays
+// substituted are offered as paytables that can be generated on the fly.
+//
+// Request (JSON):  {"observations": [{"hand": "Full House", "pay": 9}, ...],
+//                   "coins": 5, "family": "JacksOrBetter", "limit": 10}
+// `coins` is the column the pays were read from; without it pays are per coin at max bet.
+
+use super::*;
+
+const DEFAULT_LIMIT: usize = 10;
+/// Closest catalog paytables turned into generated suggestions when nothing matches
+const MAX_GENERATED: usize = 3;
+
+/// Hand types a player can read off a pay table, with the payout field that sets them
+const HAND_FIELDS: &[(HandType, &str)] = &[
+    (HandType::JacksOrBetter, "high_pair"),
+    (HandType::TensOrBetter, "high_pair"),
+    (HandType::KingsOrBetter, "high_pair"),
+    (HandType::TwoPair, "two_pair"),
+    (HandType::ThreeOfAKind, "three_of_a_kind"),
+    (HandType::Straight, "straight"),
+    (HandType::Flush, "flush"),
+    (HandType::FullHouse, "full_house"),
+    (HandType::FourOfAKind, "four_of_a_kind"),
+    (HandType::Four5ToK, "four_5_k"),
+    (HandType::Four2To4, "four_2_4"),
+    (HandType::FourAces, "four_aces"),
+    (HandType::FourJToK, "four_jqk"),
+    (HandType::Four8s, "four_8s"),
+    (HandType::Four7s, "four_7s"),
+    (HandType::FourAcesWith2To4, "four_aces_with_kicker"),
+    (HandType::Four2To4WithAceTo4, "four_2_4_with_kicker"),
+    (HandType::FourAcesWithFace, "four_aces_with_face"),
+    (HandType::FourJToKWithFace, "four_jqk_with_face"),
+    (HandType::StraightFlush, "straight_flush"),
+    (HandType::FiveOfAKind, "five_of_a_kind"),
+    (HandType::FiveAces, "five_aces"),
+    (HandType::Five2To4, "five_2_4"),
+    (HandType::Five3To5, "five_2_4"),
+    (HandType::Five5ToK, "five_5_k"),
+    (HandType::Five6ToK, "five_5_k"),
+    (HandType::FiveJToK, "five_jqk"),
+    (HandType::Five5To10, "five_5_10"),
+    (HandType::WildRoyalFlush, "wild_royal"),
+    (HandType::FourDeuces, "four_deuces"),
+    (HandType::FiveDeuces, "five_deuces"),
+    (HandType::FourWilds, "four_deuces"),
+    (HandType::FiveWilds, "five_deuces"),
+    (HandType::RoyalFlush, "royal_flush"),
+    (HandType::NaturalRoyalFlush, "royal_flush"),
+];
+
+#[derive(Deserialize)]
+pub struct IdentifyRequest {
+    pub observations: Vec<Observation>,
+    /// Coins bet in the pay table column the pays were read from (None = per coin at max bet)
+    #[serde(default)]
+    pub coins: Option<u8>,
+    /// Only consider this game family (any case, separators ignored)
+    #[serde(default)]
+    pub family: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Deserialize)]
+pub struct Observation {
+    pub hand: String,
+    pub pay: f64,
+}
+
+#[derive(Serialize)]
+pub struct ResolvedObservation {
+    pub hand: String,
+    pub hand_type: String,
+    pub field: String,
+    pub pay_per_coin: f64,
+}
+
+#[derive(Serialize)]
+pub struct Mismatch {
+    pub hand: String,
+    pub observed: f64,
+    /// What the paytable pays for the hand; None if the game has no such hand
+    pub catalog: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct Candidate {
+    pub paytable_id: String,
+    pub name: String,
+    pub game_family: String,
+    pub matched: usize,
+    pub consistent: bool,
+    pub mismatches: Vec<Mismatch>,
+    /// Optimal return from the paytable's strategy file, if one exists
+    pub return_pct: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct FieldOverride {
+    pub field: String,
+    pub value: f64,
+}
+
+#[derive(Serialize)]
+pub struct GeneratedPaytable {
+    pub base_paytable_id: String,
+    pub overrides: Vec<FieldOverride>,
+}
+
+#[derive(Serialize)]
+pub struct IdentifyResponse {
+    pub observations: Vec<ResolvedObservation>,
+    pub candidates: Vec<Candidate>,
+    /// No catalog paytable reproduces every observation
+    pub uncatalogued: bool,
+    /// Closest catalog paytables with the observed pays substituted, when uncatalogued
+    pub generated: Vec<GeneratedPaytable>,
+}
+
+/// "Four Aces w/ 2-4" -> "fouraceswith24", "four_aces" -> "fouraces"
+fn normalize(name: &str) -> String {
+    name.to_ascii_lowercase()
+        .replace("w/", "with")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+/// Hand type for an observed hand name, with the payout field that sets it. None stands
+/// for a paying pair without a named minimum ("Pair", "high_pair"), which is whatever
+/// pair the paytable pays.
+fn resolve_hand(name: &str) -> Result<(Option<HandType>, &'static str), String> {
+    let wanted = normalize(name);
+    if wanted == "pair" || wanted == "highpair" {
+        return Ok((None, "high_pair"));
+    }
+    HAND_FIELDS.iter()
+        .find(|(hand_type, _)| normalize(hand_type.name()) == wanted)
+        .or_else(|| HAND_FIELDS.iter().find(|(_, field)| normalize(field) == wanted))
+        .map(|&(hand_type, field)| (Some(hand_type), field))
+        .ok_or_else(|| format!("Unknown hand '{}'", name))
+}
+
+fn plays_wild(paytable: &Paytable) -> bool {
+    paytable.is_deuces_wild()
+        || paytable.num_jokers() > 0
+        || paytable.deck.as_ref().is_some_and(|d| !d.wild_ranks.is_empty() || !d.wild_cards.is_empty())
+}
+
+/// What `paytable` pays for an observed hand, or None if the game cannot make it
+fn catalog_pay(paytable: &Paytable, hand_type: Option<HandType>) -> Option<f64> {
+    let hand_type = match hand_type {
+        Some(t) => t,
+        None if paytable.min_pair_rank == 0 => return None,
+        None => HandType::high_pair(paytable),
+    };
+    let paying_pair = matches!(hand_type,
+        HandType::JacksOrBetter | HandType::TensOrBetter | HandType::KingsOrBetter);
+    if paying_pair && (paytable.min_pair_rank == 0 || hand_type != HandType::high_pair(paytable)) {
+        return None;
+    }
+    let wild_hand = matches!(hand_type,
+        HandType::FiveOfAKind | HandType::FiveAces | HandType::Five2To4 | HandType::Five3To5 |
+        HandType::Five5ToK | HandType::Five6ToK | HandType::FiveJToK | HandType::Five5To10 |
+        HandType::WildRoyalFlush | HandType::FourDeuces | HandType::FiveDeuces |
+        HandType::FourWilds | HandType::FiveWilds);
+    if wild_hand && !plays_wild(paytable) {
+        return None;
+    }
+    Some(paytable.payout(hand_type))
+}
+
+/// Pays are whole coins or simple fractions, so anything closer than this is the same pay
+fn same_pay(catalog: Option<f64>, observed: f64) -> bool {
+    catalog.is_some_and(|c| (c - observed).abs() < 1e-6)
+}
+
+/// Catalog paytables to match against: every playable catalog entry at the observed coins
+fn catalog(coins: Option<u8>, family: Option<&str>) -> Result<Vec<Paytable>, String> {
+    let family = family.map(|f| f.replace(['-', '_'], ""));
+    let mut paytables = Vec::new();
+    for id in get_all_paytable_ids() {
+        let Some(base) = get_paytable(id) else { continue };
+        if base.game_family == GameFamily::PickEm {
+            continue;
+        }
+        if let Some(f) = &family {
+            if !format!("{:?}", base.game_family).eq_ignore_ascii_case(f) {
+                continue;
+            }
+        }
+        let paytable = match coins {
+            Some(c) => match coin_paytable_id(id, c).and_then(|cid| get_paytable(&cid)) {
+                Some(pt) => pt,
+                None => continue,
+            },
+            None => base,
+        };
+        paytables.push(paytable);
+    }
+    if paytables.is_empty() {
+        return Err(match family {
+            Some(f) => format!("No catalog paytables in game family '{}'", f),
+            None => "No catalog paytables".to_string(),
+        });
+    }
+    Ok(paytables)
+}
+
+/// Optimal return of a catalog paytable from its strategy file
+fn strategy_file_return(
+    paytable: &Paytable,
+    strategies_dir: &str,
+    canonical_cache: &mut HashMap<String, Vec<(String, Hand, u64)>>,
+) -> Option<f64> {
+    let path = Path::new(strategies_dir).join(format!("strategy_{}.vpstrat2", paytable.id.replace("-", "_")));
+    if !path.exists() {
+        return None;
+    }
+    let entries = read_vpstrat2_entries(&path.to_string_lossy()).ok()?;
+    // Canonical hands depend only on the deck dealt
+    let deck_key = match &paytable.deck {
+        Some(_) => paytable.id.clone(),
+        None => format!("jokers{}", paytable.num_jokers()),
+    };
+    // Quietly: the response is the only thing written to stdout
+    let canonical = canonical_cache.entry(deck_key).or_insert_with(|| match &paytable.deck {
+        Some(deck) => generate_custom_deck_canonical_hands(deck, true),
+        None => generate_canonical_hands_with_multiplicity(paytable.num_jokers(), true),
+    });
+    strategy_return(paytable, &entries, canonical).ok().map(|(ret, _)| ret * 100.0)
+}
+
+pub fn identify(request: &IdentifyRequest, strategies_dir: &str) -> Result<IdentifyResponse, String> {
+    if request.observations.is_empty() {
+        return Err("No observations".to_string());
+    }
+    let coins = match request.coins {
+        Some(c) if !(1..=5).contains(&c) => return Err(format!("Coins must be between 1 and 5, got {}", c)),
+        other => other,
+    };
+    let per_coin = coins.map_or(1.0, |c| c as f64);
+
+    let mut observed: Vec<(Option<HandType>, &'static str, f64)> = Vec::new();
+    let mut resolved = Vec::new();
+    for obs in &request.observations {
+        if obs.pay < 0.0 || !obs.pay.is_finite() {
+            return Err(format!("Invalid pay {} for '{}'", obs.pay, obs.hand));
+        }
+        let (hand_type, field) = resolve_hand(&obs.hand)?;
+        let pay = obs.pay / per_coin;
+        if let Some(&(_, _, earlier)) = observed.iter().find(|(t, _, _)| *t == hand_type) {
+            if earlier != pay {
+                return Err(format!("'{}' observed with two pays ({} and {})", obs.hand, earlier * per_coin, obs.pay));
+            }
+            continue;
+        }
+        observed.push((hand_type, field, pay));
+        resolved.push(ResolvedObservation {
+            hand: obs.hand.clone(),
+            hand_type: hand_type.map_or("Pair", HandType::name).to_string(),
+            field: field.to_string(),
+            pay_per_coin: pay,
+        });
+    }
+
+    let paytables = catalog(coins, request.family.as_deref())?;
+    let mut scored: Vec<(Paytable, Vec<Mismatch>)> = paytables.into_iter()
+        .map(|paytable| {
+            let mismatches: Vec<Mismatch> = observed.iter()
+                .filter_map(|&(hand_type, _, pay)| {
+                    let catalog = catalog_pay(&paytable, hand_type);
+                    if same_pay(catalog, pay) {
+                        return None;
+                    }
+                    Some(Mismatch { hand: hand_type.map_or("Pair", HandType::name).to_string(), observed: pay, catalog })
+                })
+                .collect();
+            (paytable, mismatches)
+        })
+        .filter(|(_, mismatches)| mismatches.len() < observed.len())
+        .collect();
+    // Fewest mismatches first, then the ones that at least have every observed hand;
+    // the sort is stable, so ties stay in catalog order
+    scored.sort_by_key(|(_, mismatches)| {
+        (mismatches.len(), mismatches.iter().filter(|m| m.catalog.is_none()).count())
+    });
+
+    let uncatalogued = scored.first().is_none_or(|(_, mismatches)| !mismatches.is_empty());
+    let mut generated: Vec<GeneratedPaytable> = Vec::new();
+    if uncatalogued {
+        for (paytable, mismatches) in &scored {
+            if generated.len() >= MAX_GENERATED {
+                break;
+            }
+            // Only substitute pays for hands the base game already has
+            if mismatches.iter().any(|m| m.catalog.is_none()) {
+                continue;
+            }
+            let mut custom = paytable.clone();
+            let mut overrides = Vec::new();
+            for &(hand_type, field, pay) in &observed {
+                if same_pay(catalog_pay(paytable, hand_type), pay) {
+                    continue;
+                }
+                if custom.set_payout_field(field, pay).is_err() {
+                    break;
+                }
+                overrides.push(FieldOverride { field: field.to_string(), value: pay });
+            }
+            let reproduces = observed.iter().all(|&(t, _, pay)| same_pay(catalog_pay(&custom, t), pay));
+            if reproduces && custom.validate().is_ok() {
+                generated.push(GeneratedPaytable { base_paytable_id: paytable.id.clone(), overrides });
+            }
+        }
+    }
+
+    let limit = request.limit.unwrap_or(DEFAULT_LIMIT);
+    let mut canonical_cache = HashMap::new();
+    let candidates = scored.into_iter()
+        .take(limit)
+        .map(|(paytable, mismatches)| Candidate {
+            return_pct: strategy_file_return(&paytable, strategies_dir, &mut canonical_cache),
+            paytable_id: paytable.id,
+            name: paytable.name,
+            game_family: format!("{:?}", paytable.game_family),
+            matched: observed.len() - mismatches.len(),
+            consistent: mismatches.is_empty(),
+            mismatches,
+        })
+        .collect();
+
+    Ok(IdentifyResponse { observations: resolved, candidates, uncatalogued, generated })
+}
+
+/// Reads a request from `input` ("-" = stdin) and writes the response JSON to `output`
+/// (None = stdout)
+pub fn run_identify(input: &str, strategies_dir: &str, output: Option<&str>) {
+    let text = if input == "-" {
+        let mut text = String::new();
+        if let Err(e) = io::Read::read_to_string(&mut io::stdin(), &mut text) {
+            eprintln!("Failed to read request: {}", e);
+            std::process::exit(1);
+        }
+        text
+    } else {
+        match fs::read_to_string(input) {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("Failed to read {}: {}", input, e);
+                std::process::exit(1);
+            }
+        }
+    };
+    let request: IdentifyRequest = match serde_json::from_str(&text) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Invalid request: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let response = match identify(&request, strategies_dir) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    let json = match serde_json::to_string_pretty(&response) {
+        Ok(j) => j,
+        Err(e) => {
+            eprintln!("Failed to serialize response: {}", e);
+            std::process::exit(1);
+        }
+    };
+    match output {
+        Some(path) => {
+            if let Err(e) = fs::write(path, json) {
+                eprintln!("Failed to write {}: {}", path, e);
+                std::process::exit(1);
+            }
+        }
+        None => println!("{}", json),
+    }
+}
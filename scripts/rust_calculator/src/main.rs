@@ -12,27 +12,50 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
+mod crossplay;
+mod doubleup;
+mod handtable;
+mod identify;
+mod overlay;
+mod pickem;
+mod progressive;
+mod sensitivity;
+mod sequential;
+mod shockwave;
+mod simulate;
+mod strategydiff;
+mod www;
+
 // Card representation: 0-51 (rank * 4 + suit)
 // Ranks: 0=2, 1=3, 2=4, 3=5, 4=6, 5=7, 6=8, 7=9, 8=T, 9=J, 10=Q, 11=K, 12=A
 // Suits: 0=hearts, 1=diamonds, 2=clubs, 3=spades
-// For Joker Poker: Card 52 = Joker
+// For Joker Poker: Card 52 = Joker (52-63 are all jokers)
+// Custom decks: 64 + copy * 13 + rank = extra card of that rank in its own suit (4 + copy)
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+const EXTRA_CARD_BASE: u8 = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Card(u8);
 
 impl Card {
+    fn extra(rank: u8, copy: u8) -> Card {
+        Card(EXTRA_CARD_BASE + copy * 13 + rank)
+    }
+
     fn rank(&self) -> u8 {
-        if self.0 >= 52 { return 255; } // All jokers have no rank
+        if self.is_joker() { return 255; } // All jokers have no rank
+        if self.0 >= EXTRA_CARD_BASE { return (self.0 - EXTRA_CARD_BASE) % 13; }
         self.0 / 4
     }
 
     fn suit(&self) -> u8 {
-        if self.0 >= 52 { return 255; } // All jokers have no suit
+        if self.is_joker() { return 255; } // All jokers have no suit
+        if self.0 >= EXTRA_CARD_BASE { return 4 + (self.0 - EXTRA_CARD_BASE) / 13; }
         self.0 % 4
     }
 
     fn is_joker(&self) -> bool {
-        self.0 >= 52
+        (52..EXTRA_CARD_BASE).contains(&self.0)
     }
 
     fn rank_char(&self) -> char {
@@ -91,9 +114,129 @@ struct Paytable {
     five_jqk: Option<f64>,        // Five Js,Qs,Ks (SDB, SDDB)
     five_5_10: Option<f64>,       // Five 5s thru 10s (when five_jqk splits the range)
     five_deuces: Option<f64>,     // Five Deuces (4 natural + joker, WWW deuces games)
+    // Ordered outcomes
+    sequential_royal: Option<f64>, // Natural royal in T-J-Q-K-A order, left to right
     // Joker Poker specific (uses wild_royal, five_of_a_kind from above)
     // Minimum winning hand
     min_pair_rank: u8,        // 9=Jacks, 8=Tens, 11=Kings, 0=Two Pair minimum
+    // Custom deck composition and wild cards (None = the game family's usual deck)
+    deck: Option<DeckSpec>,
+    // How the joker substitutes in joker poker games
+    joker_mode: JokerMode,
+    // Quick Quads side rule (None = not offered)
+    quick_quads: Option<QuickQuads>,
+    // Wild Wild Wild feature (None = not a WWW paytable)
+    www: Option<WwwConfig>,
+}
+
+/// Most wild cards the Wild Wild Wild feature adds to the deck
+const MAX_WWW_WILDS: u8 = 3;
+
+/// Wild Wild Wild: a base paytable played with wild jokers added to the deck and the
+/// feature's max-bet pays applied over the base pays. Built by Paytable::with_www.
+#[derive(Clone, Debug, PartialEq)]
+struct WwwConfig {
+    /// Id of the paytable this one was derived from
+    base_id: String,
+    /// Wild jokers added to the deck, 0 to MAX_WWW_WILDS
+    wilds: u8,
+    /// Max-bet feature pays by payout field name (see www_override_table)
+    overrides: Vec<(String, f64)>,
+}
+
+/// Quick Quads: three of a kind plus two other cards adding up to its rank
+/// (A=1, J=11, Q=12, K=13) pays as four of that rank, e.g. 7-7-7-3-4 or 4-4-4-2-2.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct QuickQuads {
+    /// Wager as a multiple of the base game's max bet (6 coins instead of 5 = 1.2)
+    bet_cost: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum JokerMode {
+    /// Joker substitutes for any card
+    Wild,
+    /// "Bug": joker counts as an ace, or as any card that completes a straight or flush
+    Bug,
+}
+
+/// Deck composition and wild cards for games outside the standard families:
+/// sevens wild, one-eyed jacks, Five Aces (a fifth ace), stripped decks, added jokers.
+/// Paytables with a DeckSpec are scored by get_custom_deck_hand_type.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct DeckSpec {
+    /// Jokers added to the deck; always wild
+    jokers: u8,
+    /// Extra cards of these ranks, each in its own suit (Five Aces: [12])
+    extra_ranks: Vec<u8>,
+    /// Ranks removed from all four suits
+    stripped_ranks: Vec<u8>,
+    /// Ranks wild in every suit (Sevens Wild: [5])
+    wild_ranks: Vec<u8>,
+    /// Individual wild cards (One-Eyed Jacks: J of hearts and J of spades)
+    wild_cards: Vec<Card>,
+}
+
+impl DeckSpec {
+    fn validate(&self) -> Result<(), String> {
+        if self.jokers as usize > (EXTRA_CARD_BASE - 52) as usize {
+            return Err(format!("At most {} jokers are supported", EXTRA_CARD_BASE - 52));
+        }
+        if let Some(&r) = self.extra_ranks.iter().chain(&self.stripped_ranks).chain(&self.wild_ranks).find(|&&r| r > 12) {
+            return Err(format!("Invalid rank {}", r));
+        }
+        let mut copies = [0u8; 13];
+        for &r in &self.extra_ranks {
+            copies[r as usize] += 1;
+        }
+        if copies.iter().any(|&c| c > 3) {
+            return Err("At most 3 extra cards per rank are supported".to_string());
+        }
+        if self.extra_ranks.iter().any(|r| self.stripped_ranks.contains(r)) {
+            return Err("A rank cannot be both stripped and added".to_string());
+        }
+        if let Some(c) = self.wild_cards.iter().find(|c| c.0 >= 52 || self.stripped_ranks.contains(&c.rank())) {
+            return Err(format!("Wild card {:?} is not a standard card in this deck", c));
+        }
+        let size = self.cards().len();
+        if !(10..=100).contains(&size) {
+            return Err(format!("Deck has {} cards", size));
+        }
+        Ok(())
+    }
+
+    /// Every card in the deck, in card index order
+    fn cards(&self) -> Vec<Card> {
+        let mut cards: Vec<Card> = (0..52u8)
+            .map(Card)
+            .filter(|c| !self.stripped_ranks.contains(&c.rank()))
+            .collect();
+        cards.extend((0..self.jokers).map(|j| Card(52 + j)));
+        let mut copies = [0u8; 13];
+        for &r in &self.extra_ranks {
+            cards.push(Card::extra(r, copies[r as usize]));
+            copies[r as usize] += 1;
+        }
+        cards.sort();
+        cards
+    }
+
+    fn is_wild(&self, card: Card) -> bool {
+        card.is_joker()
+            || (card.rank() < 13 && self.wild_ranks.contains(&card.rank()))
+            || self.wild_cards.contains(&card)
+    }
+}
+
+/// Named decks a catalog paytable can be dealt from, as (id suffix, label, deck)
+fn deck_presets() -> Vec<(&'static str, &'static str, DeckSpec)> {
+    vec![
+        ("sevens-wild", "Sevens Wild", DeckSpec { wild_ranks: vec![5], ..Default::default() }),
+        ("one-eyed-jacks", "One-Eyed Jacks Wild",
+            DeckSpec { wild_cards: vec![Card(9 * 4), Card(9 * 4 + 3)], ..Default::default() }),
+        ("five-aces", "Five Aces", DeckSpec { extra_ranks: vec![12], ..Default::default() }),
+        ("stripped-2-5", "2s-5s Removed", DeckSpec { stripped_ranks: vec![0, 1, 2, 3], ..Default::default() }),
+    ]
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -140,6 +283,8 @@ enum GameFamily {
     DoubleDoubleBonusPlus,    // DDB+
     DeucesWild44,             // DW44 (different payouts)
     DeucesJokerWild,          // DJW (deuces + joker wild)
+    // Pick'em Poker (keep two, choose a stack; see pickem.rs)
+    PickEm,
 }
 
 impl Paytable {
@@ -167,173 +312,483 @@ impl Paytable {
     }
 
     fn is_www(&self) -> bool {
-        self.id.starts_with("www-")
+        self.www.is_some()
     }
 
     fn num_jokers(&self) -> u8 {
-        if self.id.starts_with("www-") {
-            // Extract wild count from ID: "www-{base}-{N}w"
-            if let Some(suffix) = self.id.rsplit('-').next() {
-                if let Some(n) = suffix.strip_suffix('w').and_then(|s| s.parse::<u8>().ok()) {
-                    return n;
-                }
-            }
-            0
+        if let Some(deck) = &self.deck {
+            deck.jokers
+        } else if let Some(www) = &self.www {
+            www.wilds
         } else if self.is_joker_poker() {
             1
         } else {
             0
         }
     }
+
+    /// Check the paytable is one the engine can evaluate
+    fn validate(&self) -> Result<(), String> {
+        if let Some(deck) = &self.deck {
+            if self.is_www() {
+                return Err(format!("{}: WWW paytables cannot use a custom deck", self.id));
+            }
+            deck.validate().map_err(|e| format!("{}: {}", self.id, e))?;
+        }
+        if self.joker_mode == JokerMode::Bug
+            && (!self.is_joker_poker() || self.is_deuces_wild() || self.deck.is_some())
+        {
+            return Err(format!("{}: bug joker is only supported on joker poker paytables", self.id));
+        }
+        if self.quick_quads.is_some()
+            && (self.is_deuces_wild() || self.is_joker_poker() || self.is_www() || self.deck.is_some())
+        {
+            return Err(format!("{}: Quick Quads is only supported on standard deck paytables", self.id));
+        }
+        // The positional solver models natural royals, wild jokers and the base wager only
+        if self.sequential_royal.is_some()
+            && (self.is_www() || self.deck.is_some() || self.joker_mode == JokerMode::Bug || self.quick_quads.is_some())
+        {
+            return Err(format!("{}: sequential royal is not supported on WWW, custom deck, bug joker or Quick Quads paytables", self.id));
+        }
+        if let Some(www) = &self.www {
+            if www.wilds > MAX_WWW_WILDS {
+                return Err(format!("{}: WWW adds at most {} wilds, got {}", self.id, MAX_WWW_WILDS, www.wilds));
+            }
+            if self.is_joker_poker() || self.game_family == GameFamily::PickEm {
+                return Err(format!("{}: WWW needs a 52-card draw game as its base", self.id));
+            }
+            let fields = self.payout_fields();
+            if let Some((name, _)) = www.overrides.iter().find(|(name, _)| !fields.iter().any(|(f, _)| f == name)) {
+                return Err(format!("{}: unknown WWW override field '{}'", self.id, name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Every card dealt in this game
+    fn deck_cards(&self) -> Vec<Card> {
+        match &self.deck {
+            Some(deck) => deck.cards(),
+            None => (0..52 + self.num_jokers()).map(Card).collect(),
+        }
+    }
+
+    /// Every payout field by name, with its value (None if the field is not on this paytable).
+    /// Names match the struct fields so they can be passed on the command line.
+    fn payout_fields(&self) -> Vec<(&'static str, Option<f64>)> {
+        vec![
+            ("royal_flush", Some(self.royal_flush)),
+            ("straight_flush", Some(self.straight_flush)),
+            ("four_of_a_kind", Some(self.four_of_a_kind)),
+            ("full_house", Some(self.full_house)),
+            ("flush", Some(self.flush)),
+            ("straight", Some(self.straight)),
+            ("three_of_a_kind", Some(self.three_of_a_kind)),
+            ("two_pair", Some(self.two_pair)),
+            ("high_pair", Some(self.high_pair)),
+            ("four_aces", self.four_aces),
+            ("four_2_4", self.four_2_4),
+            ("four_5_k", self.four_5_k),
+            ("four_jqk", self.four_jqk),
+            ("four_8s", self.four_8s),
+            ("four_7s", self.four_7s),
+            ("four_aces_with_kicker", self.four_aces_with_kicker),
+            ("four_2_4_with_kicker", self.four_2_4_with_kicker),
+            ("four_aces_with_face", self.four_aces_with_face),
+            ("four_jqk_with_face", self.four_jqk_with_face),
+            ("four_deuces", self.four_deuces),
+            ("wild_royal", self.wild_royal),
+            ("five_of_a_kind", self.five_of_a_kind),
+            ("five_aces", self.five_aces),
+            ("five_2_4", self.five_2_4),
+            ("five_5_k", self.five_5_k),
+            ("five_jqk", self.five_jqk),
+            ("five_5_10", self.five_5_10),
+            ("five_deuces", self.five_deuces),
+            ("sequential_royal", self.sequential_royal),
+        ]
+    }
+
+    fn payout_field(&self, name: &str) -> Option<f64> {
+        self.payout_fields().into_iter()
+            .find(|(n, _)| *n == name)
+            .and_then(|(_, v)| v)
+    }
+
+    /// Pay per coin for a hand type. Evaluators only return a bonus tier when its field
+    /// is set; an unset tier pays as the hand it refines. Unset wild-card bonuses pay the
+    /// classic Deuces Wild (four deuces 200, wild royal 25, five of a kind 15) or Joker
+    /// Poker (wild royal 50, five of a kind 100) amounts.
+    fn payout(&self, hand_type: HandType) -> f64 {
+        let five_of_a_kind = self.five_of_a_kind.unwrap_or(if self.is_deuces_wild() { 15.0 } else { 100.0 });
+        let four_deuces = self.four_deuces.unwrap_or(200.0);
+        let quad = |tier: Option<f64>| tier.unwrap_or(self.four_of_a_kind);
+        match hand_type {
+            HandType::Nothing => 0.0,
+            HandType::JacksOrBetter | HandType::TensOrBetter | HandType::KingsOrBetter => self.high_pair,
+            HandType::TwoPair => self.two_pair,
+            HandType::ThreeOfAKind => self.three_of_a_kind,
+            HandType::Straight => self.straight,
+            HandType::Flush => self.flush,
+            HandType::FullHouse => self.full_house,
+            HandType::FourOfAKind => self.four_of_a_kind,
+            HandType::Four5ToK => quad(self.four_5_k),
+            HandType::Four2To4 => quad(self.four_2_4),
+            HandType::FourAces => quad(self.four_aces),
+            HandType::FourJToK => quad(self.four_jqk),
+            HandType::Four8s => quad(self.four_8s),
+            HandType::Four7s => quad(self.four_7s),
+            HandType::FourAcesWith2To4 => quad(self.four_aces_with_kicker.or(self.four_aces)),
+            HandType::Four2To4WithAceTo4 => quad(self.four_2_4_with_kicker.or(self.four_2_4)),
+            HandType::FourAcesWithFace => quad(self.four_aces_with_face.or(self.four_aces)),
+            HandType::FourJToKWithFace => quad(self.four_jqk_with_face.or(self.four_jqk)),
+            HandType::StraightFlush => self.straight_flush,
+            HandType::FiveOfAKind => five_of_a_kind,
+            HandType::FiveAces => self.five_aces.unwrap_or(five_of_a_kind),
+            HandType::Five2To4 | HandType::Five3To5 => self.five_2_4.unwrap_or(five_of_a_kind),
+            HandType::Five5ToK | HandType::Five6ToK => self.five_5_k.unwrap_or(five_of_a_kind),
+            HandType::FiveJToK => self.five_jqk.unwrap_or(five_of_a_kind),
+            HandType::Five5To10 => self.five_5_10.unwrap_or(five_of_a_kind),
+            HandType::WildRoyalFlush => self.wild_royal.unwrap_or(if self.is_deuces_wild() { 25.0 } else { 50.0 }),
+            HandType::FourDeuces | HandType::FourWilds => four_deuces,
+            HandType::FiveDeuces | HandType::FiveWilds => self.five_deuces.unwrap_or(four_deuces),
+            HandType::RoyalFlush | HandType::NaturalRoyalFlush => self.royal_flush,
+        }
+    }
+
+    /// Per-coin payouts when playing fewer than max coins. The royal is the only
+    /// coin-dependent line in the catalog: its max-coin bonus (800, 940, …) drops to
+    /// 250 per coin. The result gets its own id ("{id}-{N}coin") so its strategy
+    /// files and returns are stored separately from the max-coin paytable.
+    fn short_coin(&self, coins: u8) -> Result<Paytable, String> {
+        if !(1..=5).contains(&coins) {
+            return Err(format!("Coins must be between 1 and 5, got {}", coins));
+        }
+        if coins == 5 {
+            return Ok(self.clone());
+        }
+        if self.is_www() {
+            return Err(format!("'{}' is a WWW paytable, which is only played at max coins", self.id));
+        }
+
+        let mut pt = self.clone();
+        pt.id = format!("{}-{}coin", self.id, coins);
+        pt.name = format!("{} ({} coin{})", self.name, coins, if coins == 1 { "" } else { "s" });
+        pt.royal_flush = 250.0;
+        Ok(pt)
+    }
+
+    /// The same paytable played with a bug joker: the joker counts as an ace, or as any card
+    /// that completes a straight, flush, straight flush or royal. Its id is "{id}-bug".
+    fn bug_joker(&self) -> Result<Paytable, String> {
+        if !self.is_joker_poker() || self.is_deuces_wild() || self.deck.is_some() {
+            return Err(format!("'{}' is not a joker poker paytable", self.id));
+        }
+        let mut pt = self.clone();
+        pt.id = format!("{}-bug", self.id);
+        pt.name = format!("{} (Bug Joker)", self.name);
+        pt.joker_mode = JokerMode::Bug;
+        pt.validate()?;
+        Ok(pt)
+    }
+
+    /// The same paytable dealt from one of the DECK_PRESETS. Its id is "{id}-{preset}"
+    /// (`jacks-or-better-9-6-one-eyed-jacks`). The deck's wild cards replace the game's own,
+    /// so a Deuces Wild paytable with sevens wild plays deuces as natural cards.
+    fn with_deck(&self, preset: &str) -> Result<Paytable, String> {
+        let Some((_, label, deck)) = deck_presets().into_iter().find(|(name, _, _)| *name == preset) else {
+            return Err(format!("Unknown deck '{}'", preset));
+        };
+        if self.deck.is_some() || self.is_www() || self.is_joker_poker() || self.game_family == GameFamily::PickEm {
+            return Err(format!("'{}' cannot be dealt from a custom deck", self.id));
+        }
+        let mut pt = self.clone();
+        pt.id = format!("{}-{}", self.id, preset);
+        pt.name = format!("{} ({})", self.name, label);
+        pt.deck = Some(deck);
+        pt.validate()?;
+        Ok(pt)
+    }
+
+    /// The same paytable with a bonus for a sequential royal (T-J-Q-K-A, left to right),
+    /// paying `pay` per coin. Its id is "{id}-seq{pay}".
+    fn with_sequential_royal(&self, pay: u32) -> Result<Paytable, String> {
+        if self.is_www() || self.deck.is_some() || self.sequential_royal.is_some() {
+            return Err(format!("'{}' does not support a sequential royal", self.id));
+        }
+        let mut pt = self.clone();
+        pt.id = format!("{}-seq{}", self.id, pay);
+        pt.name = format!("{} (Sequential Royal {})", self.name, pay);
+        pt.sequential_royal = Some(pay as f64);
+        pt.validate()?;
+        Ok(pt)
+    }
+
+    /// The same paytable with the Quick Quads side rule. Its id is "{id}-qq".
+    fn with_quick_quads(&self) -> Result<Paytable, String> {
+        if self.is_deuces_wild() || self.is_joker_poker() || self.is_www() || self.deck.is_some() {
+            return Err(format!("'{}' is a wild card game; Quick Quads needs a standard deck", self.id));
+        }
+        let mut pt = self.clone();
+        pt.id = format!("{}-qq", self.id);
+        pt.name = format!("{} (Quick Quads)", self.name);
+        pt.quick_quads = Some(QuickQuads { bet_cost: 1.2 });
+        pt.validate()?;
+        Ok(pt)
+    }
+
+    /// This paytable as the base of a Wild Wild Wild game with `wilds` wild jokers added.
+    /// Its id is "www-{id}-{wilds}w". The max-bet feature pays come from `overrides`
+    /// (www_override_table for catalog bases); a wild royal pays as a natural royal and
+    /// five of a kind as the best quad unless the overrides say otherwise.
+    fn with_www(&self, wilds: u8, overrides: &[(&str, f64)]) -> Result<Paytable, String> {
+        if self.is_www() || self.deck.is_some() || self.sequential_royal.is_some() || self.quick_quads.is_some() {
+            return Err(format!("'{}' cannot be used as a WWW base paytable", self.id));
+        }
+        let mut pt = self.clone();
+        pt.id = format!("www-{}-{}w", self.id, wilds);
+        pt.name = format!("WWW {}", self.name);
+        pt.www = Some(WwwConfig {
+            base_id: self.id.clone(),
+            wilds,
+            overrides: overrides.iter().map(|&(name, pay)| (name.to_string(), pay)).collect(),
+        });
+        pt.validate()?;
+
+        if pt.wild_royal.is_none() {
+            pt.wild_royal = Some(pt.royal_flush);
+        }
+        for &(name, pay) in overrides {
+            pt.put_payout_field(name, pay)?;
+        }
+        if pt.five_of_a_kind.is_none() {
+            pt.five_of_a_kind = Some(pt.four_aces_with_kicker.or(pt.four_aces).unwrap_or(pt.four_of_a_kind));
+        }
+        Ok(pt)
+    }
+
+    /// Wager per hand as a multiple of the base game's max bet
+    fn bet_cost(&self) -> f64 {
+        self.quick_quads.map_or(1.0, |qq| qq.bet_cost)
+    }
+
+    /// Overwrite a payout field by name. Only fields already present on the paytable can be
+    /// set — turning on an absent bonus tier would change which hands it applies to.
+    fn set_payout_field(&mut self, name: &str, value: f64) -> Result<(), String> {
+        if self.payout_field(name).is_none() {
+            return Err(format!("Paytable '{}' has no '{}' payout", self.id, name));
+        }
+        self.put_payout_field(name, value)
+    }
+
+    /// Set a payout field by name, turning it on if the paytable doesn't have it
+    fn put_payout_field(&mut self, name: &str, value: f64) -> Result<(), String> {
+        match name {
+            "royal_flush" => self.royal_flush = value,
+            "straight_flush" => self.straight_flush = value,
+            "four_of_a_kind" => self.four_of_a_kind = value,
+            "full_house" => self.full_house = value,
+            "flush" => self.flush = value,
+            "straight" => self.straight = value,
+            "three_of_a_kind" => self.three_of_a_kind = value,
+            "two_pair" => self.two_pair = value,
+            "high_pair" => self.high_pair = value,
+            "four_aces" => self.four_aces = Some(value),
+            "four_2_4" => self.four_2_4 = Some(value),
+            "four_5_k" => self.four_5_k = Some(value),
+            "four_jqk" => self.four_jqk = Some(value),
+            "four_8s" => self.four_8s = Some(value),
+            "four_7s" => self.four_7s = Some(value),
+            "four_aces_with_kicker" => self.four_aces_with_kicker = Some(value),
+            "four_2_4_with_kicker" => self.four_2_4_with_kicker = Some(value),
+            "four_aces_with_face" => self.four_aces_with_face = Some(value),
+            "four_jqk_with_face" => self.four_jqk_with_face = Some(value),
+            "four_deuces" => self.four_deuces = Some(value),
+            "wild_royal" => self.wild_royal = Some(value),
+            "five_of_a_kind" => self.five_of_a_kind = Some(value),
+            "five_aces" => self.five_aces = Some(value),
+            "five_2_4" => self.five_2_4 = Some(value),
+            "five_5_k" => self.five_5_k = Some(value),
+            "five_jqk" => self.five_jqk = Some(value),
+            "five_5_10" => self.five_5_10 = Some(value),
+            "five_deuces" => self.five_deuces = Some(value),
+            "sequential_royal" => self.sequential_royal = Some(value),
+            _ => return Err(format!("Unknown payout field '{}'", name)),
+        }
+        Ok(())
+    }
 }
 
 // ============================================================================
 // PAYTABLE DEFINITIONS
 // ============================================================================
 
-/// Apply WWW-specific pay table overrides for the max-bet feature.
-/// Values are per-coin max-bet payouts from docs/www-pay-tables.md.
-fn apply_www_overrides(base_id: &str, pt: &mut Paytable) {
-    // Always set wild royal to natural royal payout
-    if pt.wild_royal.is_none() {
-        pt.wild_royal = Some(pt.royal_flush);
+/// Chance the WWW feature adds 0, 1, 2 or 3 wild cards, for the base paytables whose
+/// WWW distribution is known (docs/www-pay-tables.md, from Wizard of Odds).
+fn www_wild_distribution(base_id: &str) -> Option<[f64; 4]> {
+    match base_id {
+        "jacks-or-better-9-6" => Some([0.40, 0.19, 0.21, 0.20]),
+        "jacks-or-better-9-5" => Some([0.40, 0.195, 0.205, 0.20]),
+        "jacks-or-better-8-5" => Some([0.40, 0.205, 0.195, 0.20]),
+        "jacks-or-better-7-5" => Some([0.40, 0.195, 0.205, 0.20]),
+        "jacks-or-better-6-5" => Some([0.40, 0.20, 0.20, 0.20]),
+        "bonus-poker-8-5" => Some([0.3568, 0.1005, 0.4422, 0.1005]),
+        "bonus-poker-7-5" => Some([0.355, 0.115, 0.43, 0.10]),
+        "bonus-poker-6-5" => Some([0.37, 0.10, 0.43, 0.10]),
+        "bonus-poker-deluxe-9-6" => Some([0.49, 0.208, 0.252, 0.05]),
+        "bonus-poker-deluxe-9-5" => Some([0.49, 0.215, 0.245, 0.05]),
+        "bonus-poker-deluxe-8-5" => Some([0.49, 0.22, 0.24, 0.05]),
+        "bonus-poker-deluxe-7-5" => Some([0.49, 0.24, 0.22, 0.05]),
+        "bonus-poker-deluxe-6-5" => Some([0.49, 0.245, 0.215, 0.05]),
+        "double-bonus-9-7-5" => Some([0.49, 0.15, 0.31, 0.05]),
+        "double-bonus-9-6-5" => Some([0.49, 0.155, 0.305, 0.05]),
+        "double-bonus-9-6-4" => Some([0.49, 0.165, 0.295, 0.05]),
+        "double-double-bonus-9-6" => Some([0.49, 0.22, 0.24, 0.05]),
+        "double-double-bonus-9-5" => Some([0.49, 0.23, 0.23, 0.05]),
+        "double-double-bonus-8-5" => Some([0.49, 0.23, 0.23, 0.05]),
+        "double-double-bonus-7-5" => Some([0.49, 0.24, 0.22, 0.05]),
+        "double-double-bonus-6-5" => Some([0.49, 0.24, 0.22, 0.05]),
+        "triple-double-bonus-9-7" => Some([0.49, 0.329, 0.141, 0.04]),
+        "triple-double-bonus-9-6" => Some([0.49, 0.338, 0.132, 0.04]),
+        "triple-double-bonus-8-5" => Some([0.49, 0.347, 0.123, 0.04]),
+        "deuces-wild-nsud" | "deuces-wild-illinois" => Some([0.40, 0.155, 0.245, 0.20]),
+        "deuces-wild-bonus-9-4" => Some([0.40, 0.19, 0.21, 0.20]),
+        "deuces-wild-20-12-9" => Some([0.40, 0.18, 0.22, 0.20]),
+        _ => None,
     }
+}
 
+/// WWW max-bet feature pays for a base paytable, as (payout field, per-coin pay).
+/// Values are per-coin max-bet payouts from docs/www-pay-tables.md. Bases without a
+/// table get only the defaults from Paytable::with_www.
+fn www_override_table(base_id: &str) -> &'static [(&'static str, f64)] {
     match base_id {
         // === JACKS OR BETTER 9-6 ===
         // Boosted: 4oK 25→30, FH 9→11, ST 4→5
-        "jacks-or-better-9-6" => {
-            pt.four_of_a_kind = 30.0;
-            pt.full_house = 11.0;
-            pt.straight = 5.0;
-            pt.five_aces = Some(800.0);
-            pt.five_2_4 = Some(320.0);
-            pt.five_5_k = Some(100.0);
-            pt.five_of_a_kind = Some(100.0); // fallback
-        },
+        "jacks-or-better-9-6" => &[
+            ("four_of_a_kind", 30.0),
+            ("full_house", 11.0),
+            ("straight", 5.0),
+            ("five_aces", 800.0),
+            ("five_2_4", 320.0),
+            ("five_5_k", 100.0),
+            ("five_of_a_kind", 100.0), // fallback
+        ],
 
         // === BONUS POKER 8-5 ===
         // Boosted: four_5_k 25→29, FH 8→9, FL 5→6
-        "bonus-poker-8-5" => {
-            pt.four_5_k = Some(29.0);
-            pt.full_house = 9.0;
-            pt.flush = 6.0;
-            pt.five_aces = Some(800.0);
-            pt.five_2_4 = Some(320.0);
-            pt.five_5_k = Some(100.0);
-            pt.five_of_a_kind = Some(100.0);
-        },
+        "bonus-poker-8-5" => &[
+            ("four_5_k", 29.0),
+            ("full_house", 9.0),
+            ("flush", 6.0),
+            ("five_aces", 800.0),
+            ("five_2_4", 320.0),
+            ("five_5_k", 100.0),
+            ("five_of_a_kind", 100.0),
+        ],
 
         // === BONUS POKER DELUXE 9-6 ===
         // No boosts to standard hands
-        "bonus-poker-deluxe-9-6" => {
-            pt.five_aces = Some(800.0);
-            pt.five_2_4 = Some(320.0);
-            pt.five_5_k = Some(100.0);
-            pt.five_of_a_kind = Some(100.0);
-        },
+        "bonus-poker-deluxe-9-6" => &[
+            ("five_aces", 800.0),
+            ("five_2_4", 320.0),
+            ("five_5_k", 100.0),
+            ("five_of_a_kind", 100.0),
+        ],
 
         // === DOUBLE BONUS 9-7-5 ===
         // No boosts to standard hands
-        "double-bonus-9-7-5" => {
-            pt.five_aces = Some(800.0);
-            pt.five_2_4 = Some(320.0);
-            pt.five_5_k = Some(100.0);
-            pt.five_of_a_kind = Some(100.0);
-        },
+        "double-bonus-9-7-5" => &[
+            ("five_aces", 800.0),
+            ("five_2_4", 320.0),
+            ("five_5_k", 100.0),
+            ("five_of_a_kind", 100.0),
+        ],
 
         // === DOUBLE DOUBLE BONUS 9-6 ===
         // No boosts to standard hands
-        "double-double-bonus-9-6" => {
-            pt.five_aces = Some(800.0);
-            pt.five_2_4 = Some(320.0);
-            pt.five_5_k = Some(100.0);
-            pt.five_of_a_kind = Some(100.0);
-        },
+        "double-double-bonus-9-6" => &[
+            ("five_aces", 800.0),
+            ("five_2_4", 320.0),
+            ("five_5_k", 100.0),
+            ("five_of_a_kind", 100.0),
+        ],
 
         // === TRIPLE DOUBLE BONUS 9-7 ===
         // Boosted: 3oK 2→3
-        "triple-double-bonus-9-7" => {
-            pt.three_of_a_kind = 3.0;
-            pt.five_aces = Some(800.0);
-            pt.five_2_4 = Some(400.0); // TDB unique: higher than standard 320
-            pt.five_5_k = Some(100.0);
-            pt.five_of_a_kind = Some(100.0);
-        },
+        "triple-double-bonus-9-7" => &[
+            ("three_of_a_kind", 3.0),
+            ("five_aces", 800.0),
+            ("five_2_4", 400.0), // TDB unique: higher than standard 320
+            ("five_5_k", 100.0),
+            ("five_of_a_kind", 100.0),
+        ],
 
         // === DEUCES WILD (NSUD / Illinois) ===
         // Boosted: four_deuces 200→250, SF 10→11, 4oK 4→5
-        "deuces-wild-nsud" | "deuces-wild-illinois" => {
-            pt.four_deuces = Some(250.0);
-            pt.straight_flush = 11.0;
-            pt.four_of_a_kind = 5.0;
-            pt.five_deuces = Some(800.0);
+        "deuces-wild-nsud" | "deuces-wild-illinois" => &[
+            ("four_deuces", 250.0),
+            ("straight_flush", 11.0),
+            ("four_of_a_kind", 5.0),
+            ("five_deuces", 800.0),
             // 5oK stays at 16 (no boost)
-        },
+        ],
 
         // === DEUCES WILD BONUS 9/4/4/3 (Bonus Deuces Wild) ===
         // Boosted: four_deuces 400→500, SF 9→12
         // Tiered 5oK: 5 Aces=80, 5 3s-5s=40, 5 6s-Ks=20
-        "deuces-wild-bonus-9-4" => {
-            pt.four_deuces = Some(500.0);
-            pt.straight_flush = 12.0;
-            pt.five_deuces = Some(800.0);
-            pt.five_aces = Some(80.0);
-            pt.five_2_4 = Some(40.0);  // 3s,4s,5s in deuces context
-            pt.five_5_k = Some(20.0);  // 6s thru Ks
-            pt.five_of_a_kind = Some(20.0); // fallback
-        },
+        "deuces-wild-bonus-9-4" => &[
+            ("four_deuces", 500.0),
+            ("straight_flush", 12.0),
+            ("five_deuces", 800.0),
+            ("five_aces", 80.0),
+            ("five_2_4", 40.0),  // 3s,4s,5s in deuces context
+            ("five_5_k", 20.0),  // 6s thru Ks
+            ("five_of_a_kind", 20.0), // fallback
+        ],
 
         // === SUPER DOUBLE BONUS 9-5 ===
         // No boosts to standard hands; 4-tier 5oK
-        "super-double-bonus-9-5" => {
-            pt.five_aces = Some(400.0);
-            pt.five_jqk = Some(240.0);
-            pt.five_2_4 = Some(160.0);
-            pt.five_5_10 = Some(100.0);
-            pt.five_5_k = Some(100.0); // fallback
-            pt.five_of_a_kind = Some(100.0);
-        },
+        "super-double-bonus-9-5" => &[
+            ("five_aces", 400.0),
+            ("five_jqk", 240.0),
+            ("five_2_4", 160.0),
+            ("five_5_10", 100.0),
+            ("five_5_k", 100.0), // fallback
+            ("five_of_a_kind", 100.0),
+        ],
 
         // === SUPER DOUBLE DOUBLE BONUS 8-5 ===
         // No boosts to standard hands; 4-tier 5oK
-        "super-double-double-bonus-8-5" => {
-            pt.five_aces = Some(800.0);
-            pt.five_jqk = Some(640.0);
-            pt.five_2_4 = Some(320.0);
-            pt.five_5_10 = Some(100.0);
-            pt.five_5_k = Some(100.0);
-            pt.five_of_a_kind = Some(100.0);
-        },
+        "super-double-double-bonus-8-5" => &[
+            ("five_aces", 800.0),
+            ("five_jqk", 640.0),
+            ("five_2_4", 320.0),
+            ("five_5_10", 100.0),
+            ("five_5_k", 100.0),
+            ("five_of_a_kind", 100.0),
+        ],
 
         // === DOUBLE DOUBLE BONUS PLUS 9-6 ===
         // No boosts to standard hands
-        "ddb-plus-9-6" => {
-            pt.five_aces = Some(800.0);
-            pt.five_2_4 = Some(320.0);
-            pt.five_5_k = Some(100.0);
-            pt.five_of_a_kind = Some(100.0);
-        },
+        "ddb-plus-9-6" => &[
+            ("five_aces", 800.0),
+            ("five_2_4", 320.0),
+            ("five_5_k", 100.0),
+            ("five_of_a_kind", 100.0),
+        ],
 
         // === SUPER BONUS DEUCES WILD ===
         // No boosts to standard hands
-        "super-bonus-deuces-10" | "super-bonus-deuces-9" | "super-bonus-deuces-8" => {
-            pt.five_deuces = Some(800.0);
+        "super-bonus-deuces-10" | "super-bonus-deuces-9" | "super-bonus-deuces-8" => &[
+            ("five_deuces", 800.0),
             // five_of_a_kind stays at 160 (5oK with deuce)
             // Plain 5oK (no deuce) = 15, stored as five_5_k fallback
-            pt.five_5_k = Some(15.0);
-        },
+            ("five_5_k", 15.0),
+        ],
 
-        // Fallback: derive 5oK from best bonus quad payout (existing behavior)
-        _ => {
-            if pt.five_of_a_kind.is_none() {
-                pt.five_of_a_kind = Some(
-                    pt.four_aces_with_kicker
-                        .or(pt.four_aces)
-                        .unwrap_or(pt.four_of_a_kind)
-                );
-            }
-        },
+        _ => &[],
     }
 }
 
@@ -353,7 +808,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "jacks-or-better-9-5" => Some(Paytable {
             id: id.to_string(),
@@ -368,7 +828,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "jacks-or-better-8-6" => Some(Paytable {
             id: id.to_string(),
@@ -383,7 +848,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "jacks-or-better-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -398,7 +868,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "jacks-or-better-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -413,7 +888,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "jacks-or-better-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -428,7 +908,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // Additional JoB variants from comprehensive paytable list
@@ -445,7 +930,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "jacks-or-better-9-6-940" => Some(Paytable {
             id: id.to_string(),
@@ -460,7 +950,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "jacks-or-better-8-5-35" => Some(Paytable {
             id: id.to_string(),
@@ -475,7 +970,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== TENS OR BETTER ======
@@ -492,7 +992,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 8,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== BONUS POKER ======
@@ -509,7 +1014,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-poker-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -524,7 +1034,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-poker-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -539,7 +1054,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // Additional Bonus Poker variants
@@ -556,7 +1076,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== BONUS POKER DELUXE ======
@@ -573,7 +1098,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-poker-deluxe-8-6" => Some(Paytable {
             id: id.to_string(),
@@ -588,7 +1118,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-poker-deluxe-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -603,7 +1138,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-poker-deluxe-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -618,7 +1158,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-poker-deluxe-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -633,7 +1178,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== ACES AND FACES ======
@@ -650,7 +1200,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "aces-and-faces-7-6" => Some(Paytable {
             id: id.to_string(),
@@ -665,7 +1220,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "aces-and-faces-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -680,7 +1240,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "aces-and-faces-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -695,7 +1260,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== ACES AND EIGHTS ======
@@ -712,7 +1282,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "aces-and-eights-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -727,7 +1302,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== TRIPLE BONUS ======
@@ -744,7 +1324,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11, // Kings or Better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-bonus-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -759,7 +1344,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11, // Kings or Better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-bonus-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -774,7 +1364,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11, // Kings or Better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== TRIPLE BONUS PLUS ======
@@ -792,7 +1387,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9, // Jacks or Better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-bonus-plus-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -807,7 +1407,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9, // Jacks or Better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-bonus-plus-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -822,7 +1427,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9, // Jacks or Better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== SUPER ACES ======
@@ -839,7 +1449,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "super-aces-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -854,7 +1469,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "super-aces-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -869,7 +1489,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== BONUS POKER PLUS ======
@@ -886,7 +1511,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-poker-plus-9-6" => Some(Paytable {
             id: id.to_string(),
@@ -901,7 +1531,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE JACKPOT ======
@@ -919,7 +1554,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: Some(160.0), four_jqk_with_face: Some(80.0),
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-jackpot-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -934,7 +1574,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: Some(160.0), four_jqk_with_face: Some(80.0),
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE DOUBLE JACKPOT ======
@@ -951,7 +1596,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: Some(320.0), four_jqk_with_face: Some(160.0),
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-double-jackpot-9-6" => Some(Paytable {
             id: id.to_string(),
@@ -966,7 +1616,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: Some(320.0), four_jqk_with_face: Some(160.0),
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // Additional Bonus Deluxe variants
@@ -983,7 +1638,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-poker-deluxe-8-6-100" => Some(Paytable {
             id: id.to_string(),
@@ -998,7 +1658,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE BONUS ======
@@ -1015,7 +1680,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-bonus-10-7-100" => Some(Paytable {
             id: id.to_string(),
@@ -1030,7 +1700,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-bonus-10-7-80" => Some(Paytable {
             id: id.to_string(),
@@ -1045,7 +1720,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-bonus-10-6" => Some(Paytable {
             id: id.to_string(),
@@ -1060,7 +1740,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-bonus-10-7-4" => Some(Paytable {
             id: id.to_string(),
@@ -1075,7 +1760,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-bonus-9-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -1090,7 +1780,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-bonus-9-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -1105,7 +1800,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-bonus-9-6-4" => Some(Paytable {
             id: id.to_string(),
@@ -1120,7 +1820,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== SUPER DOUBLE BONUS ======
@@ -1137,7 +1842,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "super-double-bonus-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -1152,7 +1862,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "super-double-bonus-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -1167,7 +1882,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "super-double-bonus-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -1182,7 +1902,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== SUPER DOUBLE DOUBLE BONUS (SDDB) ======
@@ -1199,7 +1924,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: Some(320.0), four_jqk_with_face: Some(160.0),
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE DOUBLE BONUS ======
@@ -1216,7 +1946,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-double-bonus-10-6" => Some(Paytable {
             id: id.to_string(),
@@ -1231,7 +1966,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-double-bonus-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -1246,7 +1986,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-double-bonus-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -1261,7 +2006,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-double-bonus-9-6" => Some(Paytable {
             id: id.to_string(),
@@ -1276,7 +2026,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-double-bonus-9-5" => Some(Paytable {
             id: id.to_string(),
@@ -1291,7 +2046,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-double-bonus-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -1306,7 +2066,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== WHITE HOT ACES ======
@@ -1323,7 +2088,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "white-hot-aces-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -1338,7 +2108,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "white-hot-aces-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -1353,7 +2128,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "white-hot-aces-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -1368,7 +2148,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== TRIPLE DOUBLE BONUS ======
@@ -1385,7 +2170,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-double-bonus-9-6" => Some(Paytable {
             id: id.to_string(),
@@ -1400,7 +2190,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-double-bonus-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -1415,7 +2210,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== ALL AMERICAN ======
@@ -1432,7 +2232,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "all-american-30-8" => Some(Paytable {
             id: id.to_string(),
@@ -1447,7 +2252,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "all-american-25-8" => Some(Paytable {
             id: id.to_string(),
@@ -1462,7 +2272,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "all-american-40-7" => Some(Paytable {
             id: id.to_string(),
@@ -1477,7 +2292,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DEUCES WILD ======
@@ -1494,7 +2314,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(15.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0, // Three of a kind minimum
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-wild-nsud" => Some(Paytable {
             id: id.to_string(),
@@ -1509,7 +2334,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(16.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-wild-illinois" => Some(Paytable {
             id: id.to_string(),
@@ -1524,7 +2354,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(15.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-wild-20-12-9" => Some(Paytable {
             id: id.to_string(),
@@ -1539,7 +2374,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(20.0), five_of_a_kind: Some(12.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // Additional Deuces Wild variants
@@ -1556,7 +2396,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(15.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-wild-20-15-9" => Some(Paytable {
             id: id.to_string(),
@@ -1571,7 +2416,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(20.0), five_of_a_kind: Some(15.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-wild-25-12-9" => Some(Paytable {
             id: id.to_string(),
@@ -1586,7 +2436,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(12.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-wild-colorado" => Some(Paytable {
             id: id.to_string(),
@@ -1601,7 +2456,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(16.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DEUCES WILD BONUS (Bonus Deuces Wild) ======
@@ -1618,7 +2478,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(15.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== LOOSE DEUCES ======
@@ -1635,7 +2500,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(500.0), wild_royal: Some(25.0), five_of_a_kind: Some(17.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "loose-deuces-500-15" => Some(Paytable {
             id: id.to_string(),
@@ -1650,7 +2520,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(500.0), wild_royal: Some(25.0), five_of_a_kind: Some(15.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "loose-deuces-500-12" => Some(Paytable {
             id: id.to_string(),
@@ -1665,7 +2540,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(500.0), wild_royal: Some(25.0), five_of_a_kind: Some(12.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "loose-deuces-400-12" => Some(Paytable {
             id: id.to_string(),
@@ -1680,7 +2560,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(12.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE DEUCES WILD ======
@@ -1697,7 +2582,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(20.0), five_of_a_kind: Some(10.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-deuces-wild-16-13" => Some(Paytable {
             id: id.to_string(),
@@ -1712,7 +2602,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(16.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // Additional Double Deuces Wild variants
@@ -1729,7 +2624,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(20.0), five_of_a_kind: Some(10.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-deuces-wild-downtown" => Some(Paytable {
             id: id.to_string(),
@@ -1744,7 +2644,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(16.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-deuces-wild-16-11" => Some(Paytable {
             id: id.to_string(),
@@ -1759,7 +2664,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(16.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-deuces-wild-16-10" => Some(Paytable {
             id: id.to_string(),
@@ -1774,7 +2684,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(16.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== TRIPLE DEUCES WILD ======
@@ -1792,7 +2707,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(600.0), wild_royal: Some(20.0), five_of_a_kind: Some(9.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-deuces-wild-11-8" => Some(Paytable {
             id: id.to_string(),
@@ -1807,7 +2727,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(600.0), wild_royal: Some(20.0), five_of_a_kind: Some(11.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-deuces-wild-10-8" => Some(Paytable {
             id: id.to_string(),
@@ -1822,7 +2747,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(600.0), wild_royal: Some(20.0), five_of_a_kind: Some(10.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DELUXE DEUCES WILD ======
@@ -1840,7 +2770,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(50.0), five_of_a_kind: Some(25.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deluxe-deuces-wild-800" => Some(Paytable {
             id: id.to_string(),
@@ -1855,7 +2790,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(50.0), five_of_a_kind: Some(25.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== JOKER POKER (KINGS OR BETTER) ======
@@ -1872,7 +2812,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(200.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11, // Kings or better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-kings-98-60" => Some(Paytable {
             id: id.to_string(),
@@ -1887,7 +2832,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(50.0), five_of_a_kind: Some(100.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-kings-97-58" => Some(Paytable {
             id: id.to_string(),
@@ -1902,7 +2852,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(50.0), five_of_a_kind: Some(100.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== JOKER POKER (TWO PAIR OR BETTER) ======
@@ -1919,7 +2874,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(50.0), five_of_a_kind: Some(100.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0, // Two pair minimum
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-two-pair-98-59" => Some(Paytable {
             id: id.to_string(),
@@ -1934,7 +2894,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(800.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE JOKER ======
@@ -1952,7 +2917,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(25.0), five_of_a_kind: Some(100.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11, // Kings or Better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-joker-5-4" => Some(Paytable {
             id: id.to_string(),
@@ -1967,7 +2937,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(25.0), five_of_a_kind: Some(100.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11, // Kings or Better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== TRIPLE TRIPLE BONUS (TTB) ======
@@ -1985,7 +2960,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-triple-bonus-9-5" => Some(Paytable {
             id: id.to_string(),
@@ -2000,7 +2980,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-triple-bonus-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -2015,7 +3000,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "triple-triple-bonus-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -2030,7 +3020,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== ROYAL ACES BONUS (RAB) ======
@@ -2048,7 +3043,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 12, // Aces or better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "royal-aces-bonus-10-5" => Some(Paytable {
             id: id.to_string(),
@@ -2063,7 +3063,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 12,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "royal-aces-bonus-8-6" => Some(Paytable {
             id: id.to_string(),
@@ -2078,7 +3083,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 12,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "royal-aces-bonus-9-5" => Some(Paytable {
             id: id.to_string(),
@@ -2093,7 +3103,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 12,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== A-C-E-S BONUS (Ace$) ======
@@ -2111,7 +3126,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "aces-bonus-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -2126,7 +3146,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "aces-bonus-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -2141,7 +3166,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== BONUS ACES AND FACES (BPAF) ======
@@ -2158,7 +3188,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-aces-faces-7-5" => Some(Paytable {
             id: id.to_string(),
@@ -2173,7 +3208,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "bonus-aces-faces-6-5" => Some(Paytable {
             id: id.to_string(),
@@ -2188,7 +3228,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE DOUBLE BONUS ACES AND FACES (DDBAF) ======
@@ -2205,7 +3250,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: Some(160.0),
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "ddb-aces-faces-9-5" => Some(Paytable {
             id: id.to_string(),
@@ -2220,7 +3270,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: Some(160.0),
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE DOUBLE BONUS PLUS (DDB+) ======
@@ -2238,7 +3293,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "ddb-plus-9-5" => Some(Paytable {
             id: id.to_string(),
@@ -2253,7 +3313,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "ddb-plus-8-5" => Some(Paytable {
             id: id.to_string(),
@@ -2268,7 +3333,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: None, five_of_a_kind: None,
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 9,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DEUCES WILD 44 (DW44) ======
@@ -2286,7 +3356,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(15.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-wild-44-nsud" => Some(Paytable {
             id: id.to_string(),
@@ -2301,7 +3376,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(16.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-wild-44-illinois" => Some(Paytable {
             id: id.to_string(),
@@ -2316,7 +3396,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(200.0), wild_royal: Some(25.0), five_of_a_kind: Some(15.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DEUCES JOKER WILD (DJW) ======
@@ -2334,7 +3419,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(25.0), wild_royal: Some(12.0), five_of_a_kind: Some(9.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "deuces-joker-wild-10-8" => Some(Paytable {
             id: id.to_string(),
@@ -2349,7 +3439,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(25.0), wild_royal: Some(10.0), five_of_a_kind: Some(8.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== DOUBLE BONUS DEUCES WILD (DBDW) ======
@@ -2366,7 +3461,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(160.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-bonus-deuces-9" => Some(Paytable {
             id: id.to_string(),
@@ -2381,7 +3481,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(160.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== SUPER BONUS DEUCES WILD (SBDW) ======
@@ -2398,7 +3503,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(160.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "super-bonus-deuces-9" => Some(Paytable {
             id: id.to_string(),
@@ -2413,7 +3523,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(160.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "super-bonus-deuces-8" => Some(Paytable {
             id: id.to_string(),
@@ -2428,7 +3543,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: Some(400.0), wild_royal: Some(25.0), five_of_a_kind: Some(160.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== ADDITIONAL JOKER POKER KINGS VARIANTS ======
@@ -2445,7 +3565,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(200.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-kings-940-20" => Some(Paytable {
             id: id.to_string(),
@@ -2460,7 +3585,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(200.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-kings-20-6" => Some(Paytable {
             id: id.to_string(),
@@ -2475,7 +3605,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(200.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-kings-18-7" => Some(Paytable {
             id: id.to_string(),
@@ -2490,7 +3625,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(200.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-kings-17-7" => Some(Paytable {
             id: id.to_string(),
@@ -2505,7 +3645,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(200.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-kings-15-7" => Some(Paytable {
             id: id.to_string(),
@@ -2520,7 +3665,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(200.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 11,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== ADDITIONAL JOKER POKER TWO PAIR VARIANTS ======
@@ -2537,7 +3687,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(50.0), five_of_a_kind: Some(100.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-two-pair-20-8" => Some(Paytable {
             id: id.to_string(),
@@ -2552,7 +3707,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(50.0), five_of_a_kind: Some(100.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "joker-poker-two-pair-20-9" => Some(Paytable {
             id: id.to_string(),
@@ -2567,7 +3727,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(50.0), five_of_a_kind: Some(100.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // ====== ADDITIONAL DOUBLE JOKER VARIANTS ======
@@ -2584,7 +3749,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(50.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-joker-9-6-800" => Some(Paytable {
             id: id.to_string(),
@@ -2599,7 +3769,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(50.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-joker-9-5-4" => Some(Paytable {
             id: id.to_string(),
@@ -2614,7 +3789,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(50.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-joker-8-6-4" => Some(Paytable {
             id: id.to_string(),
@@ -2629,7 +3809,12 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(50.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
         "double-joker-8-5-4" => Some(Paytable {
             id: id.to_string(),
@@ -2644,35 +3829,102 @@ fn get_paytable(id: &str) -> Option<Paytable> {
             four_aces_with_face: None, four_jqk_with_face: None,
             four_deuces: None, wild_royal: Some(100.0), five_of_a_kind: Some(50.0),
             five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
             min_pair_rank: 0,
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
         }),
 
         // WWW (Wild Wild Wild) variants — auto-derived from base paytable
-        // ID format: "www-{base_paytable_id}-{N}w" where N = 0, 1, 2, or 3
+        // ID format: "www-{base_paytable_id}-{N}w" where N = 0 to MAX_WWW_WILDS
         id if id.starts_with("www-") => {
-            // Strip "www-" prefix and "-Nw" suffix to get base ID
-            let without_prefix = id.trim_start_matches("www-");
-            let base_id = if let Some(pos) = without_prefix.rfind('-') {
-                &without_prefix[..pos]
-            } else {
-                without_prefix
+            let (base_id, suffix) = id.trim_start_matches("www-").rsplit_once('-')?;
+            let wilds = match suffix.strip_suffix('w').and_then(|n| n.parse::<u8>().ok()) {
+                Some(n) => n,
+                None => {
+                    eprintln!("Warning: WWW id '{}' does not end in a wild count (\"-{{N}}w\")", id);
+                    return None;
+                }
+            };
+            let base = match get_paytable(base_id) {
+                Some(pt) => pt,
+                None => {
+                    eprintln!("Warning: base paytable '{}' not found for WWW variant '{}'", base_id, id);
+                    return None;
+                }
             };
+            base.with_www(wilds, www_override_table(base_id)).map_err(|e| eprintln!("Warning: {}", e)).ok()
+        },
 
-            if let Some(mut pt) = get_paytable(base_id) {
-                pt.id = id.to_string();
-                pt.name = format!("WWW {}", pt.name);
-                apply_www_overrides(base_id, &mut pt);
-                Some(pt)
-            } else {
-                eprintln!("Warning: base paytable '{}' not found for WWW variant '{}'", base_id, id);
-                None
+        // Short-coin variants — auto-derived from base paytable
+        // ID format: "{base_paytable_id}-{N}coin" where N = 1 to 4
+        id if id.ends_with("coin") => {
+            let (base_id, suffix) = id.rsplit_once('-')?;
+            let coins = suffix.strip_suffix("coin")?.parse::<u8>().ok()?;
+            if coins >= 5 {
+                return None;
+            }
+            match get_paytable(base_id)?.short_coin(coins) {
+                Ok(pt) => Some(pt),
+                Err(e) => {
+                    eprintln!("Warning: {}", e);
+                    None
+                }
             }
         },
 
+        // Sequential royal: "{id}-seq{N}" adds an N per coin bonus for an ordered royal
+        id if id.rsplit_once('-').is_some_and(|(_, s)| s.starts_with("seq")) => {
+            let (base_id, suffix) = id.rsplit_once('-')?;
+            let pay = suffix.strip_prefix("seq")?.parse::<u32>().ok()?;
+            get_paytable(base_id)?.with_sequential_royal(pay).map_err(|e| eprintln!("Warning: {}", e)).ok()
+        },
+
+        // Quick Quads: "{id}-qq"
+        id if id.ends_with("-qq") => {
+            get_paytable(id.strip_suffix("-qq")?)?.with_quick_quads().map_err(|e| eprintln!("Warning: {}", e)).ok()
+        },
+
+        // Bug joker: "{joker-id}-bug" plays a joker poker paytable with JokerMode::Bug
+        id if id.ends_with("-bug") => {
+            get_paytable(id.strip_suffix("-bug")?)?.bug_joker().map_err(|e| eprintln!("Warning: {}", e)).ok()
+        },
+
+        // Custom decks: "{id}-{preset}" deals the paytable from a deck_presets() deck
+        id if deck_presets().iter().any(|(preset, _, _)| id.ends_with(&format!("-{}", preset))) => {
+            let (preset, _, _) = deck_presets().into_iter().find(|(preset, _, _)| id.ends_with(&format!("-{}", preset)))?;
+            let base_id = id.strip_suffix(preset)?.strip_suffix('-')?;
+            get_paytable(base_id)?.with_deck(preset).map_err(|e| eprintln!("Warning: {}", e)).ok()
+        },
+
         _ => None,
     }
 }
 
+/// Catalog id for playing `id` at `coins` coins: "{id}-{N}coin" below max coins, the id
+/// itself at max coins. None for paytables that can't be short-coined (WWW).
+fn coin_paytable_id(id: &str, coins: u8) -> Option<String> {
+    if coins >= 5 {
+        Some(id.to_string())
+    } else if get_paytable(id).is_some_and(|pt| pt.is_www()) {
+        None
+    } else {
+        Some(format!("{}-{}coin", id, coins))
+    }
+}
+
+/// Inverse of coin_paytable_id: the max-coin paytable id and coins bet.
+fn split_coin_paytable_id(id: &str) -> (&str, u8) {
+    if let Some((base_id, suffix)) = id.rsplit_once('-') {
+        if let Some(coins) = suffix.strip_suffix("coin").and_then(|n| n.parse::<u8>().ok()) {
+            return (base_id, coins);
+        }
+    }
+    (id, 5)
+}
+
 fn get_all_paytable_ids() -> Vec<&'static str> {
     vec![
         // Jacks or Better
@@ -2728,6 +3980,8 @@ fn get_all_paytable_ids() -> Vec<&'static str> {
         "ddb-plus-9-6", "ddb-plus-9-5", "ddb-plus-8-5",
         // All American
         "all-american-35-8", "all-american-30-8", "all-american-25-8", "all-american-40-7",
+        // Quick Quads
+        "jacks-or-better-9-6-qq", "bonus-poker-8-5-qq", "double-bonus-10-7-qq", "double-double-bonus-9-6-qq",
         // Deuces Wild
         "deuces-wild-full-pay", "deuces-wild-nsud", "deuces-wild-illinois", "deuces-wild-20-12-9",
         "deuces-wild-25-15-8", "deuces-wild-20-15-9", "deuces-wild-25-12-9", "deuces-wild-colorado",
@@ -2857,7 +4111,7 @@ fn is_straight_wild(non_wilds: &[Card], num_wilds: u8) -> bool {
     let deuces_wheel_ranks = [1u8, 2, 3, 12];
 
     if ranks.iter().all(|&r| wheel_ranks.contains(&r) || deuces_wheel_ranks.contains(&r)) {
-        let target_ranks: Vec<u8> = if num_wilds > 0 && ranks.iter().any(|&r| r == 0) {
+        let target_ranks: Vec<u8> = if num_wilds > 0 && ranks.contains(&0) {
             // If we have deuces as wilds, use normal wheel
             wheel_ranks.to_vec()
         } else {
@@ -2887,9 +4141,108 @@ fn is_royal_wild(non_wilds: &[Card], num_wilds: u8) -> bool {
 // PAYOUT CALCULATION
 // ============================================================================
 
-fn get_standard_payout(hand: &[Card], paytable: &Paytable) -> f64 {
-    if hand.len() != 5 { return 0.0; }
+/// A final hand as the paytable sees it. Every game family is classified into one of
+/// these and Paytable::payout maps it to the pay, so strategy generation, distributions
+/// and the tests all score hands through the same evaluator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum HandType {
+    Nothing,
+    JacksOrBetter,
+    TensOrBetter,
+    KingsOrBetter,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    Four5ToK,
+    Four2To4,
+    FourAces,
+    FourJToK,
+    Four8s,
+    Four7s,
+    FourAcesWith2To4,
+    Four2To4WithAceTo4,
+    FourAcesWithFace,
+    FourJToKWithFace,
+    StraightFlush,
+    FiveOfAKind,
+    FiveAces,
+    Five2To4,
+    /// Deuces bases: five 3s, 4s or 5s (paid from five_2_4)
+    Five3To5,
+    Five5ToK,
+    /// Deuces bases: five 6s through Kings (paid from five_5_k)
+    Five6ToK,
+    FiveJToK,
+    Five5To10,
+    WildRoyalFlush,
+    FourDeuces,
+    FiveDeuces,
+    /// Custom decks: four wild-rank cards (paid from four_deuces)
+    FourWilds,
+    /// Custom decks: four wild-rank cards and a joker (paid from five_deuces)
+    FiveWilds,
+    RoyalFlush,
+    /// A royal without wild cards, in games that also pay a wild royal
+    NaturalRoyalFlush,
+}
+
+impl HandType {
+    /// Display name, as stored with hand distributions
+    fn name(self) -> &'static str {
+        match self {
+            HandType::Nothing => "Nothing",
+            HandType::JacksOrBetter => "Jacks or Better",
+            HandType::TensOrBetter => "Tens or Better",
+            HandType::KingsOrBetter => "Kings or Better",
+            HandType::TwoPair => "Two Pair",
+            HandType::ThreeOfAKind => "Three of a Kind",
+            HandType::Straight => "Straight",
+            HandType::Flush => "Flush",
+            HandType::FullHouse => "Full House",
+            HandType::FourOfAKind => "Four of a Kind",
+            HandType::Four5ToK => "Four 5-K",
+            HandType::Four2To4 => "Four 2-4",
+            HandType::FourAces => "Four Aces",
+            HandType::FourJToK => "Four J-K",
+            HandType::Four8s => "Four 8s",
+            HandType::Four7s => "Four 7s",
+            HandType::FourAcesWith2To4 => "Four Aces w/ 2-4",
+            HandType::Four2To4WithAceTo4 => "Four 2-4 w/ A-4",
+            HandType::FourAcesWithFace => "Four Aces w/ J-K",
+            HandType::FourJToKWithFace => "Four J-K w/ Face",
+            HandType::StraightFlush => "Straight Flush",
+            HandType::FiveOfAKind => "Five of a Kind",
+            HandType::FiveAces => "Five Aces",
+            HandType::Five2To4 => "Five 2-4",
+            HandType::Five3To5 => "Five 3-5",
+            HandType::Five5ToK => "Five 5-K",
+            HandType::Five6ToK => "Five 6-K",
+            HandType::FiveJToK => "Five J-K",
+            HandType::Five5To10 => "Five 5-10",
+            HandType::WildRoyalFlush => "Wild Royal Flush",
+            HandType::FourDeuces => "Four Deuces",
+            HandType::FiveDeuces => "Five Deuces",
+            HandType::FourWilds => "Four Wilds",
+            HandType::FiveWilds => "Five Wilds",
+            HandType::RoyalFlush => "Royal Flush",
+            HandType::NaturalRoyalFlush => "Natural Royal Flush",
+        }
+    }
+
+    /// The paying pair for the paytable's minimum pair rank
+    fn high_pair(paytable: &Paytable) -> HandType {
+        match paytable.min_pair_rank {
+            8 => HandType::TensOrBetter,
+            11 => HandType::KingsOrBetter,
+            _ => HandType::JacksOrBetter,
+        }
+    }
+}
 
+fn get_standard_hand_type(hand: &[Card], paytable: &Paytable) -> HandType {
     let flush = is_flush(hand);
     let straight = is_straight(hand);
     let counts = get_rank_counts(hand);
@@ -2899,9 +4252,9 @@ fn get_standard_payout(hand: &[Card], paytable: &Paytable) -> f64 {
         let mut ranks: Vec<u8> = hand.iter().map(|c| c.rank()).collect();
         ranks.sort();
         if ranks == vec![8, 9, 10, 11, 12] {
-            return paytable.royal_flush;
+            return HandType::RoyalFlush;
         }
-        return paytable.straight_flush;
+        return HandType::StraightFlush;
     }
 
     // Count pairs, trips, quads
@@ -2919,148 +4272,74 @@ fn get_standard_payout(hand: &[Card], paytable: &Paytable) -> f64 {
         }
     }
 
-    // Four of a Kind
+    // Four of a Kind, with any bonus tier the paytable pays
     if let Some(qr) = quad_rank {
-        // Check for kicker bonuses first (DDB, TDB)
-        if paytable.has_kicker_bonus() {
-            let kicker_rank = counts.iter().enumerate()
-                .find(|(r, &c)| c == 1 && *r as u8 != qr)
-                .map(|(r, _)| r as u8)
-                .unwrap_or(0);
-
-            let is_low_kicker = kicker_rank <= 2 || kicker_rank == 12; // 2,3,4 or A
-
-            if qr == 12 { // Four Aces
-                if is_low_kicker {
-                    if let Some(payout) = paytable.four_aces_with_kicker {
-                        return payout;
-                    }
-                }
-                if let Some(payout) = paytable.four_aces {
-                    return payout;
-                }
-            } else if qr <= 2 { // Four 2s, 3s, or 4s
-                if is_low_kicker {
-                    if let Some(payout) = paytable.four_2_4_with_kicker {
-                        return payout;
-                    }
-                }
-                if let Some(payout) = paytable.four_2_4 {
-                    return payout;
-                }
-            } else { // Four 5s through Kings
-                if let Some(payout) = paytable.four_5_k {
-                    return payout;
-                }
-            }
-        }
-
-        // Check for face kicker bonuses (Double Jackpot, Double Double Jackpot)
-        if paytable.has_face_kicker_bonus() {
-            let kicker_rank = counts.iter().enumerate()
-                .find(|(r, &c)| c == 1 && *r as u8 != qr)
-                .map(|(r, _)| r as u8)
-                .unwrap_or(0);
-
-            // Face kicker = J (9), Q (10), K (11), A (12)
-            let is_face_kicker = kicker_rank >= 9;
-
-            if qr == 12 { // Four Aces
-                if is_face_kicker {
-                    if let Some(payout) = paytable.four_aces_with_face {
-                        return payout;
-                    }
-                }
-                if let Some(payout) = paytable.four_aces {
-                    return payout;
-                }
-            } else if qr >= 9 && qr <= 11 { // Four J, Q, K
-                if is_face_kicker {
-                    if let Some(payout) = paytable.four_jqk_with_face {
-                        return payout;
-                    }
-                }
-                if let Some(payout) = paytable.four_jqk {
-                    return payout;
-                }
-            } else { // Four others (5-10)
-                return paytable.four_of_a_kind;
-            }
-        }
+        let kicker_rank = counts.iter().enumerate()
+            .find(|(r, &c)| c == 1 && *r as u8 != qr)
+            .map(|(r, _)| r as u8)
+            .unwrap_or(0);
+        return get_quad_hand_type(qr, Some(kicker_rank), paytable);
+    }
 
-        // Standard bonus payouts (without kicker)
-        if qr == 12 { // Four Aces
-            if let Some(payout) = paytable.four_aces {
-                return payout;
-            }
-        }
-        if qr <= 2 { // Four 2s, 3s, or 4s
-            if let Some(payout) = paytable.four_2_4 {
-                return payout;
-            }
-        }
-        // Four J, Q, K (Aces and Faces, Super Double Bonus)
-        if qr >= 9 && qr <= 11 {
-            if let Some(payout) = paytable.four_jqk {
-                return payout;
-            }
+    // Quick Quads (a quick quad has no kicker card)
+    if trips > 0 && paytable.quick_quads.is_some() {
+        if let Some(trip_rank) = quick_quad_rank(&counts) {
+            return get_quad_hand_type(trip_rank, None, paytable);
         }
-        // Four 8s (Aces and Eights) - rank 6
-        if qr == 6 {
-            if let Some(payout) = paytable.four_8s {
-                return payout;
-            }
-        }
-        // Four 7s (Aces and Eights) - rank 5
-        if qr == 5 {
-            if let Some(payout) = paytable.four_7s {
-                return payout;
-            }
-        }
-        // Four 5-K (or default)
-        if let Some(payout) = paytable.four_5_k {
-            return payout;
-        }
-        return paytable.four_of_a_kind;
     }
 
     // Full House
     if trips > 0 && pairs > 0 {
-        return paytable.full_house;
+        return HandType::FullHouse;
     }
 
     // Flush
     if flush {
-        return paytable.flush;
+        return HandType::Flush;
     }
 
     // Straight
     if straight {
-        return paytable.straight;
+        return HandType::Straight;
     }
 
     // Three of a Kind
     if trips > 0 {
-        return paytable.three_of_a_kind;
+        return HandType::ThreeOfAKind;
     }
 
     // Two Pair
     if pairs == 2 {
-        return paytable.two_pair;
+        return HandType::TwoPair;
     }
 
     // High Pair
     if pairs == 1 {
         let pair_rank = pair_ranks[0] as u8;
         if pair_rank >= paytable.min_pair_rank {
-            return paytable.high_pair;
+            return HandType::high_pair(paytable);
         }
     }
 
-    0.0
+    HandType::Nothing
+}
+
+/// Card value for Quick Quads sums: A=1, 2-10 face value, J=11, Q=12, K=13
+fn quick_quads_value(rank: u8) -> u8 {
+    if rank == 12 { 1 } else { rank + 2 }
+}
+
+/// Trip rank when the other two cards add up to it
+fn quick_quad_rank(counts: &[u8; 13]) -> Option<u8> {
+    let trip_rank = counts.iter().position(|&c| c == 3)? as u8;
+    let kicker_sum: u8 = counts.iter().enumerate()
+        .filter(|&(r, _)| r as u8 != trip_rank)
+        .map(|(r, &c)| c * quick_quads_value(r as u8))
+        .sum();
+    (kicker_sum == quick_quads_value(trip_rank)).then_some(trip_rank)
 }
 
-fn get_deuces_wild_payout(hand: &[Card], paytable: &Paytable) -> f64 {
+fn get_deuces_wild_hand_type(hand: &[Card]) -> HandType {
     let num_deuces = count_deuces(hand);
     let non_deuces = get_non_deuces(hand);
 
@@ -3080,33 +4359,33 @@ fn get_deuces_wild_payout(hand: &[Card], paytable: &Paytable) -> f64 {
         let mut ranks: Vec<u8> = non_deuces.iter().map(|c| c.rank()).collect();
         ranks.sort();
         if ranks == vec![8, 9, 10, 11, 12] {
-            return paytable.royal_flush;
+            return HandType::NaturalRoyalFlush;
         }
     }
 
     // Four Deuces
     if num_deuces == 4 {
-        return paytable.four_deuces.unwrap_or(200.0);
+        return HandType::FourDeuces;
     }
 
     // Wild Royal Flush
     if is_royal_wild(&non_deuces, num_deuces) && num_deuces > 0 {
-        return paytable.wild_royal.unwrap_or(25.0);
+        return HandType::WildRoyalFlush;
     }
 
     // Five of a Kind
     if max_count + num_deuces >= 5 {
-        return paytable.five_of_a_kind.unwrap_or(15.0);
+        return HandType::FiveOfAKind;
     }
 
     // Straight Flush (not royal)
     if is_flush && is_straight && !is_royal_wild(&non_deuces, num_deuces) {
-        return paytable.straight_flush;
+        return HandType::StraightFlush;
     }
 
     // Four of a Kind
     if max_count + num_deuces >= 4 {
-        return paytable.four_of_a_kind;
+        return HandType::FourOfAKind;
     }
 
     // Full House
@@ -3126,30 +4405,68 @@ fn get_deuces_wild_payout(hand: &[Card], paytable: &Paytable) -> f64 {
         };
 
         if can_make_full_house && max_count + num_deuces < 4 {
-            return paytable.full_house;
+            return HandType::FullHouse;
         }
     }
 
     // Flush
     if is_flush && !is_straight {
-        return paytable.flush;
+        return HandType::Flush;
     }
 
     // Straight
     if is_straight && !is_flush {
-        return paytable.straight;
+        return HandType::Straight;
     }
 
     // Three of a Kind
     if max_count + num_deuces >= 3 {
-        return paytable.three_of_a_kind;
+        return HandType::ThreeOfAKind;
     }
 
-    0.0
+    HandType::Nothing
+}
+
+// Bug joker: the joker is an ace unless it completes a straight or flush, so pairs,
+// trips, quads and full houses can only be improved with aces
+fn get_bug_joker_hand_type(hand: &[Card], paytable: &Paytable) -> HandType {
+    let num_jokers = count_jokers(hand);
+    let non_jokers = get_non_jokers(hand);
+    let is_flush = is_flush_wild(&non_jokers);
+    let is_straight = is_straight_wild(&non_jokers, num_jokers);
+
+    // Rank counts with every joker played as an ace
+    let mut counts = [0u8; 13];
+    for card in &non_jokers { if card.rank() < 13 { counts[card.rank() as usize] += 1; } }
+    counts[12] += num_jokers;
+    let mut sorted_counts: Vec<u8> = counts.iter().cloned().filter(|&c| c > 0).collect();
+    sorted_counts.sort(); sorted_counts.reverse();
+
+    if sorted_counts[0] >= 5 { return HandType::FiveOfAKind; }
+    if is_royal_wild(&non_jokers, num_jokers) { return HandType::WildRoyalFlush; }
+    if is_flush && is_straight { return HandType::StraightFlush; }
+    if sorted_counts[0] == 4 { return HandType::FourOfAKind; }
+    if sorted_counts[0] == 3 && sorted_counts[1] == 2 { return HandType::FullHouse; }
+    if is_flush { return HandType::Flush; }
+    if is_straight { return HandType::Straight; }
+    if sorted_counts[0] == 3 { return HandType::ThreeOfAKind; }
+    if sorted_counts[0] == 2 && sorted_counts[1] == 2 && paytable.two_pair > 0.0 {
+        return HandType::TwoPair;
+    }
+    let high_pair = counts.iter().enumerate()
+        .any(|(r, &c)| c >= 2 && r as u8 >= paytable.min_pair_rank);
+    if high_pair && paytable.high_pair > 0.0 {
+        return HandType::high_pair(paytable);
+    }
+
+    HandType::Nothing
 }
 
-fn get_joker_payout(hand: &[Card], paytable: &Paytable) -> f64 {
+fn get_joker_hand_type(hand: &[Card], paytable: &Paytable) -> HandType {
     let num_jokers = count_jokers(hand);
+    if num_jokers > 0 && paytable.joker_mode == JokerMode::Bug {
+        return get_bug_joker_hand_type(hand, paytable);
+    }
     let non_jokers = get_non_jokers(hand);
 
     // Get rank counts for non-jokers
@@ -3171,28 +4488,28 @@ fn get_joker_payout(hand: &[Card], paytable: &Paytable) -> f64 {
         let mut ranks: Vec<u8> = non_jokers.iter().map(|c| c.rank()).collect();
         ranks.sort();
         if ranks == vec![8, 9, 10, 11, 12] {
-            return paytable.royal_flush;
+            return HandType::NaturalRoyalFlush;
         }
     }
 
     // Five of a Kind (with joker)
     if max_count + num_jokers >= 5 {
-        return paytable.five_of_a_kind.unwrap_or(100.0);
+        return HandType::FiveOfAKind;
     }
 
     // Wild Royal Flush
     if is_royal_wild(&non_jokers, num_jokers) && num_jokers > 0 {
-        return paytable.wild_royal.unwrap_or(50.0);
+        return HandType::WildRoyalFlush;
     }
 
     // Straight Flush
     if is_flush && is_straight {
-        return paytable.straight_flush;
+        return HandType::StraightFlush;
     }
 
     // Four of a Kind
     if max_count + num_jokers >= 4 {
-        return paytable.four_of_a_kind;
+        return HandType::FourOfAKind;
     }
 
     // Full House
@@ -3210,30 +4527,30 @@ fn get_joker_payout(hand: &[Card], paytable: &Paytable) -> f64 {
         };
 
         if can_make_full_house && max_count + num_jokers < 4 {
-            return paytable.full_house;
+            return HandType::FullHouse;
         }
     }
 
     // Flush
     if is_flush && !is_straight {
-        return paytable.flush;
+        return HandType::Flush;
     }
 
     // Straight
     if is_straight && !is_flush {
-        return paytable.straight;
+        return HandType::Straight;
     }
 
     // Three of a Kind
     if max_count + num_jokers >= 3 {
-        return paytable.three_of_a_kind;
+        return HandType::ThreeOfAKind;
     }
 
     // Two Pair
-    if num_pairs == 2 || (num_pairs == 1 && num_jokers >= 1 && max_count < 3) {
-        if paytable.two_pair > 0.0 {
-            return paytable.two_pair;
-        }
+    if (num_pairs == 2 || (num_pairs == 1 && num_jokers >= 1 && max_count < 3))
+        && paytable.two_pair > 0.0
+    {
+        return HandType::TwoPair;
     }
 
     // High Pair (Kings or better for most joker games)
@@ -3246,102 +4563,111 @@ fn get_joker_payout(hand: &[Card], paytable: &Paytable) -> f64 {
         pair_ranks.reverse();
 
         if !pair_ranks.is_empty() && pair_ranks[0] as u8 >= paytable.min_pair_rank {
-            return paytable.high_pair;
+            return HandType::high_pair(paytable);
         }
         // With a joker, we can make a pair of any rank
         if num_jokers >= 1 && paytable.high_pair > 0.0 {
             // Find highest card rank
             if let Some(highest) = counts.iter().enumerate().rev().find(|(_, &c)| c >= 1).map(|(r, _)| r) {
                 if highest as u8 >= paytable.min_pair_rank {
-                    return paytable.high_pair;
+                    return HandType::high_pair(paytable);
                 }
             }
         }
     }
 
-    0.0
+    HandType::Nothing
 }
 
-fn get_www_quad_payout(quad_rank: u8, kicker_rank: u8, paytable: &Paytable) -> f64 {
-    // Kicker bonuses (DDB, TDB, TTB): low kicker = 2,3,4 or Ace
+/// Four-of-a-kind tier for a quad of `quad_rank` with `kicker_rank` as the fifth card
+/// (None for a quick quad, which has no kicker).
+fn get_quad_hand_type(quad_rank: u8, kicker_rank: Option<u8>, paytable: &Paytable) -> HandType {
+    // Kicker bonuses (DDB, TDB, TTB): four aces take a 2-4 kicker, four 2s-4s take an A-4
+    // kicker. A fifth ace (Five Aces deck) is not a kicker for four aces.
     if paytable.has_kicker_bonus() {
-        let is_low_kicker = kicker_rank <= 2 || kicker_rank == 12;
         if quad_rank == 12 {
-            if is_low_kicker { if let Some(p) = paytable.four_aces_with_kicker { return p; } }
-            if let Some(p) = paytable.four_aces { return p; }
+            let is_2_4_kicker = matches!(kicker_rank, Some(k) if k <= 2);
+            if is_2_4_kicker && paytable.four_aces_with_kicker.is_some() { return HandType::FourAcesWith2To4; }
+            if paytable.four_aces.is_some() { return HandType::FourAces; }
         } else if quad_rank <= 2 {
-            if is_low_kicker { if let Some(p) = paytable.four_2_4_with_kicker { return p; } }
-            if let Some(p) = paytable.four_2_4 { return p; }
-        } else {
-            if let Some(p) = paytable.four_5_k { return p; }
+            let is_a_4_kicker = matches!(kicker_rank, Some(k) if k <= 2 || k == 12);
+            if is_a_4_kicker && paytable.four_2_4_with_kicker.is_some() { return HandType::Four2To4WithAceTo4; }
+            if paytable.four_2_4.is_some() { return HandType::Four2To4; }
+        } else if paytable.four_5_k.is_some() {
+            return HandType::Four5ToK;
         }
     }
 
     // Face kicker bonuses (Double Jackpot, Double Double Jackpot)
     if paytable.has_face_kicker_bonus() {
-        let is_face_kicker = kicker_rank >= 9;
+        let is_face_kicker = matches!(kicker_rank, Some(k) if k >= 9);
         if quad_rank == 12 {
-            if is_face_kicker { if let Some(p) = paytable.four_aces_with_face { return p; } }
-            if let Some(p) = paytable.four_aces { return p; }
-        } else if quad_rank >= 9 && quad_rank <= 11 {
-            if is_face_kicker { if let Some(p) = paytable.four_jqk_with_face { return p; } }
-            if let Some(p) = paytable.four_jqk { return p; }
+            if is_face_kicker && paytable.four_aces_with_face.is_some() { return HandType::FourAcesWithFace; }
+            if paytable.four_aces.is_some() { return HandType::FourAces; }
+        } else if (9..=11).contains(&quad_rank) {
+            if is_face_kicker && paytable.four_jqk_with_face.is_some() { return HandType::FourJToKWithFace; }
+            if paytable.four_jqk.is_some() { return HandType::FourJToK; }
         } else {
-            return paytable.four_of_a_kind;
+            return HandType::FourOfAKind;
         }
     }
 
     // Standard bonus payouts
-    if quad_rank == 12 { if let Some(p) = paytable.four_aces { return p; } }
-    if quad_rank <= 2 { if let Some(p) = paytable.four_2_4 { return p; } }
-    if quad_rank >= 9 && quad_rank <= 11 { if let Some(p) = paytable.four_jqk { return p; } }
-    if quad_rank == 6 { if let Some(p) = paytable.four_8s { return p; } }
-    if quad_rank == 5 { if let Some(p) = paytable.four_7s { return p; } }
-    if let Some(p) = paytable.four_5_k { return p; }
-    paytable.four_of_a_kind
+    if quad_rank == 12 && paytable.four_aces.is_some() { return HandType::FourAces; }
+    if quad_rank <= 2 && paytable.four_2_4.is_some() { return HandType::Four2To4; }
+    if (9..=11).contains(&quad_rank) && paytable.four_jqk.is_some() { return HandType::FourJToK; }
+    if quad_rank == 6 && paytable.four_8s.is_some() { return HandType::Four8s; }
+    if quad_rank == 5 && paytable.four_7s.is_some() { return HandType::Four7s; }
+    if paytable.four_5_k.is_some() { return HandType::Four5ToK; }
+    HandType::FourOfAKind
 }
 
-/// Resolve tiered five-of-a-kind payout by rank.
+/// Resolve the tiered five of a kind by rank; tiers the paytable doesn't pay fall back
+/// to plain Five of a Kind.
 /// For deuces games, rank grouping shifts: five_2_4 covers 3s,4s,5s (since 2s are wild).
-fn get_www_five_of_a_kind_payout(rank: u8, paytable: &Paytable) -> f64 {
-    let is_deuces = paytable.is_deuces_wild();
-    let fallback = paytable.five_of_a_kind.unwrap_or(100.0);
+fn get_www_five_of_a_kind_hand_type(rank: u8, paytable: &Paytable) -> HandType {
+    let tier = |hand_type: HandType, payout: Option<f64>| {
+        if payout.is_some() { hand_type } else { HandType::FiveOfAKind }
+    };
 
     if rank == 12 {
         // Aces
-        return paytable.five_aces.unwrap_or(fallback);
+        return tier(HandType::FiveAces, paytable.five_aces);
     }
 
-    if is_deuces {
+    if paytable.is_deuces_wild() {
         // Deuces games: five_2_4 covers 3s,4s,5s (ranks 1,2,3)
-        if rank >= 1 && rank <= 3 {
-            return paytable.five_2_4.unwrap_or(fallback);
+        if (1..=3).contains(&rank) {
+            return tier(HandType::Five3To5, paytable.five_2_4);
         }
         // Everything else (6s thru Ks, ranks 4-11)
-        return paytable.five_5_k.unwrap_or(fallback);
+        return tier(HandType::Five6ToK, paytable.five_5_k);
     }
 
     // Non-deuces games: five_2_4 covers 2s,3s,4s (ranks 0,1,2)
     if rank <= 2 {
-        return paytable.five_2_4.unwrap_or(fallback);
+        return tier(HandType::Five2To4, paytable.five_2_4);
     }
 
     // Js,Qs,Ks (ranks 9,10,11) — only meaningful for SDB/SDDB
-    if rank >= 9 && rank <= 11 {
-        return paytable.five_jqk
-            .or(paytable.five_5_k)
-            .unwrap_or(fallback);
+    if (9..=11).contains(&rank) {
+        if paytable.five_jqk.is_some() {
+            return HandType::FiveJToK;
+        }
+        return tier(HandType::Five5ToK, paytable.five_5_k);
     }
 
     // 5s thru 10s (ranks 3-8) — or 5s thru Ks if no five_jqk
-    if paytable.five_5_10.is_some() && rank >= 3 && rank <= 8 {
-        return paytable.five_5_10.unwrap_or(fallback);
+    if paytable.five_5_10.is_some() && (3..=8).contains(&rank) {
+        return HandType::Five5To10;
     }
 
-    paytable.five_5_k.unwrap_or(fallback)
+    tier(HandType::Five5ToK, paytable.five_5_k)
 }
 
-fn get_www_payout(hand: &[Card], paytable: &Paytable) -> f64 {
+/// Hand type for WWW paytables: jokers (and deuces on deuces bases) are wild,
+/// five of a kind is tiered by rank and quads follow the base game's bonus rules.
+fn get_www_hand_type(hand: &[Card], paytable: &Paytable) -> HandType {
     let num_jokers = hand.iter().filter(|c| c.is_joker()).count() as u8;
 
     // Determine if base game treats deuces as wild
@@ -3368,15 +4694,13 @@ fn get_www_payout(hand: &[Card], paytable: &Paytable) -> f64 {
     let is_straight = is_straight_wild(&naturals, total_wilds);
 
     // Five Deuces (4 natural deuces + joker)
-    if is_deuces_base && num_deuces == 4 && num_jokers >= 1 {
-        if let Some(five_d) = paytable.five_deuces {
-            return five_d;
-        }
+    if is_deuces_base && num_deuces == 4 && num_jokers >= 1 && paytable.five_deuces.is_some() {
+        return HandType::FiveDeuces;
     }
 
     // Deuces-specific: Four Deuces (requires actual deuces, not jokers)
     if is_deuces_base && num_deuces == 4 {
-        return paytable.four_deuces.unwrap_or(200.0);
+        return HandType::FourDeuces;
     }
 
     // Natural Royal (zero wilds of any kind)
@@ -3384,34 +4708,34 @@ fn get_www_payout(hand: &[Card], paytable: &Paytable) -> f64 {
         let mut ranks: Vec<u8> = naturals.iter().map(|c| c.rank()).collect();
         ranks.sort();
         if ranks == vec![8, 9, 10, 11, 12] {
-            return paytable.royal_flush;
+            return HandType::NaturalRoyalFlush;
         }
     }
 
-    // Five of a Kind — use tiered payout by rank
+    // Five of a Kind — tiered by rank
     if max_count + total_wilds >= 5 {
         // Find the rank that forms the five-of-a-kind
         let five_rank = counts.iter().enumerate()
             .max_by_key(|(_, &c)| c)
             .map(|(r, _)| r as u8)
             .unwrap_or(0);
-        return get_www_five_of_a_kind_payout(five_rank, paytable);
+        return get_www_five_of_a_kind_hand_type(five_rank, paytable);
     }
 
     // Wild Royal Flush
     if total_wilds > 0 && is_royal_wild(&naturals, total_wilds) {
-        return paytable.wild_royal.unwrap_or(50.0);
+        return HandType::WildRoyalFlush;
     }
 
     // Straight Flush
     if is_flush && is_straight {
-        return paytable.straight_flush;
+        return HandType::StraightFlush;
     }
 
     // Four of a Kind — use bonus-aware resolution for non-deuces bases
     if max_count + total_wilds >= 4 {
         if is_deuces_base {
-            return paytable.four_of_a_kind;
+            return HandType::FourOfAKind;
         }
         // For bonus games: find which rank makes the quad
         let quad_rank = counts.iter().enumerate()
@@ -3424,7 +4748,7 @@ fn get_www_payout(hand: &[Card], paytable: &Paytable) -> f64 {
             .map(|c| c.rank())
             .max()
             .unwrap_or(0);
-        return get_www_quad_payout(quad_rank, kicker, paytable);
+        return get_quad_hand_type(quad_rank, Some(kicker), paytable);
     }
 
     // Full House
@@ -3438,31 +4762,31 @@ fn get_www_payout(hand: &[Card], paytable: &Paytable) -> f64 {
             let need_for_trips = 3_u8.saturating_sub(sorted_counts[0]);
             let need_for_pair = 2_u8.saturating_sub(sorted_counts[1]);
             if need_for_trips + need_for_pair <= total_wilds && max_count + total_wilds < 4 {
-                return paytable.full_house;
+                return HandType::FullHouse;
             }
         }
     }
 
     // Flush
     if is_flush && !is_straight {
-        return paytable.flush;
+        return HandType::Flush;
     }
 
     // Straight
     if is_straight && !is_flush {
-        return paytable.straight;
+        return HandType::Straight;
     }
 
     // Three of a Kind
     if max_count + total_wilds >= 3 {
-        return paytable.three_of_a_kind;
+        return HandType::ThreeOfAKind;
     }
 
     // Two Pair
-    if num_pairs >= 2 || (num_pairs == 1 && total_wilds >= 1 && max_count < 3) {
-        if paytable.two_pair > 0.0 {
-            return paytable.two_pair;
-        }
+    if (num_pairs >= 2 || (num_pairs == 1 && total_wilds >= 1 && max_count < 3))
+        && paytable.two_pair > 0.0
+    {
+        return HandType::TwoPair;
     }
 
     // High Pair
@@ -3473,105 +4797,320 @@ fn get_www_payout(hand: &[Card], paytable: &Paytable) -> f64 {
             counts.iter().enumerate().rev()
                 .find(|(_, &c)| c >= 2).map(|(r, _)| r as u8).unwrap_or(0)
         };
-        if best_pair_rank >= paytable.min_pair_rank {
-            return paytable.high_pair;
+        if best_pair_rank >= paytable.min_pair_rank && paytable.high_pair > 0.0 {
+            return HandType::high_pair(paytable);
         }
     }
 
-    0.0
+    HandType::Nothing
 }
 
-fn get_payout(hand: &[Card], paytable: &Paytable) -> f64 {
-    if hand.len() != 5 { return 0.0; }
+/// Hand type for paytables with a custom deck (see DeckSpec). Wild cards take whatever
+/// values pay the most, so every hand the cards can form is considered and the best paying
+/// one wins; in ranking order on ties.
+fn get_custom_deck_hand_type(hand: &[Card], paytable: &Paytable, deck: &DeckSpec) -> HandType {
+    let naturals: Vec<Card> = hand.iter().filter(|c| !deck.is_wild(**c)).cloned().collect();
+    let num_wilds = (hand.len() - naturals.len()) as u8;
+    // Wild-rank cards only (not jokers or single wild cards), for Four/Five Deuces style bonuses
+    let num_wild_rank = hand.iter().filter(|c| c.rank() < 13 && deck.wild_ranks.contains(&c.rank())).count() as u8;
+
+    let mut counts = [0u8; 13];
+    for card in &naturals {
+        counts[card.rank() as usize] += 1;
+    }
+    let max_count = *counts.iter().max().unwrap_or(&0);
+    let distinct = counts.iter().filter(|&&c| c > 0).count();
+
+    let flush = is_flush_wild(&naturals);
+    let straight = max_count <= 1 && (
+        (0..=8u8).any(|low| naturals.iter().all(|c| c.rank() >= low && c.rank() <= low + 4))
+            || naturals.iter().all(|c| c.rank() == 12 || c.rank() <= 3)
+    );
+    let royal = flush && max_count <= 1 && naturals.iter().all(|c| c.rank() >= 8);
+
+    let mut candidates: Vec<HandType> = Vec::new();
+
+    if royal && num_wilds == 0 {
+        let has_wilds = deck.jokers > 0 || !deck.wild_ranks.is_empty() || !deck.wild_cards.is_empty();
+        candidates.push(if has_wilds { HandType::NaturalRoyalFlush } else { HandType::RoyalFlush });
+    }
+    if num_wild_rank == 4 && num_wilds == 5 && paytable.five_deuces.is_some() {
+        candidates.push(HandType::FiveWilds);
+    }
+    if num_wild_rank == 4 && paytable.four_deuces.is_some() {
+        candidates.push(HandType::FourWilds);
+    }
+    if royal && num_wilds > 0 && paytable.wild_royal.is_some() {
+        candidates.push(HandType::WildRoyalFlush);
+    }
+    if max_count + num_wilds >= 5 {
+        // All-wild hands can be five aces
+        let rank = if naturals.is_empty() { 12 } else { naturals[0].rank() };
+        if rank == 12 && paytable.five_aces.is_some() {
+            candidates.push(HandType::FiveAces);
+        }
+        if paytable.five_of_a_kind.is_some() {
+            candidates.push(HandType::FiveOfAKind);
+        }
+    }
+    if flush && straight {
+        candidates.push(HandType::StraightFlush);
+    }
+    // Quads of any reachable rank; a leftover wild can stand in for any kicker
+    for quad_rank in 0..13u8 {
+        let natural = counts[quad_rank as usize];
+        if natural + num_wilds < 4 || (natural == 0 && num_wilds < 4) {
+            continue;
+        }
+        let kickers: Vec<u8> = match naturals.iter().find(|c| c.rank() != quad_rank) {
+            Some(c) => vec![c.rank()],
+            None if natural == 5 => vec![quad_rank],
+            None => (0..13u8).filter(|&r| r != quad_rank).collect(),
+        };
+        for kicker in kickers {
+            candidates.push(get_quad_hand_type(quad_rank, Some(kicker), paytable));
+        }
+    }
+    let mut sorted_counts: Vec<u8> = counts.iter().cloned().filter(|&c| c > 0).collect();
+    sorted_counts.sort();
+    sorted_counts.reverse();
+    let top = sorted_counts.first().cloned().unwrap_or(0);
+    let second = sorted_counts.get(1).cloned().unwrap_or(0);
+    if distinct <= 2 && 3u8.saturating_sub(top) + 2u8.saturating_sub(second) <= num_wilds && top <= 3 {
+        candidates.push(HandType::FullHouse);
+    }
+    if flush {
+        candidates.push(HandType::Flush);
+    }
+    if straight {
+        candidates.push(HandType::Straight);
+    }
+    if max_count + num_wilds >= 3 {
+        candidates.push(HandType::ThreeOfAKind);
+    }
+    let pairs = counts.iter().filter(|&&c| c >= 2).count() as u8;
+    let singles = counts.iter().filter(|&&c| c == 1).count() as u8;
+    let paired_singles = num_wilds.min(singles);
+    if pairs + paired_singles + (num_wilds - paired_singles) / 2 >= 2 {
+        candidates.push(HandType::TwoPair);
+    }
+    let best_pair_rank = if num_wilds >= 2 {
+        Some(12)
+    } else {
+        counts.iter().enumerate().rev()
+            .find(|(_, &c)| c >= 2 || (c == 1 && num_wilds == 1))
+            .map(|(r, _)| r as u8)
+    };
+    if best_pair_rank.is_some_and(|r| r >= paytable.min_pair_rank) {
+        candidates.push(HandType::high_pair(paytable));
+    }
 
-    if paytable.is_www() {
-        get_www_payout(hand, paytable)
+    let mut best = HandType::Nothing;
+    for candidate in candidates {
+        if paytable.payout(candidate) > paytable.payout(best) {
+            best = candidate;
+        }
+    }
+    best
+}
+
+/// Classify a final five-card hand under the paytable's rules
+fn evaluate_hand(hand: &[Card], paytable: &Paytable) -> HandType {
+    if hand.len() != 5 { return HandType::Nothing; }
+
+    if let Some(deck) = &paytable.deck {
+        get_custom_deck_hand_type(hand, paytable, deck)
+    } else if paytable.is_www() {
+        get_www_hand_type(hand, paytable)
     } else if paytable.is_deuces_wild() {
-        get_deuces_wild_payout(hand, paytable)
+        get_deuces_wild_hand_type(hand)
     } else if paytable.is_joker_poker() {
-        get_joker_payout(hand, paytable)
+        get_joker_hand_type(hand, paytable)
     } else {
-        get_standard_payout(hand, paytable)
+        get_standard_hand_type(hand, paytable)
     }
 }
 
+fn get_payout(hand: &[Card], paytable: &Paytable) -> f64 {
+    paytable.payout(evaluate_hand(hand, paytable))
+}
+
 // ============================================================================
 // EV CALCULATION
 // ============================================================================
 
-fn calculate_hold_ev(hand: &Hand, hold_mask: u8, paytable: &Paytable, deck_size: u8) -> f64 {
-    let mut held: Vec<Card> = Vec::with_capacity(5);
-    for i in 0..5 {
+/// Held cards at the front of a five-card array, and how many there are
+fn held_cards(hand: &Hand, hold_mask: u8) -> ([Card; 5], usize) {
+    let mut final_hand = *hand;
+    let mut held = 0;
+    for (i, &card) in hand.iter().enumerate() {
         if hold_mask & (1 << i) != 0 {
-            held.push(hand[i]);
+            final_hand[held] = card;
+            held += 1;
         }
     }
+    (final_hand, held)
+}
 
-    let num_to_draw = 5 - held.len();
+/// EV of a hold, drawing from `full_deck` and classifying through the paytable's hand table
+fn calculate_hold_ev(hand: &Hand, hold_mask: u8, paytable: &Paytable, table: &handtable::HandTable, full_deck: &[Card]) -> f64 {
+    let (mut final_hand, held) = held_cards(hand, hold_mask);
 
-    if num_to_draw == 0 {
-        let final_hand: [Card; 5] = held.try_into().unwrap();
-        return get_payout(&final_hand, paytable);
+    if held == 5 {
+        return paytable.payout(table.hand_type(&final_hand));
     }
 
     // Build remaining deck
-    let mut deck: Vec<Card> = Vec::with_capacity((deck_size - 5) as usize);
-    for card_idx in 0..deck_size {
-        let card = Card(card_idx);
-        if !hand.contains(&card) {
-            deck.push(card);
-        }
-    }
+    let deck: Vec<Card> = full_deck.iter().filter(|c| !hand.contains(c)).cloned().collect();
 
     let mut total_payout = 0.0;
     let mut count = 0u64;
 
-    for draw in deck.iter().combinations(num_to_draw) {
-        let mut final_hand = held.clone();
-        for &card in &draw {
-            final_hand.push(*card);
+    for draw in deck.iter().combinations(5 - held) {
+        for (slot, &card) in final_hand[held..].iter_mut().zip(&draw) {
+            *slot = *card;
         }
-        let final_arr: [Card; 5] = final_hand.try_into().unwrap();
-        total_payout += get_payout(&final_arr, paytable);
+        total_payout += paytable.payout(table.hand_type(&final_hand));
         count += 1;
     }
 
     total_payout / count as f64
 }
 
-fn analyze_hand(hand: &Hand, paytable: &Paytable) -> (u8, f64, HashMap<String, f64>) {
-    let deck_size = 52 + paytable.num_jokers();
+/// Like calculate_hold_ev, but scores each draw against several paytables in one pass.
+/// The paytables must share a deck, and `tables` holds each one's hand table in the
+/// same order; returns one EV per paytable, in order.
+fn calculate_hold_ev_multi(
+    hand: &Hand,
+    hold_mask: u8,
+    paytables: &[&Paytable],
+    tables: &[&handtable::HandTable],
+    full_deck: &[Card],
+) -> Vec<f64> {
+    let (mut final_hand, held) = held_cards(hand, hold_mask);
 
-    let mut hold_evs: HashMap<String, f64> = HashMap::new();
-    let mut best_hold = 0u8;
-    let mut best_ev = f64::NEG_INFINITY;
+    if held == 5 {
+        return paytables.iter().zip(tables).map(|(pt, table)| pt.payout(table.hand_type(hand))).collect();
+    }
 
-    for hold_mask in 0..32u8 {
-        let ev = calculate_hold_ev(hand, hold_mask, paytable, deck_size);
-        hold_evs.insert(hold_mask.to_string(), (ev * 1000000.0).round() / 1000000.0);
-        if ev > best_ev {
-            best_ev = ev;
-            best_hold = hold_mask;
+    let deck: Vec<Card> = full_deck.iter().filter(|c| !hand.contains(c)).cloned().collect();
+
+    let mut totals = vec![0.0; paytables.len()];
+    let mut count = 0u64;
+
+    for draw in deck.iter().combinations(5 - held) {
+        for (slot, &card) in final_hand[held..].iter_mut().zip(&draw) {
+            *slot = *card;
+        }
+        for ((total, pt), table) in totals.iter_mut().zip(paytables).zip(tables) {
+            *total += pt.payout(table.hand_type(&final_hand));
         }
+        count += 1;
     }
 
-    (best_hold, (best_ev * 1000000.0).round() / 1000000.0, hold_evs)
+    totals.into_iter().map(|t| t / count as f64).collect()
 }
 
 // ============================================================================
-// CANONICAL HAND GENERATION
+// ORDERED OUTCOMES (SEQUENTIAL ROYAL)
 // ============================================================================
+//
+// Everything above treats a hand as a set of cards. A sequential royal also depends on
+// order: replacement cards fill the discarded positions left to right in the order they
+// are drawn, so each set of drawn cards lands in every one of its k! placements equally
+// often. Hold masks here are positional (bit i = the card in position i).
 
-fn hand_to_canonical_key(hand: &Hand) -> String {
-    let mut sorted: Vec<Card> = hand.to_vec();
+const SEQUENTIAL_ROYAL_RANKS: [u8; 5] = [8, 9, 10, 11, 12]; // T J Q K A, left to right
+
+fn is_sequential_royal(hand: &[Card]) -> bool {
+    hand.len() == 5 && !hand[0].is_joker()
+        && hand.iter().zip(SEQUENTIAL_ROYAL_RANKS).all(|(c, rank)| {
+            !c.is_joker() && c.rank() == rank && c.suit() == hand[0].suit()
+        })
+}
+
+/// Payout of a final hand in dealt order
+fn get_positional_payout(hand: &[Card], paytable: &Paytable) -> f64 {
+    match paytable.sequential_royal {
+        Some(pay) if is_sequential_royal(hand) => pay,
+        _ => get_payout(hand, paytable),
+    }
+}
+
+/// Chance that holding `hold_mask` finishes as a sequential royal, with `remaining`
+/// cards left in the deck after the deal
+fn sequential_royal_probability(hand: &Hand, hold_mask: u8, remaining: usize) -> f64 {
+    let to_draw = 5 - hold_mask.count_ones() as usize;
+    // Each needed card must land in one exact position
+    let ordered_draws: f64 = (0..to_draw).map(|i| (remaining - i) as f64).product();
+
+    let completions = (0..4u8)
+        .filter(|&suit| {
+            (0..5).all(|i| {
+                let target = Card(SEQUENTIAL_ROYAL_RANKS[i] * 4 + suit);
+                if hold_mask & (1 << i) != 0 {
+                    hand[i] == target
+                } else {
+                    // Needed cards that were dealt and discarded are gone
+                    !hand.contains(&target)
+                }
+            })
+        })
+        .count();
+
+    completions as f64 / ordered_draws
+}
+
+/// calculate_hold_ev for a hand in dealt order, including the sequential royal bonus.
+/// A sequential royal is also a natural royal, so the unordered EV already counts it at
+/// royal_flush; only the difference is added.
+fn calculate_positional_hold_ev(hand: &Hand, hold_mask: u8, paytable: &Paytable, table: &handtable::HandTable, full_deck: &[Card]) -> f64 {
+    let ev = calculate_hold_ev(hand, hold_mask, paytable, table, full_deck);
+    match paytable.sequential_royal {
+        Some(pay) => ev + (pay - paytable.royal_flush)
+            * sequential_royal_probability(hand, hold_mask, full_deck.len() - 5),
+        None => ev,
+    }
+}
+
+fn analyze_hand(hand: &Hand, paytable: &Paytable, table: &handtable::HandTable) -> (u8, f64, HashMap<String, f64>) {
+    let deck = paytable.deck_cards();
+
+    let mut hold_evs: HashMap<String, f64> = HashMap::new();
+    let mut best_hold = 0u8;
+    let mut best_ev = f64::NEG_INFINITY;
+
+    for hold_mask in 0..32u8 {
+        let ev = calculate_hold_ev(hand, hold_mask, paytable, table, &deck);
+        hold_evs.insert(hold_mask.to_string(), (ev * 1000000.0).round() / 1000000.0);
+        if ev > best_ev {
+            best_ev = ev;
+            best_hold = hold_mask;
+        }
+    }
+
+    (best_hold, (best_ev * 1000000.0).round() / 1000000.0, hold_evs)
+}
+
+// ============================================================================
+// CANONICAL HAND GENERATION
+// ============================================================================
+
+fn hand_to_canonical_key(hand: &Hand) -> String {
+    let mut sorted: Vec<Card> = hand.to_vec();
     sorted.sort_by_key(|c| c.rank());
+    hand_to_positional_key(&sorted)
+}
 
+/// Key for the cards in the order given: suits are relabelled by first appearance as in
+/// hand_to_canonical_key, but ranks are not sorted. Used where card position matters.
+fn hand_to_positional_key(cards: &[Card]) -> String {
     let mut suit_map: HashMap<u8, char> = HashMap::new();
     let suit_letters = ['a', 'b', 'c', 'd'];
     let mut next_suit = 0;
 
     let mut key = String::with_capacity(10);
-    for card in &sorted {
+    for card in cards {
         if card.is_joker() {
             key.push('W');
             key.push('w');
@@ -3589,6 +5128,87 @@ fn hand_to_canonical_key(hand: &Hand) -> String {
     key
 }
 
+/// Canonical hands for the paytable's deck (without multiplicity)
+fn canonical_hands_for_paytable(paytable: &Paytable) -> Vec<(String, Hand)> {
+    match &paytable.deck {
+        Some(deck) => generate_custom_deck_canonical_hands(deck, false)
+            .into_iter()
+            .map(|(key, hand, _)| (key, hand))
+            .collect(),
+        None => generate_canonical_hands(paytable.num_jokers()),
+    }
+}
+
+/// Canonical hands for the paytable's deck, with the number of dealt hands each represents
+fn canonical_hands_with_multiplicity_for_paytable(paytable: &Paytable) -> Vec<(String, Hand, u64)> {
+    match &paytable.deck {
+        Some(deck) => generate_custom_deck_canonical_hands(deck, false),
+        None => generate_canonical_hands_with_multiplicity(paytable.num_jokers(), false),
+    }
+}
+
+/// Suit permutations that leave a custom deck unchanged. Stripped, extra and wild ranks
+/// treat every suit alike, so only individually wild cards restrict the permutations.
+fn custom_deck_suit_permutations(deck: &DeckSpec) -> Vec<[u8; 4]> {
+    [0u8, 1, 2, 3].iter().cloned().permutations(4)
+        .map(|p| [p[0], p[1], p[2], p[3]])
+        .filter(|perm| deck.wild_cards.iter().all(|c| {
+            deck.wild_cards.contains(&Card(c.rank() * 4 + perm[c.suit() as usize]))
+        }))
+        .collect()
+}
+
+/// Canonical form of a hand from a custom deck: the smallest sorted card list over the
+/// deck's suit permutations. Keys spell the cards literally (suits h/d/c/s, extra suits
+/// x/y/z, jokers Ww) in card index order, and the returned hand is in the same order so
+/// hold bit i is the i-th card of the key.
+fn custom_deck_canonical(hand: &Hand, perms: &[[u8; 4]]) -> (String, Hand) {
+    let mut best: Option<Hand> = None;
+    for perm in perms {
+        let mut mapped: Hand = hand.map(|c| if c.0 < 52 { Card(c.rank() * 4 + perm[c.suit() as usize]) } else { c });
+        mapped.sort();
+        if best.is_none_or(|b| mapped < b) {
+            best = Some(mapped);
+        }
+    }
+    let best = best.unwrap_or(*hand);
+
+    let mut key = String::with_capacity(10);
+    for card in &best {
+        if card.is_joker() {
+            key.push_str("Ww");
+        } else {
+            key.push(card.rank_char());
+            key.push(['h', 'd', 'c', 's', 'x', 'y', 'z'][card.suit() as usize]);
+        }
+    }
+    (key, best)
+}
+
+/// Canonical hands of a custom deck with multiplicity; `quiet` skips the progress lines
+fn generate_custom_deck_canonical_hands(deck: &DeckSpec, quiet: bool) -> Vec<(String, Hand, u64)> {
+    if !quiet {
+        println!("  Generating canonical hands for custom deck...");
+    }
+    let cards = deck.cards();
+    let perms = custom_deck_suit_permutations(deck);
+    let mut seen: HashMap<String, (Hand, u64)> = HashMap::new();
+
+    for combo in cards.iter().combinations(5) {
+        let hand: Hand = [*combo[0], *combo[1], *combo[2], *combo[3], *combo[4]];
+        let (key, canonical) = custom_deck_canonical(&hand, &perms);
+        seen.entry(key)
+            .and_modify(|(_, count)| *count += 1)
+            .or_insert((canonical, 1));
+    }
+
+    let total_dealt: u64 = seen.values().map(|(_, c)| c).sum();
+    if !quiet {
+        println!("  {} canonical hands, {} total dealt hands ({}-card deck)", seen.len(), total_dealt, cards.len());
+    }
+    seen.into_iter().map(|(key, (hand, count))| (key, hand, count)).collect()
+}
+
 fn generate_canonical_hands(num_jokers: u8) -> Vec<(String, Hand)> {
     let include_str = if num_jokers > 0 { format!(" (with {} joker(s))", num_jokers) } else { String::new() };
     println!("Generating canonical hands{}...", include_str);
@@ -3891,9 +5511,10 @@ fn save_binary_strategy_v2(binary: &[u8], paytable_id: &str, output_dir: &str) -
 /// Returns (json_gz_bytes, binary_v1_bytes, binary_v2_bytes, hand_count, version)
 fn generate_strategy_file(paytable: &Paytable) -> (Vec<u8>, Vec<u8>, Vec<u8>, usize, u32) {
     let num_jokers = paytable.num_jokers();
-    let all_hands = generate_canonical_hands(num_jokers);
+    let all_hands = canonical_hands_for_paytable(paytable);
     let total = all_hands.len();
 
+    let table = handtable::hand_table(paytable);
     println!("\nCalculating {} hands using {} threads...", total, rayon::current_num_threads());
     io::stdout().flush().unwrap();
 
@@ -3906,7 +5527,7 @@ fn generate_strategy_file(paytable: &Paytable) -> (Vec<u8>, Vec<u8>, Vec<u8>, us
     let strategies: HashMap<String, StrategyEntry> = all_hands
         .par_iter()
         .map(|(key, hand)| {
-            let (best_hold, best_ev, hold_evs) = analyze_hand(hand, paytable);
+            let (best_hold, best_ev, hold_evs) = analyze_hand(hand, paytable, &table);
 
             let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
             // Print every 5% progress
@@ -4125,14 +5746,16 @@ fn update_manifest(
 
 fn make_hand(cards: [(u8, u8); 5]) -> Hand {
     // cards: [(rank, suit); 5] where rank: 0=2..12=A, suit: 0-3
-    // Special: rank 255 = joker
-    [
-        Card(if cards[0].0 == 255 { 52 } else { cards[0].0 * 4 + cards[0].1 }),
-        Card(if cards[1].0 == 255 { 52 } else { cards[1].0 * 4 + cards[1].1 }),
-        Card(if cards[2].0 == 255 { 52 } else { cards[2].0 * 4 + cards[2].1 }),
-        Card(if cards[3].0 == 255 { 52 } else { cards[3].0 * 4 + cards[3].1 }),
-        Card(if cards[4].0 == 255 { 52 } else { cards[4].0 * 4 + cards[4].1 }),
-    ]
+    // Special: rank 255 = joker, suit 4+ = extra card of a custom deck
+    cards.map(|(rank, suit)| {
+        if rank == 255 {
+            Card(52)
+        } else if suit >= 4 {
+            Card::extra(rank, suit - 4)
+        } else {
+            Card(rank * 4 + suit)
+        }
+    })
 }
 
 fn hand_to_string(hand: &Hand) -> String {
@@ -4140,7 +5763,7 @@ fn hand_to_string(hand: &Hand) -> String {
         if c.is_joker() {
             "JK".to_string()
         } else {
-            format!("{}{}", c.rank_char(), match c.suit() { 0 => 'h', 1 => 'd', 2 => 'c', _ => 's' })
+            format!("{}{}", c.rank_char(), match c.suit() { 0 => 'h', 1 => 'd', 2 => 'c', 3 => 's', _ => 'x' })
         }
     }).collect::<Vec<_>>().join(" ")
 }
@@ -4604,6 +6227,140 @@ fn run_tests(filter: Option<&str>) {
                 ("www-jacks-or-better-9-6-1w", 320.0),     // JoB Five 2s-4s = 320
             ],
         },
+        // ============= SHORT COIN =============
+        TestCase {
+            name: "Short coin: Royal Flush (As Ks Qs Js Ts)",
+            hand: make_hand([(12, 3), (11, 3), (10, 3), (9, 3), (8, 3)]),
+            tests: vec![
+                ("jacks-or-better-9-6-4coin", 250.0),  // 800 only at max coins
+                ("deuces-wild-nsud-1coin", 250.0),      // Natural royal drops too
+                ("joker-poker-kings-100-64-3coin", 250.0),
+            ],
+        },
+        TestCase {
+            name: "Short coin: Wild Royal (As Ks 2s Js Ts)",
+            hand: make_hand([(12, 3), (11, 3), (0, 3), (9, 3), (8, 3)]),
+            tests: vec![
+                ("deuces-wild-nsud-1coin", 25.0),       // Wild royal is not coin-dependent
+            ],
+        },
+
+        // ============= QUICK QUADS =============
+        TestCase {
+            name: "Quick Quads: 7-7-7 + 3-4 (7h 7d 7c 3s 4h)",
+            hand: make_hand([(5, 0), (5, 1), (5, 2), (1, 3), (2, 0)]),
+            tests: vec![
+                ("jacks-or-better-9-6-qq", 25.0),
+                ("jacks-or-better-9-6", 3.0),
+                ("double-double-bonus-9-6-qq", 50.0),
+            ],
+        },
+        TestCase {
+            name: "Quick Quads: full house 4-4-4 + 2-2 (4h 4d 4c 2s 2h)",
+            hand: make_hand([(2, 0), (2, 1), (2, 2), (0, 3), (0, 0)]),
+            tests: vec![
+                ("jacks-or-better-9-6-qq", 25.0),
+                ("double-double-bonus-9-6-qq", 80.0),  // No kicker, so not 160
+                ("double-bonus-10-7-qq", 80.0),
+            ],
+        },
+        TestCase {
+            name: "Quick Quads: Kings with 6 + 7 (Kh Kd Kc 6s 7h)",
+            hand: make_hand([(11, 0), (11, 1), (11, 2), (4, 3), (5, 0)]),
+            tests: vec![
+                ("bonus-poker-8-5-qq", 25.0),
+            ],
+        },
+        TestCase {
+            name: "Quick Quads: Fives with A-4 (5h 5d 5c As 4h)",
+            hand: make_hand([(3, 0), (3, 1), (3, 2), (12, 3), (2, 0)]),
+            tests: vec![
+                ("jacks-or-better-9-6-qq", 25.0),
+            ],
+        },
+        TestCase {
+            name: "Quick Quads: sum misses (7h 7d 7c 3s 5h)",
+            hand: make_hand([(5, 0), (5, 1), (5, 2), (1, 3), (3, 0)]),
+            tests: vec![
+                ("jacks-or-better-9-6-qq", 3.0),
+            ],
+        },
+
+        // ============= BUG JOKER =============
+        TestCase {
+            name: "Bug joker: Five Aces (Ah Ad Ac As Jkr)",
+            hand: make_hand([(12, 0), (12, 1), (12, 2), (12, 3), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 200.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: Four 3s (3h 3d 3c 3s Jkr)",
+            hand: make_hand([(1, 0), (1, 1), (1, 2), (1, 3), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 17.0),  // Not five of a kind
+                ("joker-poker-kings-100-64", 200.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: Trip Kings (Kh Kd Kc Jkr 5h)",
+            hand: make_hand([(11, 0), (11, 1), (11, 2), (255, 0), (3, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 2.0),   // Joker is an ace
+                ("joker-poker-kings-100-64", 17.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: Aces Full (Ah Ad Ks Kc Jkr)",
+            hand: make_hand([(12, 0), (12, 1), (11, 3), (11, 2), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 7.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: Kings Up (Kh Kd 5c 5s Jkr)",
+            hand: make_hand([(11, 0), (11, 1), (3, 2), (3, 3), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 1.0),   // No full house
+                ("joker-poker-kings-100-64", 7.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: Wild Royal (Ks Qs Js Ts Jkr)",
+            hand: make_hand([(11, 3), (10, 3), (9, 3), (8, 3), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 100.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: Straight (9h 8d 7c 6s Jkr)",
+            hand: make_hand([(7, 0), (6, 1), (5, 2), (4, 3), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 3.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: Flush (2h 5h 9h Jh Jkr)",
+            hand: make_hand([(0, 0), (3, 0), (7, 0), (9, 0), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 5.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: Pair of Aces (Ah Kd 7c 5s Jkr)",
+            hand: make_hand([(12, 0), (11, 1), (5, 2), (3, 3), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 1.0),
+            ],
+        },
+        TestCase {
+            name: "Bug joker: King high (Kh 9d 7c 4s Jkr)",
+            hand: make_hand([(11, 0), (7, 1), (5, 2), (2, 3), (255, 0)]),
+            tests: vec![
+                ("joker-poker-kings-100-64-bug", 0.0),   // Can't pair the king
+                ("joker-poker-kings-100-64", 1.0),
+            ],
+        },
     ];
 
     for tc in test_cases.iter() {
@@ -4616,16 +6373,18 @@ fn run_tests(filter: Option<&str>) {
             }
 
             if let Some(paytable) = get_paytable(paytable_id) {
-                let actual = get_payout(&tc.hand, &paytable);
+                let hand_type = evaluate_hand(&tc.hand, &paytable);
+                let actual = paytable.payout(hand_type);
                 let pass = (actual - expected).abs() < 0.001;
 
                 if pass {
                     passed += 1;
-                    println!("✓ {} [{}]: {} = {}", tc.name, paytable_id, hand_to_string(&tc.hand), actual);
+                    println!("✓ {} [{}]: {} = {} ({})",
+                        tc.name, paytable_id, hand_to_string(&tc.hand), actual, hand_type.name());
                 } else {
                     failed += 1;
-                    println!("✗ {} [{}]: {} = {} (expected {})",
-                        tc.name, paytable_id, hand_to_string(&tc.hand), actual, expected);
+                    println!("✗ {} [{}]: {} = {} ({}, expected {})",
+                        tc.name, paytable_id, hand_to_string(&tc.hand), actual, hand_type.name(), expected);
                 }
             } else {
                 println!("? Paytable not found: {}", paytable_id);
@@ -4633,90 +6392,446 @@ fn run_tests(filter: Option<&str>) {
         }
     }
 
-    println!("\n=== Results: {} passed, {} failed ===", passed, failed);
-
-    if failed > 0 {
-        std::process::exit(1);
-    }
-}
+    // ============= OVERLAYS =============
+    // A made-up distribution: half lose, half pay 2 (return 100%, variance 1 per line).
+    // (description, overlay, shared variance, expected return %, expected std dev)
+    let overlay_distribution: HashMap<String, (f64, f64, usize)> = [
+        ("Loss", (0.5, 0.0, 0)),
+        ("Pays 2", (0.5, 2.0, 1)),
+    ].into_iter().map(|(name, entry)| (name.to_string(), entry)).collect();
+    let spin_poker = overlay::builtin_overlays().into_iter().find(|o| o.id == "spin-poker").unwrap();
+    let overlay_cases: Vec<(&str, overlay::OverlayDef, f64, f64, f64)> = vec![
+        ("One line", overlay::OverlayDef { lines: 1, ..spin_poker.clone() }, 0.25, 100.0, 1.0),
+        // Nine lines: (1 - 0.25) / 9 + 0.25
+        ("Nine lines, a quarter of the variance from the deal", spin_poker.clone(), 0.25, 100.0, (0.75f64 / 9.0 + 0.25).sqrt()),
+        ("Nine lines, every deal the same", spin_poker.clone(), 0.0, 100.0, (1.0f64 / 9.0).sqrt()),
+        ("Nine lines, the draw decides nothing", spin_poker, 1.0, 100.0, 1.0),
+    ];
 
-fn format_duration(secs: u64) -> String {
-    if secs < 60 {
-        format!("{}s", secs)
-    } else if secs < 3600 {
-        format!("{}m {}s", secs / 60, secs % 60)
-    } else {
-        format!("{}h {}m {}s", secs / 3600, (secs % 3600) / 60, secs % 60)
+    for (name, overlay, shared, expected_return, expected_std_dev) in &overlay_cases {
+        if let Some(f) = filter {
+            if !"overlay".contains(f) {
+                continue;
+            }
+        }
+        let result = overlay::evaluate_overlay(&overlay_distribution, overlay, *shared);
+        let return_pct = result.net_return * 100.0;
+        if (return_pct - expected_return).abs() < 1e-9 && (result.std_dev - expected_std_dev).abs() < 1e-9 {
+            passed += 1;
+            println!("✓ Overlay {}: return {:.4}%, std dev {:.4}", name, return_pct, result.std_dev);
+        } else {
+            failed += 1;
+            println!("✗ Overlay {}: return {:.4}%, std dev {:.4} (expected {:.4}%, {:.4})",
+                name, return_pct, result.std_dev, expected_return, expected_std_dev);
+        }
     }
-}
-
-fn generate_all_strategies(output_dir: &str) {
-    let all_ids = get_all_paytable_ids();
-    let total_paytables = all_ids.len();
 
-    // iOS Resources directory for checking bundled files
-    let ios_resources_dir = "../../ios-native/VideoPokerTrainer/VideoPokerTrainer/Resources";
+    // ============= HAND TYPES =============
+    // Bonus tiers and pair minimums: (description, paytable, hand, expected hand type)
+    let hand_type_cases: Vec<(&str, &str, Hand, HandType)> = vec![
+        ("DDB: Four Aces w/ 3 kicker (Ah Ad Ac As 3h)", "double-double-bonus-9-6",
+            make_hand([(12, 0), (12, 1), (12, 2), (12, 3), (1, 0)]), HandType::FourAcesWith2To4),
+        ("DDB: Four Aces w/ King (Ah Ad Ac As Kh)", "double-double-bonus-9-6",
+            make_hand([(12, 0), (12, 1), (12, 2), (12, 3), (11, 0)]), HandType::FourAces),
+        ("DDB: Four 3s w/ Ace (3h 3d 3c 3s Ah)", "double-double-bonus-9-6",
+            make_hand([(1, 0), (1, 1), (1, 2), (1, 3), (12, 0)]), HandType::Four2To4WithAceTo4),
+        ("Double Jackpot: Four Kings w/ Ace (Kh Kd Kc Ks Ah)", "double-jackpot-8-5",
+            make_hand([(11, 0), (11, 1), (11, 2), (11, 3), (12, 0)]), HandType::FourJToKWithFace),
+        ("Double Jackpot: Four Kings w/ 5 (Kh Kd Kc Ks 5h)", "double-jackpot-8-5",
+            make_hand([(11, 0), (11, 1), (11, 2), (11, 3), (3, 0)]), HandType::FourJToK),
+        ("Aces and Eights: Four 8s (8h 8d 8c 8s Kh)", "aces-and-eights-8-5",
+            make_hand([(6, 0), (6, 1), (6, 2), (6, 3), (11, 0)]), HandType::Four8s),
+        ("Quick Quads: 7-7-7 + 3-4 (7h 7d 7c 3s 4h)", "double-double-bonus-9-6-qq",
+            make_hand([(5, 0), (5, 1), (5, 2), (1, 3), (2, 0)]), HandType::Four5ToK),
+        ("Tens or Better: Pair of Tens (Th Td 4c 6s Kh)", "tens-or-better-6-5",
+            make_hand([(8, 0), (8, 1), (2, 2), (4, 3), (11, 0)]), HandType::TensOrBetter),
+        ("Triple Bonus: Pair of Kings (Kh Kd 4c 6s 9h)", "triple-bonus-9-5",
+            make_hand([(11, 0), (11, 1), (2, 2), (4, 3), (7, 0)]), HandType::KingsOrBetter),
+        ("Deuces: Natural Royal (As Ks Qs Js Ts)", "deuces-wild-nsud",
+            make_hand([(12, 3), (11, 3), (10, 3), (9, 3), (8, 3)]), HandType::NaturalRoyalFlush),
+        ("Deuces: Four Deuces (2h 2d 2c 2s 7h)", "deuces-wild-nsud",
+            make_hand([(0, 0), (0, 1), (0, 2), (0, 3), (5, 0)]), HandType::FourDeuces),
+        ("Joker: Five Kings (Kh Kd Kc Ks Jkr)", "joker-poker-kings-100-64",
+            make_hand([(11, 0), (11, 1), (11, 2), (11, 3), (255, 0)]), HandType::FiveOfAKind),
+        ("WWW: Five Aces (Ah Ad Ac As Jkr)", "www-jacks-or-better-9-6-1w",
+            make_hand([(12, 0), (12, 1), (12, 2), (12, 3), (255, 0)]), HandType::FiveAces),
+        ("WWW: Five 3s (3h 3d 3c 3s Jkr)", "www-jacks-or-better-9-6-1w",
+            make_hand([(1, 0), (1, 1), (1, 2), (1, 3), (255, 0)]), HandType::Five2To4),
+        ("WWW: Five Kings (Kh Kd Kc Ks Jkr)", "www-super-double-bonus-9-5-1w",
+            make_hand([(11, 0), (11, 1), (11, 2), (11, 3), (255, 0)]), HandType::FiveJToK),
+        ("WWW: Five 7s (7h 7d 7c 7s Jkr)", "www-super-double-bonus-9-5-1w",
+            make_hand([(5, 0), (5, 1), (5, 2), (5, 3), (255, 0)]), HandType::Five5To10),
+        ("WWW: Five 4s on a deuces base (4h 4d 4c 2s Jkr)", "www-deuces-wild-bonus-9-4-1w",
+            make_hand([(2, 0), (2, 1), (2, 2), (0, 3), (255, 0)]), HandType::Five3To5),
+        ("WWW: Five Deuces (2h 2d 2c 2s Jkr)", "www-deuces-wild-nsud-1w",
+            make_hand([(0, 0), (0, 1), (0, 2), (0, 3), (255, 0)]), HandType::FiveDeuces),
+    ];
 
-    // Ensure output directory exists
-    if let Err(e) = fs::create_dir_all(output_dir) {
-        eprintln!("Failed to create output directory: {}", e);
-        std::process::exit(1);
+    for (name, paytable_id, hand, expected) in &hand_type_cases {
+        if let Some(f) = filter {
+            if !paytable_id.contains(f) {
+                continue;
+            }
+        }
+        let Some(paytable) = get_paytable(paytable_id) else {
+            println!("? Paytable not found: {}", paytable_id);
+            continue;
+        };
+        let actual = evaluate_hand(hand, &paytable);
+        if actual == *expected {
+            passed += 1;
+            println!("✓ {} [{}]: {} = {} ({})",
+                name, paytable_id, hand_to_string(hand), actual.name(), paytable.payout(actual));
+        } else {
+            failed += 1;
+            println!("✗ {} [{}]: {} = {} (expected {})",
+                name, paytable_id, hand_to_string(hand), actual.name(), expected.name());
+        }
     }
 
-    // Check which paytables already have .vpstrat2 files (in output dir or iOS bundle)
-    let mut already_done: Vec<&str> = Vec::new();
-    let mut to_process: Vec<&str> = Vec::new();
-
-    for id in &all_ids {
-        let v2_filename = format!("strategy_{}.vpstrat2", id.replace("-", "_"));
-        let output_path = Path::new(output_dir).join(&v2_filename);
-        let ios_path = Path::new(ios_resources_dir).join(&v2_filename);
+    // ============= CUSTOM DECKS =============
+    // Catalog paytables dealt from a deck_presets() deck: (description, paytable id, hand, expected)
+    let deck_cases: Vec<(&str, &str, Hand, f64)> = vec![
+        ("Sevens wild: Four Sevens (7h 7d 7c 7s Ah)", "deuces-wild-full-pay-sevens-wild",
+            make_hand([(5, 0), (5, 1), (5, 2), (5, 3), (12, 0)]), 200.0),
+        ("Sevens wild: Wild Royal (As Ks 7s Js Ts)", "deuces-wild-full-pay-sevens-wild",
+            make_hand([(12, 3), (11, 3), (5, 3), (9, 3), (8, 3)]), 25.0),
+        ("Sevens wild: deuces are natural (2h 2d 2c 2s 7h)", "deuces-wild-full-pay-sevens-wild",
+            make_hand([(0, 0), (0, 1), (0, 2), (0, 3), (5, 0)]), 15.0),
+        ("Sevens wild: Natural Straight Flush (2h 3h 4h 5h 6h)", "deuces-wild-full-pay-sevens-wild",
+            make_hand([(0, 0), (1, 0), (2, 0), (3, 0), (4, 0)]), 9.0),
+        ("One-eyed jacks: Jh wild pairs the King (Jh Jd Kc 5s 3h)", "jacks-or-better-9-6-one-eyed-jacks",
+            make_hand([(9, 0), (9, 1), (11, 2), (3, 3), (1, 0)]), 1.0),
+        ("One-eyed jacks: two wilds make Four Aces (Jh Js Ad Ac 4s)", "jacks-or-better-9-6-one-eyed-jacks",
+            make_hand([(9, 0), (9, 3), (12, 1), (12, 2), (2, 3)]), 25.0),
+        ("One-eyed jacks: Jd is natural (Jd Jc 5h 8s Kh)", "jacks-or-better-9-6-one-eyed-jacks",
+            make_hand([(9, 1), (9, 2), (3, 0), (6, 3), (11, 0)]), 1.0),
+        ("Five Aces: an ace is no kicker for four aces (Ah Ad Ac As Ax)", "double-double-bonus-9-6-five-aces",
+            make_hand([(12, 0), (12, 1), (12, 2), (12, 3), (12, 4)]), 160.0),
+        ("Five Aces: four aces with a 3 kicker (Ah Ad Ac Ax 3s)", "double-double-bonus-9-6-five-aces",
+            make_hand([(12, 0), (12, 1), (12, 2), (12, 4), (1, 3)]), 400.0),
+        ("Five Aces: four 3s take the extra ace as kicker (3h 3d 3c 3s Ax)", "double-double-bonus-9-6-five-aces",
+            make_hand([(1, 0), (1, 1), (1, 2), (1, 3), (12, 4)]), 160.0),
+        ("Five Aces: extra ace breaks a flush (Ah Kh Qh 9h Ax)", "double-double-bonus-9-6-five-aces",
+            make_hand([(12, 0), (11, 0), (10, 0), (7, 0), (12, 4)]), 1.0),
+        ("Stripped 2-5: Straight (6h 7d 8c 9s Th)", "jacks-or-better-9-6-stripped-2-5",
+            make_hand([(4, 0), (5, 1), (6, 2), (7, 3), (8, 0)]), 4.0),
+    ];
 
-        if output_path.exists() || ios_path.exists() {
-            already_done.push(id);
+    for (name, paytable_id, hand, expected) in &deck_cases {
+        if let Some(f) = filter {
+            if !paytable_id.contains(f) && !"custom-deck".contains(f) {
+                continue;
+            }
+        }
+        let Some(paytable) = get_paytable(paytable_id) else {
+            failed += 1;
+            println!("✗ {}: paytable {} not found", name, paytable_id);
+            continue;
+        };
+        let actual = get_payout(hand, &paytable);
+        if (actual - expected).abs() < 0.001 {
+            passed += 1;
+            println!("✓ {} [{}]: {} = {}", name, paytable_id, hand_to_string(hand), actual);
         } else {
-            to_process.push(id);
+            failed += 1;
+            println!("✗ {} [{}]: {} = {} (expected {})", name, paytable_id, hand_to_string(hand), actual, expected);
         }
     }
 
-    let previously_completed = already_done.len();
-    let remaining = to_process.len();
-
-    println!("╔══════════════════════════════════════════════════════════════════╗");
-    println!("║          VIDEO POKER STRATEGY GENERATOR - BATCH MODE            ║");
-    println!("╠══════════════════════════════════════════════════════════════════╣");
-    println!("║  Total paytables: {:<47} ║", total_paytables);
-    println!("║  Already completed: {:<45} ║", previously_completed);
-    println!("║  To process this run: {:<43} ║", remaining);
-    println!("║  Output directory: {:<46} ║", output_dir);
-    println!("║  Threads: {:<55} ║", rayon::current_num_threads());
-    println!("╚══════════════════════════════════════════════════════════════════╝");
-    println!();
+    // ============= PICK'EM =============
+    // Final five-card hands; Pick'em paytables live in pickem.rs
+    let pickem_cases: Vec<(&str, &str, Hand, f64)> = vec![
+        ("Pick'em: Pair of Nines (9h 9d 4c 6s Kh)", "pickem-full-pay",
+            make_hand([(7, 0), (7, 1), (2, 2), (4, 3), (11, 0)]), 2.0),
+        ("Pick'em: Pair of Eights pays nothing (8h 8d 4c 6s Kh)", "pickem-full-pay",
+            make_hand([(6, 0), (6, 1), (2, 2), (4, 3), (11, 0)]), 0.0),
+        ("Pick'em: Straight (5h 6d 7c 8s 9h)", "pickem-full-pay",
+            make_hand([(3, 0), (4, 1), (5, 2), (6, 3), (7, 0)]), 11.0),
+        ("Pick'em: Royal Flush (As Ks Qs Js Ts)", "pickem-full-pay",
+            make_hand([(12, 3), (11, 3), (10, 3), (9, 3), (8, 3)]), 1200.0),
+    ];
 
-    if remaining == 0 {
-        println!("All paytables already generated! Nothing to do.");
-        return;
+    for (name, paytable_id, hand, expected) in &pickem_cases {
+        if let Some(f) = filter {
+            if !paytable_id.contains(f) {
+                continue;
+            }
+        }
+        let Some(paytable) = pickem::get_pickem_paytable(paytable_id) else {
+            println!("? Paytable not found: {}", paytable_id);
+            continue;
+        };
+        let actual = get_payout(hand, &paytable);
+        if (actual - expected).abs() < 0.001 {
+            passed += 1;
+            println!("✓ {} [{}]: {} = {}", name, paytable_id, hand_to_string(hand), actual);
+        } else {
+            failed += 1;
+            println!("✗ {} [{}]: {} = {} (expected {})", name, paytable_id, hand_to_string(hand), actual, expected);
+        }
     }
 
-    let overall_start = Instant::now();
-    let mut completed_this_run = 0;
-    let mut total_hands = 0usize;
-    let mut total_bytes = 0u64;
-    let mut failed_paytables: Vec<String> = Vec::new();
-
-    // Track timing for ETA calculation
-    let mut paytable_times: Vec<f64> = Vec::new();
+    // ============= SEQUENTIAL ROYAL =============
+    // Cards are in dealt order. With a hold mask the expected value is the EV the
+    // sequential bonus adds to that hold; without one it is the payout.
+    let sequential_cases: Vec<(&str, &str, Hand, Option<u8>, f64)> = vec![
+        ("Sequential Royal (Th Jh Qh Kh Ah)", "jacks-or-better-9-6-seq4000",
+            make_hand([(8, 0), (9, 0), (10, 0), (11, 0), (12, 0)]), None, 4000.0),
+        ("Reversed royal is not sequential (Ah Kh Qh Jh Th)", "jacks-or-better-9-6-seq4000",
+            make_hand([(12, 0), (11, 0), (10, 0), (9, 0), (8, 0)]), None, 800.0),
+        ("Out of order royal (Jh Th Qh Kh Ah)", "jacks-or-better-9-6-seq4000",
+            make_hand([(9, 0), (8, 0), (10, 0), (11, 0), (12, 0)]), None, 800.0),
+        ("Wild royal in order is not sequential (Th Jh Qh Kh 2h)", "deuces-wild-nsud-seq4000",
+            make_hand([(8, 0), (9, 0), (10, 0), (11, 0), (0, 0)]), None, 25.0),
+        ("Bonus EV: hold Th Jh Qh Kh in place, draw Ah to the end", "jacks-or-better-9-6-seq4000",
+            make_hand([(8, 0), (9, 0), (10, 0), (11, 0), (7, 2)]), Some(0b01111), 3200.0 / 47.0),
+        ("Bonus EV: Jh Th out of place (Jh Th Qh Kh 9c)", "jacks-or-better-9-6-seq4000",
+            make_hand([(9, 0), (8, 0), (10, 0), (11, 0), (7, 2)]), Some(0b01111), 0.0),
+        ("Bonus EV: Th Jh in place, Qh discarded (Th Jh Qh 5c 6d)", "jacks-or-better-9-6-seq4000",
+            make_hand([(8, 0), (9, 0), (10, 0), (3, 2), (4, 1)]), Some(0b00011), 0.0),
+        ("Bonus EV: stand on a sequential royal", "jacks-or-better-9-6-seq4000",
+            make_hand([(8, 0), (9, 0), (10, 0), (11, 0), (12, 0)]), Some(0b11111), 3200.0),
+    ];
 
-    for (idx, paytable_id) in to_process.iter().enumerate() {
-        let paytable = match get_paytable(paytable_id) {
-            Some(pt) => pt,
-            None => {
-                println!("  ⚠ Paytable not found: {}", paytable_id);
-                failed_paytables.push(paytable_id.to_string());
+    for (name, paytable_id, hand, hold, expected) in &sequential_cases {
+        if let Some(f) = filter {
+            if !paytable_id.contains(f) && !"sequential".contains(f) {
                 continue;
             }
+        }
+        let Some(paytable) = get_paytable(paytable_id) else {
+            println!("? Paytable not found: {}", paytable_id);
+            continue;
         };
-
+        let actual = match hold {
+            Some(mask) => {
+                let deck = paytable.deck_cards();
+                let table = handtable::hand_table(&paytable);
+                calculate_positional_hold_ev(hand, *mask, &paytable, &table, &deck)
+                    - calculate_hold_ev(hand, *mask, &paytable, &table, &deck)
+            }
+            None => get_positional_payout(hand, &paytable),
+        };
+        if (actual - expected).abs() < 0.001 {
+            passed += 1;
+            println!("✓ {} [{}]: {} = {}", name, paytable_id, hand_to_string(hand), actual);
+        } else {
+            failed += 1;
+            println!("✗ {} [{}]: {} = {} (expected {})", name, paytable_id, hand_to_string(hand), actual, expected);
+        }
+    }
+
+    // ============= DOUBLE-UP =============
+    // A made-up distribution: 50% losers, 40% pay 1, 10% pay 2 (base return 60%).
+    // (description, policy, bet cost, expected return %, expected variance)
+    let double_up_distribution: HashMap<String, (f64, f64, usize)> = [
+        ("Loss", (0.5, 0.0, 0)),
+        ("Pays 1", (0.4, 1.0, 1)),
+        ("Pays 2", (0.1, 2.0, 2)),
+    ].into_iter().map(|(name, entry)| (name.to_string(), entry)).collect();
+    let double_up_cases: Vec<(&str, doubleup::DoubleUpPolicy, f64, f64, f64)> = vec![
+        // E[X^2] = 0.4 * 1 + 0.1 * 4
+        ("Never doubles", doubleup::DoubleUpPolicy::Never, 1.0, 60.0, 0.8 - 0.36),
+        // Pays 1 doubles once to 2 (p 0.2); Pays 2 stands. E[X^2] = 0.2 * 4 + 0.1 * 4
+        ("threshold:2 doubles only the 1s", doubleup::DoubleUpPolicy::Threshold(2.0), 1.0, 60.0, 1.2 - 0.36),
+        // Five doubles: 32 with p 0.4/32, 64 with p 0.1/32. E[X^2] = 12.8 + 12.8
+        ("Always doubles, five per win", doubleup::DoubleUpPolicy::Always, 1.0, 60.0, 25.6 - 0.36),
+        // Six coins for a five-coin game: 0.6 / 1.2, E[X^2] = 25.6 / 1.44
+        ("Always at a 1.2 bet cost", doubleup::DoubleUpPolicy::Always, 1.2, 50.0, 25.6 / 1.44 - 0.25),
+    ];
+
+    for (name, policy, bet_cost, expected_return, expected_variance) in &double_up_cases {
+        if let Some(f) = filter {
+            if !"double-up".contains(f) {
+                continue;
+            }
+        }
+        let result = doubleup::evaluate_double_up(&double_up_distribution, *policy, &doubleup::DoubleUpRules::default(), *bet_cost);
+        if (result.return_pct - expected_return).abs() < 1e-9 && (result.variance - expected_variance).abs() < 1e-9 {
+            passed += 1;
+            println!("✓ Double-up {} [{}]: return {:.4}%, variance {:.4}", name, policy.label(), result.return_pct, result.variance);
+        } else {
+            failed += 1;
+            println!("✗ Double-up {} [{}]: return {:.4}%, variance {:.4} (expected {:.4}%, {:.4})",
+                name, policy.label(), result.return_pct, result.variance, expected_return, expected_variance);
+        }
+    }
+
+    // ============= RISK OF RUIN =============
+    // (description, return rate, variance per hand, bankroll, hands, expected chance)
+    let ruin_cases: Vec<(&str, f64, f64, f64, u64, f64)> = vec![
+        // No variance: the bankroll goes exactly when the steady loss reaches it
+        ("No variance, loss reaches the bankroll", 0.99, 0.0, 10.0, 1000, 1.0),
+        ("No variance, loss stops short", 0.99, 0.0, 10.0, 999, 0.0),
+        // No drift: reflection principle, 2 * Phi(-b / (sigma * sqrt(n))) = 2 * Phi(-1)
+        ("No drift, bankroll one sigma of the session", 1.0, 1.0, 10.0, 100, 0.317311),
+        // Phi(-0.9) + e^0.2 * Phi(-1.1)
+        ("1% house edge, 100 hands", 0.99, 1.0, 10.0, 100, 0.349763),
+        // Long sessions with an edge tend to e^(-2 * drift * b / variance) = e^-0.2
+        ("1% player edge, endless session", 1.01, 1.0, 10.0, 1_000_000_000, 0.818731),
+    ];
+
+    for (name, return_rate, variance, bankroll, hands, expected) in &ruin_cases {
+        if let Some(f) = filter {
+            if !"risk-of-ruin".contains(f) {
+                continue;
+            }
+        }
+        let actual = risk_of_ruin(*return_rate, *variance, *bankroll, *hands);
+        if (actual - expected).abs() < 1e-6 {
+            passed += 1;
+            println!("✓ Risk of ruin: {} = {:.6}", name, actual);
+        } else {
+            failed += 1;
+            println!("✗ Risk of ruin: {} = {:.6} (expected {:.6})", name, actual, expected);
+        }
+    }
+
+    // ============= IDENTIFY =============
+    // (description, request, best candidate, uncatalogued, first generated (base, field, value))
+    type Override<'a> = (&'a str, &'a str, f64);
+    let identify_cases: Vec<(&str, &str, &str, bool, Option<Override>)> = vec![
+        ("Full 9/6 Jacks or Better pay table",
+            r#"{"observations": [{"hand": "Royal Flush", "pay": 800}, {"hand": "Straight Flush", "pay": 50},
+                {"hand": "Four of a Kind", "pay": 25}, {"hand": "Full House", "pay": 9}, {"hand": "Flush", "pay": 6},
+                {"hand": "Straight", "pay": 4}, {"hand": "Three of a Kind", "pay": 3}, {"hand": "Two Pair", "pay": 2},
+                {"hand": "Jacks or Better", "pay": 1}]}"#,
+            "jacks-or-better-9-6", false, None),
+        ("9/6 with a straight paying 5 is not in the catalog",
+            r#"{"observations": [{"hand": "Full House", "pay": 9}, {"hand": "Flush", "pay": 6},
+                {"hand": "Straight", "pay": 5}, {"hand": "Jacks or Better", "pay": 1}], "family": "JacksOrBetter"}"#,
+            "jacks-or-better-9-6", true, Some(("jacks-or-better-9-6", "straight", 5.0))),
+        ("Pays read from the 5-coin column",
+            r#"{"observations": [{"hand": "Royal Flush", "pay": 4000}, {"hand": "Four of a Kind", "pay": 125},
+                {"hand": "Full House", "pay": 45}, {"hand": "Flush", "pay": 30}], "coins": 5, "family": "JacksOrBetter"}"#,
+            "jacks-or-better-9-6", false, None),
+    ];
+
+    for (name, request, best, uncatalogued, generated) in &identify_cases {
+        if let Some(f) = filter {
+            if !"identify".contains(f) {
+                continue;
+            }
+        }
+        // No strategy files: candidates come back without returns
+        let result = serde_json::from_str::<identify::IdentifyRequest>(request)
+            .map_err(|e| e.to_string())
+            .and_then(|r| identify::identify(&r, "/nonexistent"));
+        let response = match result {
+            Ok(response) => response,
+            Err(e) => {
+                failed += 1;
+                println!("✗ Identify: {}: {}", name, e);
+                continue;
+            }
+        };
+        let actual_best = response.candidates.first().map_or("none", |c| c.paytable_id.as_str());
+        let actual_generated = response.generated.first().and_then(|g| {
+            g.overrides.first().map(|o| (g.base_paytable_id.as_str(), o.field.as_str(), o.value))
+        });
+        if actual_best == *best && response.uncatalogued == *uncatalogued && actual_generated == *generated {
+            passed += 1;
+            println!("✓ Identify: {} = {} (uncatalogued: {})", name, actual_best, response.uncatalogued);
+        } else {
+            failed += 1;
+            println!("✗ Identify: {} = {} (uncatalogued: {}, generated {:?}; expected {}, {}, {:?})",
+                name, actual_best, response.uncatalogued, actual_generated, best, uncatalogued, generated);
+        }
+    }
+
+    println!("\n=== Results: {} passed, {} failed ===", passed, failed);
+
+    if failed > 0 {
+        std::process::exit(1);
+    }
+}
+
+fn format_duration(secs: u64) -> String {
+    if secs < 60 {
+        format!("{}s", secs)
+    } else if secs < 3600 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h {}m {}s", secs / 3600, (secs % 3600) / 60, secs % 60)
+    }
+}
+
+fn generate_all_strategies(output_dir: &str, coins: u8) {
+    let all_ids: Vec<String> = get_all_paytable_ids()
+        .into_iter()
+        .filter_map(|id| coin_paytable_id(id, coins))
+        .collect();
+    let total_paytables = all_ids.len();
+
+    // iOS Resources directory for checking bundled files
+    let ios_resources_dir = "../../ios-native/VideoPokerTrainer/VideoPokerTrainer/Resources";
+
+    // Ensure output directory exists
+    if let Err(e) = fs::create_dir_all(output_dir) {
+        eprintln!("Failed to create output directory: {}", e);
+        std::process::exit(1);
+    }
+
+    // Check which paytables already have .vpstrat2 files (in output dir or iOS bundle)
+    let mut already_done: Vec<&str> = Vec::new();
+    let mut to_process: Vec<&str> = Vec::new();
+
+    for id in &all_ids {
+        let v2_filename = format!("strategy_{}.vpstrat2", id.replace("-", "_"));
+        let output_path = Path::new(output_dir).join(&v2_filename);
+        let ios_path = Path::new(ios_resources_dir).join(&v2_filename);
+
+        if output_path.exists() || ios_path.exists() {
+            already_done.push(id);
+        } else {
+            to_process.push(id);
+        }
+    }
+
+    let previously_completed = already_done.len();
+    let remaining = to_process.len();
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          VIDEO POKER STRATEGY GENERATOR - BATCH MODE            ║");
+    println!("╠══════════════════════════════════════════════════════════════════╣");
+    println!("║  Total paytables: {:<47} ║", total_paytables);
+    println!("║  Already completed: {:<45} ║", previously_completed);
+    println!("║  To process this run: {:<43} ║", remaining);
+    println!("║  Output directory: {:<46} ║", output_dir);
+    println!("║  Threads: {:<55} ║", rayon::current_num_threads());
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+
+    if remaining == 0 {
+        println!("All paytables already generated! Nothing to do.");
+        return;
+    }
+
+    let overall_start = Instant::now();
+    let mut completed_this_run = 0;
+    let mut total_hands = 0usize;
+    let mut total_bytes = 0u64;
+    let mut failed_paytables: Vec<String> = Vec::new();
+
+    // Track timing for ETA calculation
+    let mut paytable_times: Vec<f64> = Vec::new();
+
+    for (idx, paytable_id) in to_process.iter().enumerate() {
+        let paytable = match get_paytable(paytable_id) {
+            Some(pt) => pt,
+            None => {
+                println!("  ⚠ Paytable not found: {}", paytable_id);
+                failed_paytables.push(paytable_id.to_string());
+                continue;
+            }
+        };
+        if let Err(e) = paytable.validate() {
+            println!("  ⚠ Invalid paytable: {}", e);
+            failed_paytables.push(paytable_id.to_string());
+            continue;
+        }
+
         let overall_done = previously_completed + completed_this_run;
 
         // Calculate ETA based on average time per paytable
@@ -4758,7 +6873,7 @@ fn generate_all_strategies(output_dir: &str) {
                 total_hands += hand_count;
                 total_bytes += binary_v2_size;
                 println!("  ✓ Saved: {} ({:.2} MB) in {:.1}s",
-                    binary_v2_path.split('/').last().unwrap_or(&binary_v2_path),
+                    binary_v2_path.split('/').next_back().unwrap_or(&binary_v2_path),
                     binary_v2_size as f64 / 1024.0 / 1024.0,
                     elapsed
                 );
@@ -4882,18 +6997,17 @@ fn upload_existing_strategies(input_dir: &str) {
         }
 
         // Parse the file to get hand count for manifest
-        let hand_count = match flate2::read::GzDecoder::new(&compressed[..]) {
-            decoder => {
-                let mut json_str = String::new();
-                if std::io::Read::read_to_string(&mut std::io::BufReader::new(decoder), &mut json_str).is_ok() {
-                    if let Ok(strategy_file) = serde_json::from_str::<StrategyFile>(&json_str) {
-                        strategy_file.hand_count
-                    } else {
-                        0
-                    }
+        let hand_count = {
+            let decoder = flate2::read::GzDecoder::new(&compressed[..]);
+            let mut json_str = String::new();
+            if std::io::Read::read_to_string(&mut std::io::BufReader::new(decoder), &mut json_str).is_ok() {
+                if let Ok(strategy_file) = serde_json::from_str::<StrategyFile>(&json_str) {
+                    strategy_file.hand_count
                 } else {
                     0
                 }
+            } else {
+                0
             }
         };
 
@@ -4959,7 +7073,7 @@ fn convert_json_to_binary(input_dir: &str) {
         io::stdout().flush().unwrap();
 
         // Read and decompress
-        let gz_data = match fs::read(&path) {
+        let gz_data = match fs::read(path) {
             Ok(data) => data,
             Err(e) => {
                 println!("✗ Failed to read: {}", e);
@@ -4986,17 +7100,14 @@ fn convert_json_to_binary(input_dir: &str) {
             }
         };
 
-        // Detect joker count from paytable ID
-        let num_jokers: u8 = if strategy_file.paytable_id.starts_with("www-") {
-            // Extract wild count from ID: "www-{base}-{N}w"
-            strategy_file.paytable_id.rsplit('-').next()
-                .and_then(|s| s.strip_suffix('w'))
-                .and_then(|s| s.parse::<u8>().ok())
-                .unwrap_or(0)
-        } else if strategy_file.paytable_id.contains("joker") || strategy_file.hand_count > 210000 {
-            1
-        } else {
-            0
+        // Joker count decides the key format, so it must come from the paytable itself
+        let num_jokers = match get_paytable(&strategy_file.paytable_id) {
+            Some(pt) => pt.num_jokers(),
+            None => {
+                println!("✗ Unknown paytable: {}", strategy_file.paytable_id);
+                failed += 1;
+                continue;
+            }
         };
 
         // Generate binary v1
@@ -5092,7 +7203,7 @@ fn verify_binary_files(input_dir: &str) {
         }
 
         // Load binary file
-        let binary_data = match fs::read(&binary_path) {
+        let binary_data = match fs::read(binary_path) {
             Ok(data) => data,
             Err(e) => {
                 println!("✗ Failed to read binary: {}", e);
@@ -5221,9 +7332,10 @@ fn verify_binary_files(input_dir: &str) {
 /// Returns (json_gz_bytes, binary_v1_bytes, binary_v2_bytes, hand_count, version)
 fn generate_strategy_file_with_progress(paytable: &Paytable, current_paytable: usize, total_paytables: usize) -> (Vec<u8>, Vec<u8>, Vec<u8>, usize, u32) {
     let num_jokers = paytable.num_jokers();
-    let all_hands = generate_canonical_hands(num_jokers);
+    let all_hands = canonical_hands_for_paytable(paytable);
     let total = all_hands.len();
 
+    let table = handtable::hand_table(paytable);
     println!("  Calculating {} hands...", total);
     io::stdout().flush().unwrap();
 
@@ -5235,10 +7347,10 @@ fn generate_strategy_file_with_progress(paytable: &Paytable, current_paytable: u
     let strategies: HashMap<String, StrategyEntry> = all_hands
         .par_iter()
         .map(|(key, hand)| {
-            let (best_hold, best_ev, hold_evs) = analyze_hand(hand, paytable);
+            let (best_hold, best_ev, hold_evs) = analyze_hand(hand, paytable, &table);
 
             let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
-            if count % 5000 == 0 || count == total_for_progress {
+            if count.is_multiple_of(5000) || count == total_for_progress {
                 let pct = (count as f64 / total_for_progress as f64 * 100.0) as u32;
                 let elapsed = start.elapsed().as_secs_f64();
                 let rate = count as f64 / elapsed;
@@ -5315,332 +7427,114 @@ fn generate_strategy_file_with_progress(paytable: &Paytable, current_paytable: u
 // HAND DISTRIBUTION CALCULATION
 // ============================================================================
 
-/// Classify a final hand into its hand type name and payout.
-/// Mirrors get_payout exactly but also returns the hand type string.
-fn get_standard_hand_type(hand: &[Card], paytable: &Paytable) -> (&'static str, f64) {
-    if hand.len() != 5 { return ("Nothing", 0.0); }
+/// Read a .vpstrat2 file and return a map of canonical_key -> best_hold_mask
+fn read_vpstrat2_holds(path: &str) -> Result<HashMap<String, u8>, String> {
+    Ok(read_vpstrat2_entries(path)?.into_iter().map(|(key, (hold, _, _))| (key, hold)).collect())
+}
 
-    let flush = is_flush(hand);
-    let straight = is_straight(hand);
-    let counts = get_rank_counts(hand);
+/// Read a .vpstrat2 file and return canonical_key -> (best_hold_mask, best hold EV, EV step).
+/// The step is the encoding resolution; the stored EV is within half a step of the real one.
+fn read_vpstrat2_entries(path: &str) -> Result<HashMap<String, (u8, f64, f64)>, String> {
+    Ok(read_vpstrat2_evs(path)?.into_iter()
+        .map(|(key, (best_hold, evs, step))| (key, (best_hold, evs[best_hold as usize], step)))
+        .collect())
+}
 
-    if flush && straight {
-        let mut ranks: Vec<u8> = hand.iter().map(|c| c.rank()).collect();
-        ranks.sort();
-        if ranks == vec![8, 9, 10, 11, 12] {
-            return ("Royal Flush", paytable.royal_flush);
-        }
-        return ("Straight Flush", paytable.straight_flush);
-    }
+/// canonical_key -> (best_hold_mask, EV of every hold, EV step)
+type StrategyEvs = HashMap<String, (u8, [f64; 32], f64)>;
 
-    let mut pairs = 0;
-    let mut trips = 0;
-    let mut quad_rank: Option<u8> = None;
-    let mut pair_ranks: Vec<usize> = Vec::new();
+/// Read a .vpstrat2 file with the EV of every hold
+fn read_vpstrat2_evs(path: &str) -> Result<StrategyEvs, String> {
+    let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    if data.len() < VPS2_HEADER_SIZE { return Err("File too small".to_string()); }
+    if &data[0..4] != VPS2_MAGIC { return Err("Invalid magic".to_string()); }
 
-    for (rank, &count) in counts.iter().enumerate() {
-        match count {
-            2 => { pairs += 1; pair_ranks.push(rank); }
-            3 => trips += 1,
-            4 => quad_rank = Some(rank as u8),
-            _ => {}
-        }
+    let entry_count = u32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize;
+    let key_length = data[12] as usize;
+    let index_size = entry_count * key_length;
+    let data_start = VPS2_HEADER_SIZE + index_size;
+    if data.len() < data_start + entry_count * VPS2_DATA_ENTRY_SIZE {
+        return Err("File truncated".to_string());
     }
 
-    if let Some(qr) = quad_rank {
-        if paytable.has_kicker_bonus() {
-            let kicker_rank = counts.iter().enumerate()
-                .find(|(r, &c)| c == 1 && *r as u8 != qr)
-                .map(|(r, _)| r as u8)
-                .unwrap_or(0);
-            let is_low_kicker = kicker_rank <= 2 || kicker_rank == 12;
-
-            if qr == 12 {
-                if is_low_kicker {
-                    if let Some(payout) = paytable.four_aces_with_kicker {
-                        return ("Four Aces w/ 2-4", payout);
-                    }
-                }
-                if let Some(payout) = paytable.four_aces {
-                    return ("Four Aces", payout);
-                }
-            } else if qr <= 2 {
-                if is_low_kicker {
-                    if let Some(payout) = paytable.four_2_4_with_kicker {
-                        return ("Four 2-4 w/ A-4", payout);
-                    }
-                }
-                if let Some(payout) = paytable.four_2_4 {
-                    return ("Four 2-4", payout);
-                }
-            } else {
-                if let Some(payout) = paytable.four_5_k {
-                    return ("Four 5-K", payout);
-                }
-            }
+    let mut entries = HashMap::with_capacity(entry_count);
+    for i in 0..entry_count {
+        let key_offset = VPS2_HEADER_SIZE + i * key_length;
+        let key = String::from_utf8_lossy(&data[key_offset..key_offset + key_length])
+            .trim_end_matches('\0').to_string();
+        let data_offset = data_start + i * VPS2_DATA_ENTRY_SIZE;
+        let best_hold = data[data_offset];
+        let step = VPS2_SCALES[data[data_offset + 1].min(3) as usize];
+        let mut evs = [0.0; 32];
+        for (hold, ev) in evs.iter_mut().enumerate() {
+            let ev_offset = data_offset + 2 + hold * 2;
+            *ev = u16::from_le_bytes([data[ev_offset], data[ev_offset + 1]]) as f64 * step;
         }
+        entries.insert(key, (best_hold, evs, step));
+    }
+    Ok(entries)
+}
 
-        if paytable.has_face_kicker_bonus() {
-            let kicker_rank = counts.iter().enumerate()
-                .find(|(r, &c)| c == 1 && *r as u8 != qr)
-                .map(|(r, _)| r as u8)
-                .unwrap_or(0);
-            let is_face_kicker = kicker_rank >= 9;
+/// Generate canonical hands with multiplicity count; `quiet` skips the progress lines
+fn generate_canonical_hands_with_multiplicity(num_jokers: u8, quiet: bool) -> Vec<(String, Hand, u64)> {
+    if !quiet {
+        println!("  Generating canonical hands with multiplicity...");
+    }
+    let mut seen: HashMap<String, (Hand, u64)> = HashMap::new();
+    let max_card = 52 + num_jokers;
 
-            if qr == 12 {
-                if is_face_kicker {
-                    if let Some(payout) = paytable.four_aces_with_face {
-                        return ("Four Aces w/ J-K", payout);
-                    }
-                }
-                if let Some(payout) = paytable.four_aces {
-                    return ("Four Aces", payout);
-                }
-            } else if qr >= 9 && qr <= 11 {
-                if is_face_kicker {
-                    if let Some(payout) = paytable.four_jqk_with_face {
-                        return ("Four J-K w/ Face", payout);
+    for c1 in 0..(max_card - 4) {
+        for c2 in (c1 + 1)..(max_card - 3) {
+            for c3 in (c2 + 1)..(max_card - 2) {
+                for c4 in (c3 + 1)..(max_card - 1) {
+                    for c5 in (c4 + 1)..max_card {
+                        let hand: Hand = [Card(c1), Card(c2), Card(c3), Card(c4), Card(c5)];
+                        let key = hand_to_canonical_key(&hand);
+                        seen.entry(key)
+                            .and_modify(|(_, count)| *count += 1)
+                            .or_insert((hand, 1));
                     }
                 }
-                if let Some(payout) = paytable.four_jqk {
-                    return ("Four J-K", payout);
-                }
-            } else {
-                return ("Four of a Kind", paytable.four_of_a_kind);
             }
         }
-
-        if qr == 12 {
-            if let Some(payout) = paytable.four_aces {
-                return ("Four Aces", payout);
-            }
-        }
-        if qr <= 2 {
-            if let Some(payout) = paytable.four_2_4 {
-                return ("Four 2-4", payout);
-            }
-        }
-        if qr >= 9 && qr <= 11 {
-            if let Some(payout) = paytable.four_jqk {
-                return ("Four J-K", payout);
-            }
-        }
-        if qr == 6 {
-            if let Some(payout) = paytable.four_8s {
-                return ("Four 8s", payout);
-            }
-        }
-        if qr == 5 {
-            if let Some(payout) = paytable.four_7s {
-                return ("Four 7s", payout);
-            }
-        }
-        if let Some(payout) = paytable.four_5_k {
-            return ("Four 5-K", payout);
-        }
-        return ("Four of a Kind", paytable.four_of_a_kind);
     }
 
-    if trips > 0 && pairs > 0 { return ("Full House", paytable.full_house); }
-    if flush { return ("Flush", paytable.flush); }
-    if straight { return ("Straight", paytable.straight); }
-    if trips > 0 { return ("Three of a Kind", paytable.three_of_a_kind); }
-    if pairs == 2 { return ("Two Pair", paytable.two_pair); }
-
-    if pairs == 1 {
-        let pair_rank = pair_ranks[0] as u8;
-        if pair_rank >= paytable.min_pair_rank {
-            let name = match paytable.min_pair_rank {
-                8 => "Tens or Better",
-                11 => "Kings or Better",
-                _ => "Jacks or Better",
-            };
-            return (name, paytable.high_pair);
-        }
+    let total_dealt: u64 = seen.values().map(|(_, c)| c).sum();
+    if !quiet {
+        println!("  {} canonical hands, {} total dealt hands", seen.len(), total_dealt);
     }
-
-    ("Nothing", 0.0)
+    seen.into_iter().map(|(key, (hand, count))| (key, hand, count)).collect()
 }
 
-fn get_deuces_wild_hand_type(hand: &[Card], paytable: &Paytable) -> (&'static str, f64) {
-    let num_deuces = count_deuces(hand);
-    let non_deuces = get_non_deuces(hand);
-    let mut counts = [0u8; 13];
-    for card in &non_deuces { counts[card.rank() as usize] += 1; }
-    let max_count = *counts.iter().max().unwrap_or(&0);
-    let is_flush = is_flush_wild(&non_deuces);
-    let is_straight = is_straight_wild(&non_deuces, num_deuces);
-
-    if num_deuces == 0 && is_flush && is_straight {
-        let mut ranks: Vec<u8> = non_deuces.iter().map(|c| c.rank()).collect();
-        ranks.sort();
-        if ranks == vec![8, 9, 10, 11, 12] {
-            return ("Natural Royal Flush", paytable.royal_flush);
+/// Return per unit wagered implied by a strategy file's best-hold EVs over the dealt hands,
+/// and how far the EV encoding could move it. Like compute_distribution, this divides by
+/// the paytable's bet cost. Errors if a canonical hand is missing from the strategy.
+fn strategy_return(
+    paytable: &Paytable,
+    entries: &HashMap<String, (u8, f64, f64)>,
+    canonical_hands: &[(String, Hand, u64)],
+) -> Result<(f64, f64), String> {
+    let mut total = 0.0;
+    let mut tolerance = 0.0;
+    let mut dealt = 0.0;
+    let mut missing = 0;
+    for (key, _, multiplicity) in canonical_hands {
+        let m = *multiplicity as f64;
+        dealt += m;
+        match entries.get(key) {
+            Some(&(_, ev, step)) => {
+                total += m * ev;
+                // Half a step from the encoding, plus the 1e-6 rounding before it
+                tolerance += m * (step / 2.0 + 5e-7);
+            }
+            None => missing += 1,
         }
     }
-    if num_deuces == 4 { return ("Four Deuces", paytable.four_deuces.unwrap_or(200.0)); }
-    if is_royal_wild(&non_deuces, num_deuces) && num_deuces > 0 {
-        return ("Wild Royal Flush", paytable.wild_royal.unwrap_or(25.0));
-    }
-    if max_count + num_deuces >= 5 { return ("Five of a Kind", paytable.five_of_a_kind.unwrap_or(15.0)); }
-    if is_flush && is_straight && !is_royal_wild(&non_deuces, num_deuces) {
-        return ("Straight Flush", paytable.straight_flush);
-    }
-    if max_count + num_deuces >= 4 { return ("Four of a Kind", paytable.four_of_a_kind); }
-
-    if max_count + num_deuces >= 3 {
-        let mut sorted_counts: Vec<u8> = counts.iter().cloned().filter(|&c| c > 0).collect();
-        sorted_counts.sort(); sorted_counts.reverse();
-        let can_make_full_house = if sorted_counts.len() >= 2 {
-            let need_trips = 3_u8.saturating_sub(sorted_counts[0]);
-            let need_pair = 2_u8.saturating_sub(sorted_counts[1]);
-            need_trips + need_pair <= num_deuces
-        } else if sorted_counts.len() == 1 {
-            sorted_counts[0] + num_deuces >= 5 && sorted_counts[0] >= 2
-        } else { num_deuces >= 5 };
-        if can_make_full_house && max_count + num_deuces < 4 {
-            return ("Full House", paytable.full_house);
-        }
+    if missing > 0 {
+        return Err(format!("{} canonical hands are missing from the strategy file", missing));
     }
-
-    if is_flush && !is_straight { return ("Flush", paytable.flush); }
-    if is_straight && !is_flush { return ("Straight", paytable.straight); }
-    if max_count + num_deuces >= 3 { return ("Three of a Kind", paytable.three_of_a_kind); }
-    ("Nothing", 0.0)
-}
-
-fn get_joker_hand_type(hand: &[Card], paytable: &Paytable) -> (&'static str, f64) {
-    let num_jokers = count_jokers(hand);
-    let non_jokers = get_non_jokers(hand);
-    let mut counts = [0u8; 13];
-    for card in &non_jokers { if card.rank() < 13 { counts[card.rank() as usize] += 1; } }
-    let max_count = *counts.iter().max().unwrap_or(&0);
-    let num_pairs = counts.iter().filter(|&&c| c == 2).count() as u8;
-    let is_flush = is_flush_wild(&non_jokers);
-    let is_straight = is_straight_wild(&non_jokers, num_jokers);
-
-    if num_jokers == 0 && is_flush && is_straight {
-        let mut ranks: Vec<u8> = non_jokers.iter().map(|c| c.rank()).collect();
-        ranks.sort();
-        if ranks == vec![8, 9, 10, 11, 12] {
-            return ("Natural Royal Flush", paytable.royal_flush);
-        }
-    }
-    if max_count + num_jokers >= 5 { return ("Five of a Kind", paytable.five_of_a_kind.unwrap_or(100.0)); }
-    if is_royal_wild(&non_jokers, num_jokers) && num_jokers > 0 {
-        return ("Wild Royal Flush", paytable.wild_royal.unwrap_or(50.0));
-    }
-    if is_flush && is_straight { return ("Straight Flush", paytable.straight_flush); }
-    if max_count + num_jokers >= 4 { return ("Four of a Kind", paytable.four_of_a_kind); }
-
-    if (max_count + num_jokers >= 3) && (num_pairs >= 1 || max_count >= 2) {
-        let mut sorted_counts: Vec<u8> = counts.iter().cloned().filter(|&c| c > 0).collect();
-        sorted_counts.sort(); sorted_counts.reverse();
-        let can_make_full_house = if sorted_counts.len() >= 2 {
-            let need_trips = 3_u8.saturating_sub(sorted_counts[0]);
-            let need_pair = 2_u8.saturating_sub(sorted_counts[1]);
-            need_trips + need_pair <= num_jokers
-        } else { false };
-        if can_make_full_house && max_count + num_jokers < 4 {
-            return ("Full House", paytable.full_house);
-        }
-    }
-
-    if is_flush && !is_straight { return ("Flush", paytable.flush); }
-    if is_straight && !is_flush { return ("Straight", paytable.straight); }
-    if max_count + num_jokers >= 3 { return ("Three of a Kind", paytable.three_of_a_kind); }
-
-    if num_pairs == 2 || (num_pairs == 1 && num_jokers >= 1 && max_count < 3) {
-        if paytable.two_pair > 0.0 { return ("Two Pair", paytable.two_pair); }
-    }
-
-    if num_pairs == 1 || (num_jokers >= 1 && max_count >= 1) {
-        let mut pr: Vec<usize> = counts.iter().enumerate()
-            .filter(|(_, &c)| c >= 2 || (c == 1 && num_jokers >= 1))
-            .map(|(r, _)| r).collect();
-        pr.sort(); pr.reverse();
-        if !pr.is_empty() && pr[0] as u8 >= paytable.min_pair_rank {
-            let name = match paytable.min_pair_rank {
-                11 => "Kings or Better",
-                _ => "Jacks or Better",
-            };
-            return (name, paytable.high_pair);
-        }
-        if num_jokers >= 1 && paytable.high_pair > 0.0 {
-            if let Some(highest) = counts.iter().enumerate().rev().find(|(_, &c)| c >= 1).map(|(r, _)| r) {
-                if highest as u8 >= paytable.min_pair_rank {
-                    let name = match paytable.min_pair_rank {
-                        11 => "Kings or Better",
-                        _ => "Jacks or Better",
-                    };
-                    return (name, paytable.high_pair);
-                }
-            }
-        }
-    }
-
-    ("Nothing", 0.0)
-}
-
-fn get_hand_type(hand: &[Card], paytable: &Paytable) -> (&'static str, f64) {
-    if hand.len() != 5 { return ("Nothing", 0.0); }
-    if paytable.is_deuces_wild() {
-        get_deuces_wild_hand_type(hand, paytable)
-    } else if paytable.is_joker_poker() {
-        get_joker_hand_type(hand, paytable)
-    } else {
-        get_standard_hand_type(hand, paytable)
-    }
-}
-
-/// Read a .vpstrat2 file and return a map of canonical_key -> best_hold_mask
-fn read_vpstrat2_holds(path: &str) -> Result<HashMap<String, u8>, String> {
-    let data = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
-    if data.len() < VPS2_HEADER_SIZE { return Err("File too small".to_string()); }
-    if &data[0..4] != VPS2_MAGIC { return Err("Invalid magic".to_string()); }
-
-    let entry_count = u32::from_le_bytes([data[8], data[9], data[10], data[11]]) as usize;
-    let key_length = data[12] as usize;
-    let index_size = entry_count * key_length;
-    let data_start = VPS2_HEADER_SIZE + index_size;
-
-    let mut holds = HashMap::with_capacity(entry_count);
-    for i in 0..entry_count {
-        let key_offset = VPS2_HEADER_SIZE + i * key_length;
-        let key = String::from_utf8_lossy(&data[key_offset..key_offset + key_length])
-            .trim_end_matches('\0').to_string();
-        let data_offset = data_start + i * VPS2_DATA_ENTRY_SIZE;
-        let best_hold = data[data_offset];
-        holds.insert(key, best_hold);
-    }
-    Ok(holds)
-}
-
-/// Generate canonical hands with multiplicity count
-fn generate_canonical_hands_with_multiplicity(num_jokers: u8) -> Vec<(String, Hand, u64)> {
-    println!("  Generating canonical hands with multiplicity...");
-    let mut seen: HashMap<String, (Hand, u64)> = HashMap::new();
-    let max_card = 52 + num_jokers;
-
-    for c1 in 0..(max_card - 4) {
-        for c2 in (c1 + 1)..(max_card - 3) {
-            for c3 in (c2 + 1)..(max_card - 2) {
-                for c4 in (c3 + 1)..(max_card - 1) {
-                    for c5 in (c4 + 1)..max_card {
-                        let hand: Hand = [Card(c1), Card(c2), Card(c3), Card(c4), Card(c5)];
-                        let key = hand_to_canonical_key(&hand);
-                        seen.entry(key)
-                            .and_modify(|(_, count)| *count += 1)
-                            .or_insert((hand, 1));
-                    }
-                }
-            }
-        }
-    }
-
-    let total_dealt: u64 = seen.values().map(|(_, c)| c).sum();
-    println!("  {} canonical hands, {} total dealt hands", seen.len(), total_dealt);
-    seen.into_iter().map(|(key, (hand, count))| (key, hand, count)).collect()
+    let wagered = dealt * paytable.bet_cost();
+    Ok((total / wagered, tolerance / wagered))
 }
 
 /// Compute hand type distribution for a paytable
@@ -5649,20 +7543,21 @@ fn compute_distribution(
     holds: &HashMap<String, u8>,
     canonical_hands: &[(String, Hand, u64)],
 ) -> (HashMap<String, (f64, f64, usize)>, f64) {
-    let deck_size: u8 = 52 + paytable.num_jokers();
-    // C(deck_size, 5): total unique 5-card hands that can be dealt
-    let n = deck_size as f64;
+    let full_deck = paytable.deck_cards();
+    // C(deck size, 5): total unique 5-card hands that can be dealt
+    let n = full_deck.len() as f64;
     let total_dealt: f64 = n * (n - 1.0) * (n - 2.0) * (n - 3.0) * (n - 4.0) / 120.0;
+    let table = handtable::hand_table(paytable);
 
     let processed = Arc::new(AtomicUsize::new(0));
     let total_hands = canonical_hands.len();
     let calc_start = Instant::now();
 
-    let results: Vec<HashMap<&'static str, (f64, f64)>> = canonical_hands
+    let results: Vec<HashMap<HandType, f64>> = canonical_hands
         .par_iter()
         .map(|(key, hand, multiplicity)| {
             let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
-            if count % 5000 == 0 || count == total_hands {
+            if count.is_multiple_of(5000) || count == total_hands {
                 let elapsed = calc_start.elapsed().as_secs_f64();
                 let rate = count as f64 / elapsed;
                 let remaining = (total_hands - count) as f64 / rate;
@@ -5675,21 +7570,10 @@ fn compute_distribution(
                 None => 0, // fallback: discard all
             };
 
-            let mut held: Vec<Card> = Vec::with_capacity(5);
-            for i in 0..5 {
-                if best_hold & (1 << i) != 0 {
-                    held.push(hand[i]);
-                }
-            }
-            let num_to_draw = 5 - held.len();
+            let (mut final_hand, held) = held_cards(hand, best_hold);
+            let num_to_draw = 5 - held;
 
-            let mut deck: Vec<Card> = Vec::with_capacity((deck_size - 5) as usize);
-            for card_idx in 0..deck_size {
-                let card = Card(card_idx);
-                if !hand.contains(&card) {
-                    deck.push(card);
-                }
-            }
+            let deck: Vec<Card> = full_deck.iter().filter(|c| !hand.contains(c)).cloned().collect();
 
             let total_draws = if num_to_draw == 0 { 1u64 } else {
                 let n = deck.len() as u64;
@@ -5697,28 +7581,25 @@ fn compute_distribution(
                 (0..k).fold(1u64, |acc, i| acc * (n - i) / (i + 1))
             };
 
-            let mut local_counts: HashMap<&'static str, (u64, f64)> = HashMap::new(); // count, payout
+            let mut local_counts: HashMap<HandType, u64> = HashMap::new();
 
             if num_to_draw == 0 {
-                let (ht, payout) = get_hand_type(hand, paytable);
-                local_counts.insert(ht, (1, payout));
+                local_counts.insert(table.hand_type(hand), 1);
             } else {
                 for draw in deck.iter().combinations(num_to_draw) {
-                    let mut final_hand = held.clone();
-                    for &card in &draw { final_hand.push(*card); }
-                    let final_arr: [Card; 5] = final_hand.try_into().unwrap();
-                    let (ht, payout) = get_hand_type(&final_arr, paytable);
-                    let entry = local_counts.entry(ht).or_insert((0, payout));
-                    entry.0 += 1;
+                    for (slot, &card) in final_hand[held..].iter_mut().zip(&draw) {
+                        *slot = *card;
+                    }
+                    *local_counts.entry(table.hand_type(&final_hand)).or_insert(0) += 1;
                 }
             }
 
             // Convert to probability contributions
             let mult = *multiplicity as f64;
-            let mut prob_map: HashMap<&'static str, (f64, f64)> = HashMap::new();
-            for (ht, (count, payout)) in local_counts {
+            let mut prob_map: HashMap<HandType, f64> = HashMap::new();
+            for (ht, count) in local_counts {
                 let prob_contribution = mult * (count as f64) / (total_dealt * total_draws as f64);
-                prob_map.insert(ht, (prob_contribution, payout));
+                prob_map.insert(ht, prob_contribution);
             }
             prob_map
         })
@@ -5726,17 +7607,16 @@ fn compute_distribution(
 
     println!();
     // Merge all thread results
-    let mut merged: HashMap<String, (f64, f64)> = HashMap::new(); // name -> (total_prob, payout)
+    let mut merged: HashMap<HandType, f64> = HashMap::new(); // hand type -> total_prob
     for result in results {
-        for (ht, (prob, payout)) in result {
-            let entry = merged.entry(ht.to_string()).or_insert((0.0, payout));
-            entry.0 += prob;
+        for (ht, prob) in result {
+            *merged.entry(ht).or_insert(0.0) += prob;
         }
     }
 
     // Sort by payout descending to assign display order
     let mut sorted: Vec<(String, f64, f64)> = merged.iter()
-        .map(|(name, (prob, payout))| (name.clone(), *prob, *payout))
+        .map(|(ht, prob)| (ht.name().to_string(), *prob, paytable.payout(*ht)))
         .collect();
     sorted.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
 
@@ -5747,10 +7627,153 @@ fn compute_distribution(
         overall_return += return_contrib;
         final_map.insert(name.clone(), (*prob, *payout, order + 1));
     }
+    // Payouts are per coin of the base game; side bets like Quick Quads cost extra
+    overall_return /= paytable.bet_cost();
 
     (final_map, overall_return)
 }
 
+/// Default risk of ruin inputs: bankroll in bets and session length in hands
+const DEFAULT_ROR_BANKROLL: f64 = 400.0;
+const DEFAULT_ROR_HANDS: u64 = 1000;
+
+/// Per-hand figures of a hand distribution, per unit wagered
+#[derive(Serialize, Clone)]
+struct DistributionStats {
+    return_pct: f64,
+    /// Coins wagered per coin of the base game; hand type payouts are divided by it
+    bet_cost: f64,
+    /// Variance and standard deviation of the amount returned per unit wagered
+    variance: f64,
+    std_dev: f64,
+    /// Standard deviation over the expected amount returned
+    coefficient_of_variation: f64,
+    /// Share of hands that pay anything
+    hit_frequency: f64,
+    /// Expected hands between natural royal flushes (None if the paytable has none)
+    hands_per_royal: Option<f64>,
+    /// Chance of losing `ror_bankroll` bets at some point within `ror_hands` hands
+    risk_of_ruin: f64,
+    ror_bankroll: f64,
+    ror_hands: u64,
+}
+
+/// Standard normal CDF (Abramowitz and Stegun 7.1.26, error below 1.5e-7)
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+    let poly = t * (0.254829592 + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erfc = poly * (-z * z).exp();
+    if x >= 0.0 { 1.0 - erfc / 2.0 } else { erfc / 2.0 }
+}
+
+/// Chance a bankroll of `bankroll` bets is lost within `hands` hands, treating the running
+/// result as Brownian motion with the game's drift (return - 1) and variance per hand.
+/// The royal's long tail makes this an estimate; it is most accurate over long sessions.
+fn risk_of_ruin(return_rate: f64, variance: f64, bankroll: f64, hands: u64) -> f64 {
+    if hands == 0 || bankroll <= 0.0 {
+        return if bankroll <= 0.0 { 1.0 } else { 0.0 };
+    }
+    let n = hands as f64;
+    let drift = return_rate - 1.0;
+    if variance <= 0.0 {
+        return if drift * n <= -bankroll { 1.0 } else { 0.0 };
+    }
+    let spread = (variance * n).sqrt();
+    let first = normal_cdf((-bankroll - drift * n) / spread);
+    let second = (-2.0 * drift * bankroll / variance).exp() * normal_cdf((-bankroll + drift * n) / spread);
+    (first + second).clamp(0.0, 1.0)
+}
+
+/// Variance, hit frequency, hands per royal and risk of ruin for a distribution from
+/// compute_distribution, whose payouts are per coin of the base game
+fn distribution_stats(
+    distribution: &HashMap<String, (f64, f64, usize)>,
+    overall_return: f64,
+    bet_cost: f64,
+    ror_bankroll: f64,
+    ror_hands: u64,
+) -> DistributionStats {
+    let mut second = 0.0;
+    let mut hit_frequency = 0.0;
+    for &(prob, payout, _) in distribution.values() {
+        if payout > 0.0 {
+            second += prob * payout * payout;
+            hit_frequency += prob;
+        }
+    }
+    let variance = (second / (bet_cost * bet_cost) - overall_return * overall_return).max(0.0);
+    let std_dev = variance.sqrt();
+    let royal_prob = ["Royal Flush", "Natural Royal Flush"].iter()
+        .filter_map(|name| distribution.get(*name))
+        .map(|&(prob, _, _)| prob)
+        .find(|&prob| prob > 0.0);
+    DistributionStats {
+        return_pct: overall_return * 100.0,
+        bet_cost,
+        variance,
+        std_dev,
+        coefficient_of_variation: if overall_return > 0.0 { std_dev / overall_return } else { 0.0 },
+        hit_frequency,
+        hands_per_royal: royal_prob.map(|p| 1.0 / p),
+        risk_of_ruin: risk_of_ruin(overall_return, variance, ror_bankroll, ror_hands),
+        ror_bankroll,
+        ror_hands,
+    }
+}
+
+/// Write the distribution and its stats to `{output_dir}/distribution_{id}.json`
+fn save_distribution_locally(
+    output_dir: &str,
+    paytable_id: &str,
+    distribution: &HashMap<String, (f64, f64, usize)>,
+    stats: &DistributionStats,
+    total_canonical: usize,
+    total_dealt: u64,
+) -> Result<String, String> {
+    #[derive(Serialize)]
+    struct HandTypeRow<'a> {
+        hand_type: &'a str,
+        hand_type_order: usize,
+        payout_per_coin: f64,
+        probability: f64,
+        return_contribution: f64,
+    }
+    #[derive(Serialize)]
+    struct DistributionFile<'a> {
+        paytable_id: &'a str,
+        #[serde(flatten)]
+        stats: &'a DistributionStats,
+        total_canonical_hands: usize,
+        total_dealt_hands: u64,
+        hand_types: Vec<HandTypeRow<'a>>,
+    }
+
+    let mut hand_types: Vec<HandTypeRow> = distribution.iter()
+        .map(|(name, &(probability, payout, order))| HandTypeRow {
+            hand_type: name,
+            hand_type_order: order,
+            payout_per_coin: payout,
+            probability,
+            return_contribution: probability * payout / stats.bet_cost,
+        })
+        .collect();
+    hand_types.sort_by_key(|row| row.hand_type_order);
+    let file = DistributionFile {
+        paytable_id,
+        stats,
+        total_canonical_hands: total_canonical,
+        total_dealt_hands: total_dealt,
+        hand_types,
+    };
+
+    fs::create_dir_all(output_dir).map_err(|e| format!("Failed to create {}: {}", output_dir, e))?;
+    let path = Path::new(output_dir).join(format!("distribution_{}.json", paytable_id.replace('-', "_")));
+    let json = serde_json::to_string_pretty(&file).map_err(|e| format!("Failed to serialize: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path.to_string_lossy().to_string())
+}
+
 /// Check which paytables already have distribution data in Supabase
 fn fetch_completed_distributions(
     client: &reqwest::blocking::Client,
@@ -5787,13 +7810,14 @@ fn fetch_completed_distributions(
 }
 
 /// Upload distribution results to Supabase
+#[allow(clippy::too_many_arguments)]
 fn upload_distribution_to_supabase(
     client: &reqwest::blocking::Client,
     supabase_url: &str,
     service_key: &str,
     paytable_id: &str,
     distribution: &HashMap<String, (f64, f64, usize)>,
-    overall_return: f64,
+    stats: &DistributionStats,
     total_canonical: usize,
     total_dealt: u64,
 ) -> Result<(), String> {
@@ -5830,7 +7854,7 @@ fn upload_distribution_to_supabase(
             hand_type_order: *order,
             payout_per_coin: *payout,
             probability: *prob,
-            return_contribution: prob * payout,
+            return_contribution: prob * payout / stats.bet_cost,
         }
     }).collect();
 
@@ -5854,16 +7878,37 @@ fn upload_distribution_to_supabase(
     #[derive(Serialize)]
     struct ReturnRow {
         paytable_id: String,
+        base_paytable_id: String,
+        coins: i32,
         calculated_return_pct: f64,
         total_canonical_hands: i64,
         total_dealt_hands: i64,
+        variance: f64,
+        std_dev: f64,
+        coefficient_of_variation: f64,
+        hit_frequency: f64,
+        hands_per_royal: Option<f64>,
+        risk_of_ruin: f64,
+        ror_bankroll: f64,
+        ror_hands: i64,
     }
 
+    let (base_paytable_id, coins) = split_coin_paytable_id(paytable_id);
     let return_row = ReturnRow {
         paytable_id: paytable_id.to_string(),
-        calculated_return_pct: overall_return * 100.0, // convert to percentage
+        base_paytable_id: base_paytable_id.to_string(),
+        coins: coins as i32,
+        calculated_return_pct: stats.return_pct,
         total_canonical_hands: total_canonical as i64,
         total_dealt_hands: total_dealt as i64,
+        variance: stats.variance,
+        std_dev: stats.std_dev,
+        coefficient_of_variation: stats.coefficient_of_variation,
+        hit_frequency: stats.hit_frequency,
+        hands_per_royal: stats.hands_per_royal,
+        risk_of_ruin: stats.risk_of_ruin,
+        ror_bankroll: stats.ror_bankroll,
+        ror_hands: stats.ror_hands as i64,
     };
 
     let url2 = format!("{}/rest/v1/paytable_returns", supabase_url);
@@ -5885,34 +7930,55 @@ fn upload_distribution_to_supabase(
     Ok(())
 }
 
-fn run_distribution(strategies_dir: &str, single_paytable: Option<&str>) {
+struct DistributionOptions {
+    strategies_dir: String,
+    coins: u8,
+    /// Directory for the local distribution_<id>.json files
+    output_dir: String,
+    /// Upload to Supabase, skipping paytables already there
+    upload: bool,
+    ror_bankroll: f64,
+    ror_hands: u64,
+}
+
+fn run_distribution(single_paytable: Option<&str>, opts: &DistributionOptions) {
     println!("╔══════════════════════════════════════════════════════════════════╗");
     println!("║          HAND DISTRIBUTION CALCULATOR                           ║");
     println!("╚══════════════════════════════════════════════════════════════════╝");
     println!();
+    let strategies_dir = opts.strategies_dir.as_str();
+    let coins = opts.coins;
 
     // Load environment
     dotenv::from_path("../../.env").ok();
-    let supabase_url = std::env::var("SUPABASE_URL")
-        .unwrap_or_else(|_| "https://ctqefgdvqiaiumtmcjdz.supabase.co".to_string());
-    let service_key = match std::env::var("SUPABASE_SERVICE_ROLE_KEY")
-        .or_else(|_| std::env::var("SUPABASE_SERVICE_KEY"))
-    {
-        Ok(key) => key,
-        Err(_) => { eprintln!("SUPABASE_SERVICE_ROLE_KEY not set in .env"); std::process::exit(1); }
+    let supabase = if opts.upload {
+        let supabase_url = std::env::var("SUPABASE_URL")
+            .unwrap_or_else(|_| "https://ctqefgdvqiaiumtmcjdz.supabase.co".to_string());
+        let service_key = match std::env::var("SUPABASE_SERVICE_ROLE_KEY")
+            .or_else(|_| std::env::var("SUPABASE_SERVICE_KEY"))
+        {
+            Ok(key) => key,
+            Err(_) => { eprintln!("SUPABASE_SERVICE_ROLE_KEY not set in .env"); std::process::exit(1); }
+        };
+        let client = reqwest::blocking::Client::builder()
+            .timeout(std::time::Duration::from_secs(120))
+            .build()
+            .expect("Failed to create HTTP client");
+        Some((client, supabase_url, service_key))
+    } else {
+        None
     };
 
-    let client = reqwest::blocking::Client::builder()
-        .timeout(std::time::Duration::from_secs(120))
-        .build()
-        .expect("Failed to create HTTP client");
-
     // Get all paytable IDs (or just the one requested)
-    let all_ids: Vec<&str> = if let Some(id) = single_paytable {
+    let base_ids: Vec<&str> = if let Some(id) = single_paytable {
         vec![id]
     } else {
         get_all_paytable_ids()
     };
+    let all_ids: Vec<String> = base_ids
+        .into_iter()
+        .filter_map(|id| coin_paytable_id(id, coins))
+        .collect();
 
     // Check which have strategy files
     let ios_resources = "../../ios-native/VideoPokerAcademy/VideoPokerAcademy/Resources";
@@ -5929,9 +7995,15 @@ fn run_distribution(strategies_dir: &str, single_paytable: Option<&str>) {
     }
 
     // Check which are already computed in Supabase
-    println!("Checking Supabase for already-computed distributions...");
-    let completed = fetch_completed_distributions(&client, &supabase_url, &service_key);
-    println!("  Found {} already completed\n", completed.len());
+    let completed = match &supabase {
+        Some((client, supabase_url, service_key)) => {
+            println!("Checking Supabase for already-computed distributions...");
+            let completed = fetch_completed_distributions(client, supabase_url, service_key);
+            println!("  Found {} already completed\n", completed.len());
+            completed
+        }
+        None => HashSet::new(),
+    };
 
     let mut to_process: Vec<(&str, String)> = paytables_with_strategies
         .into_iter()
@@ -5963,10 +8035,10 @@ fn run_distribution(strategies_dir: &str, single_paytable: Option<&str>) {
 
     // Pre-generate canonical hands (reuse across paytables of same deck type)
     println!("Pre-generating canonical hands...");
-    let standard_hands = generate_canonical_hands_with_multiplicity(0);
-    let joker_hands_1 = generate_canonical_hands_with_multiplicity(1);
-    let joker_hands_2 = generate_canonical_hands_with_multiplicity(2);
-    let joker_hands_3 = generate_canonical_hands_with_multiplicity(3);
+    let standard_hands = generate_canonical_hands_with_multiplicity(0, false);
+    let joker_hands_1 = generate_canonical_hands_with_multiplicity(1, false);
+    let joker_hands_2 = generate_canonical_hands_with_multiplicity(2, false);
+    let joker_hands_3 = generate_canonical_hands_with_multiplicity(3, false);
     println!();
 
     for (idx, (paytable_id, strat_path)) in to_process.iter().enumerate() {
@@ -5999,7 +8071,7 @@ fn run_distribution(strategies_dir: &str, single_paytable: Option<&str>) {
         // Load strategy file
         print!("  Loading strategy file... ");
         io::stdout().flush().unwrap();
-        let holds = match read_vpstrat2_holds(strat_path) {
+        let entries = match read_vpstrat2_entries(strat_path) {
             Ok(h) => { println!("{} entries", h.len()); h }
             Err(e) => {
                 println!("FAILED: {}", e);
@@ -6008,13 +8080,20 @@ fn run_distribution(strategies_dir: &str, single_paytable: Option<&str>) {
             }
         };
 
-        let canonical: &Vec<(String, Hand, u64)> = match paytable.num_jokers() {
-            1 => &joker_hands_1,
-            2 => &joker_hands_2,
-            3 => &joker_hands_3,
-            _ => &standard_hands,
+        let custom_hands;
+        let canonical: &Vec<(String, Hand, u64)> = if paytable.deck.is_some() {
+            custom_hands = canonical_hands_with_multiplicity_for_paytable(&paytable);
+            &custom_hands
+        } else {
+            match paytable.num_jokers() {
+                1 => &joker_hands_1,
+                2 => &joker_hands_2,
+                3 => &joker_hands_3,
+                _ => &standard_hands,
+            }
         };
         let total_dealt: u64 = canonical.iter().map(|(_, _, m)| m).sum();
+        let holds: HashMap<String, u8> = entries.iter().map(|(key, (hold, _, _))| (key.clone(), *hold)).collect();
 
         // Compute distribution
         print!("  Computing distribution ({} canonical hands)... ", canonical.len());
@@ -6042,21 +8121,59 @@ fn run_distribution(strategies_dir: &str, single_paytable: Option<&str>) {
             overall_return * 100.0);
         println!("  └─────────────────────────────┴────────────┴──────────────┴──────────────┘");
 
-        // Upload to Supabase
-        print!("  Uploading to Supabase... ");
-        io::stdout().flush().unwrap();
-        match upload_distribution_to_supabase(
-            &client, &supabase_url, &service_key, paytable_id,
-            &distribution, overall_return, canonical.len(), total_dealt,
-        ) {
-            Ok(()) => println!("✓"),
+        // Self-check: the classified distribution must pay what the strategy's EVs say it does
+        match strategy_return(&paytable, &entries, canonical) {
+            Ok((expected, tolerance)) => {
+                let diff = (overall_return - expected).abs();
+                if diff > tolerance + 1e-9 {
+                    println!("  ✗ Self-check failed: distribution return {:.6}% vs strategy EV {:.6}% (tolerance {:.6}%)",
+                        overall_return * 100.0, expected * 100.0, tolerance * 100.0);
+                    failed_list.push(paytable_id.to_string());
+                    continue;
+                }
+                println!("  ✓ Matches strategy EV {:.6}% (off by {:.6}%)", expected * 100.0, diff * 100.0);
+            }
             Err(e) => {
-                println!("FAILED: {}", e);
+                println!("  ✗ Self-check failed: {}", e);
                 failed_list.push(paytable_id.to_string());
                 continue;
             }
         }
 
+        let stats = distribution_stats(&distribution, overall_return, paytable.bet_cost(), opts.ror_bankroll, opts.ror_hands);
+        println!("  Std deviation {:.4} (variance {:.4}) | CV {:.4} | Hit frequency {:.4}%",
+            stats.std_dev, stats.variance, stats.coefficient_of_variation, stats.hit_frequency * 100.0);
+        if let Some(hands) = stats.hands_per_royal {
+            println!("  Hands per royal: {:.0}", hands);
+        }
+        println!("  Risk of ruin, {} bets over {} hands: {:.4}%", stats.ror_bankroll, stats.ror_hands, stats.risk_of_ruin * 100.0);
+
+        match save_distribution_locally(&opts.output_dir, paytable_id, &distribution, &stats, canonical.len(), total_dealt) {
+            Ok(path) => println!("  ✓ Saved {}", path),
+            Err(e) => {
+                println!("  ✗ {}", e);
+                failed_list.push(paytable_id.to_string());
+                continue;
+            }
+        }
+
+        // Upload to Supabase
+        if let Some((client, supabase_url, service_key)) = &supabase {
+            print!("  Uploading to Supabase... ");
+            io::stdout().flush().unwrap();
+            match upload_distribution_to_supabase(
+                client, supabase_url, service_key, paytable_id,
+                &distribution, &stats, canonical.len(), total_dealt,
+            ) {
+                Ok(()) => println!("✓"),
+                Err(e) => {
+                    println!("FAILED: {}", e);
+                    failed_list.push(paytable_id.to_string());
+                    continue;
+                }
+            }
+        }
+
         let pt_elapsed = pt_start.elapsed().as_secs_f64();
         paytable_times.push(pt_elapsed);
         completed_count += 1;
@@ -6079,6 +8196,16 @@ fn run_distribution(strategies_dir: &str, single_paytable: Option<&str>) {
     println!("╚══════════════════════════════════════════════════════════════════╝");
 }
 
+fn parse_coins(arg: &str) -> u8 {
+    match arg.parse::<u8>() {
+        Ok(n) if (1..=5).contains(&n) => n,
+        _ => {
+            eprintln!("--coins expects a number from 1 to 5, got '{}'", arg);
+            std::process::exit(1);
+        }
+    }
+}
+
 fn main() {
     let args: Vec<String> = std::env::args().collect();
 
@@ -6092,13 +8219,51 @@ fn main() {
         println!("  vp_calculator upload-existing [DIR]      Upload existing .json.gz files to Supabase");
         println!("  vp_calculator list                       List all available paytables");
         println!("  vp_calculator test [filter]              Run payout tests");
+        println!("  vp_calculator test-hand-tables [filter]  Check precomputed hand tables against the evaluators");
         println!("  vp_calculator manifest                   Show current manifest from Supabase");
         println!("  vp_calculator distribution               Compute hand distributions for all paytables");
         println!("  vp_calculator distribution <id>          Compute for a single paytable");
+        println!("                                           (return, variance, risk of ruin; saved to");
+        println!("                                           ./strategies/distribution unless --output)");
+        println!("  vp_calculator progressive <id>           Sweep a progressive meter (break-even, hold switches)");
+        println!("  vp_calculator overlay <id>               Return/variance of Super Times Pay, Hot Roll, etc.");
+        println!("  vp_calculator www-blend <base-id>        Wild Wild Wild return blended over 0-3 added wilds");
+        println!("  vp_calculator double-up <id>             Variance and pay distribution with double-up policies");
+        println!("  vp_calculator sequential <id>-seq<N>     Positional strategy with an N per coin sequential royal");
+        println!("  vp_calculator pickem <id>                Pick'em Poker strategy and return (pickem-full-pay)");
+        println!("  vp_calculator shockwave <id>             Strategies and return with a four of a kind shockwave");
+        println!("  vp_calculator strategy-diff <id-a> <id-b> Hands where B's best hold differs, cost of A's strategy on B");
+        println!("  vp_calculator cross-play [family|id ...] Return of each paytable played with another's strategy");
+        println!("  vp_calculator simulate <id>              Monte Carlo sessions with the strategy file's holds");
+        println!("  vp_calculator sensitivity <id>           Return change per unit of each payout, fixed and re-optimized");
+        println!("  vp_calculator identify [FILE|-]          Catalog paytables matching observed pays (JSON in and out)");
         println!("\nOptions:");
         println!("  --no-upload      Skip uploading to Supabase Storage");
         println!("  --output DIR     Specify output directory (default: ../../supabase-uploads)");
         println!("  --strategies DIR Strategy files directory (default: ./strategies)");
+        println!("  --coins N        Coins bet, 1-5 (default: 5). Below 5 the royal pays 250 per coin and");
+        println!("                   results are stored under <paytable-id>-<N>coin");
+        println!("  --field NAME     Progressive: payout field used as the meter (default: royal_flush)");
+        println!("  --grid S:E:STEP  Progressive: meter values to sweep (default: catalog value to 3x)");
+        println!("  --hands K1,K2    Progressive: canonical hands to report switch points for");
+        println!("  --top N          Progressive/sequential/strategy-diff: rows to print (default: 25)");
+        println!("  --also IDS       Cross-play: extra strategies, comma-separated, played on every family");
+        println!("                   dealt from the same deck");
+        println!("  --no-strategies  Progressive/shockwave: skip writing .vpstrat2 files");
+        println!("  --shock-hands N  Shockwave: hands in a shockwave (default: 10)");
+        println!("  --multiplier M   Shockwave: multiplier on every pay during a shockwave (default: 2)");
+        println!("  --no-retrigger   Shockwave: four of a kind during a shockwave does not restart it");
+        println!("  --overlay IDS    Overlay: built-in overlays, comma-separated (default: all)");
+        println!("  --overlay-file F Overlay: JSON overlay definition (see docs/VARIANTS.md)");
+        println!("  --wilds P0,..,P3 WWW blend: wild-count odds (default: docs/www-pay-tables.md)");
+        println!("  --policy P,...   Double-up: always, threshold:<amount>, stop:<N> (default: stop:1,stop:2,always)");
+        println!("  --max-doubles N  Double-up: machine limit on doubles per win (default: 5)");
+        println!("  --tie-prob P     Double-up: chance a comparison ties and is replayed (default: 3/51)");
+        println!("  --bankroll N     Distribution: risk of ruin bankroll in bets (default: 400)");
+        println!("  --session N      Distribution/simulate: session length in hands (default: 1000)");
+        println!("  --sessions N     Simulate: sessions to play (default: 10000)");
+        println!("  --lines N        Simulate: lines per hand, 1-100, each drawing from its own deck (default: 1)");
+        println!("  --seed S         Simulate: RNG seed (default: 1)");
         return;
     }
 
@@ -6116,16 +8281,20 @@ fn main() {
     // Check for generate-all mode
     if args.get(1).map(|s| s.as_str()) == Some("generate-all") {
         let mut output_dir = "./strategies".to_string();
+        let mut coins = 5u8;
         let mut i = 2;
         while i < args.len() {
             if args[i] == "--output" && i + 1 < args.len() {
                 output_dir = args[i + 1].clone();
                 i += 2;
+            } else if args[i] == "--coins" && i + 1 < args.len() {
+                coins = parse_coins(&args[i + 1]);
+                i += 2;
             } else {
                 i += 1;
             }
         }
-        generate_all_strategies(&output_dir);
+        generate_all_strategies(&output_dir, coins);
         return;
     }
 
@@ -6143,6 +8312,13 @@ fn main() {
         return;
     }
 
+    // Check for hand table test mode
+    if args.get(1).map(|s| s.as_str()) == Some("test-hand-tables") {
+        let filter = args.get(2).map(|s| s.as_str());
+        handtable::run_hand_table_tests(filter);
+        return;
+    }
+
     // Check for convert-to-binary mode
     if args.get(1).map(|s| s.as_str()) == Some("convert-to-binary") {
         let input_dir = args.get(2).map(|s| s.as_str()).unwrap_or("./strategies");
@@ -6159,12 +8335,34 @@ fn main() {
 
     // Check for distribution mode
     if args.get(1).map(|s| s.as_str()) == Some("distribution") {
-        let mut strategies_dir = "./strategies".to_string();
         let mut single_id: Option<String> = None;
+        let mut opts = DistributionOptions {
+            strategies_dir: "./strategies".to_string(),
+            coins: 5,
+            output_dir: "./strategies/distribution".to_string(),
+            upload: true,
+            ror_bankroll: DEFAULT_ROR_BANKROLL,
+            ror_hands: DEFAULT_ROR_HANDS,
+        };
         let mut i = 2;
         while i < args.len() {
             if args[i] == "--strategies" && i + 1 < args.len() {
-                strategies_dir = args[i + 1].clone();
+                opts.strategies_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--coins" && i + 1 < args.len() {
+                opts.coins = parse_coins(&args[i + 1]);
+                i += 2;
+            } else if args[i] == "--output" && i + 1 < args.len() {
+                opts.output_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--no-upload" {
+                opts.upload = false;
+                i += 1;
+            } else if args[i] == "--bankroll" && i + 1 < args.len() {
+                opts.ror_bankroll = args[i + 1].parse().unwrap_or(DEFAULT_ROR_BANKROLL);
+                i += 2;
+            } else if args[i] == "--session" && i + 1 < args.len() {
+                opts.ror_hands = args[i + 1].parse().unwrap_or(DEFAULT_ROR_HANDS);
                 i += 2;
             } else if !args[i].starts_with("--") {
                 single_id = Some(args[i].clone());
@@ -6173,7 +8371,453 @@ fn main() {
                 i += 1;
             }
         }
-        run_distribution(&strategies_dir, single_id.as_deref());
+        run_distribution(single_id.as_deref(), &opts);
+        return;
+    }
+
+    // Check for shockwave mode
+    if args.get(1).map(|s| s.as_str()) == Some("shockwave") {
+        let mut paytable_id: Option<String> = None;
+        let mut opts = shockwave::ShockwaveOptions {
+            hands: 10,
+            multiplier: 2.0,
+            retrigger: true,
+            output_dir: "./strategies/shockwave".to_string(),
+            write_strategies: true,
+        };
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--shock-hands" && i + 1 < args.len() {
+                opts.hands = args[i + 1].parse().unwrap_or(10);
+                i += 2;
+            } else if args[i] == "--multiplier" && i + 1 < args.len() {
+                opts.multiplier = args[i + 1].parse().unwrap_or(2.0);
+                i += 2;
+            } else if args[i] == "--no-retrigger" {
+                opts.retrigger = false;
+                i += 1;
+            } else if args[i] == "--output" && i + 1 < args.len() {
+                opts.output_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--no-strategies" {
+                opts.write_strategies = false;
+                i += 1;
+            } else if !args[i].starts_with("--") {
+                paytable_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match paytable_id {
+            Some(id) => shockwave::run_shockwave(&id, &opts),
+            None => {
+                eprintln!("Usage: vp_calculator shockwave <paytable-id> [--shock-hands N] [--multiplier M] [--no-retrigger]");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for Pick'em mode
+    if args.get(1).map(|s| s.as_str()) == Some("pickem") {
+        let mut paytable_id: Option<String> = None;
+        let mut output_dir = "./strategies".to_string();
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--output" && i + 1 < args.len() {
+                output_dir = args[i + 1].clone();
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                paytable_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match paytable_id {
+            Some(id) => pickem::run_pickem(&id, &output_dir),
+            None => {
+                eprintln!("Usage: vp_calculator pickem <paytable-id> [--output DIR]");
+                eprintln!("Available: {}", pickem::pickem_paytable_ids().join(", "));
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for simulation mode
+    if args.get(1).map(|s| s.as_str()) == Some("simulate") {
+        let mut paytable_id: Option<String> = None;
+        let mut opts = simulate::SimulateOptions {
+            sessions: 10000,
+            hands: 1000,
+            lines: 1,
+            seed: 1,
+            strategies_dir: "./strategies".to_string(),
+            output_dir: "./strategies/simulate".to_string(),
+        };
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--sessions" && i + 1 < args.len() {
+                opts.sessions = args[i + 1].parse().unwrap_or(10000);
+                i += 2;
+            } else if args[i] == "--session" && i + 1 < args.len() {
+                opts.hands = args[i + 1].parse().unwrap_or(1000);
+                i += 2;
+            } else if args[i] == "--lines" && i + 1 < args.len() {
+                opts.lines = args[i + 1].parse().unwrap_or(1);
+                i += 2;
+            } else if args[i] == "--seed" && i + 1 < args.len() {
+                opts.seed = args[i + 1].parse().unwrap_or(1);
+                i += 2;
+            } else if args[i] == "--strategies" && i + 1 < args.len() {
+                opts.strategies_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--output" && i + 1 < args.len() {
+                opts.output_dir = args[i + 1].clone();
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                paytable_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match paytable_id {
+            Some(id) => simulate::run_simulate(&id, &opts),
+            None => {
+                eprintln!("Usage: vp_calculator simulate <paytable-id> [--sessions N] [--session HANDS] [--lines N] [--seed S]");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for identify mode
+    if args.get(1).map(|s| s.as_str()) == Some("identify") {
+        let mut input = "-".to_string();
+        let mut strategies_dir = "./strategies".to_string();
+        let mut output: Option<String> = None;
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--strategies" && i + 1 < args.len() {
+                strategies_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--output" && i + 1 < args.len() {
+                output = Some(args[i + 1].clone());
+                i += 2;
+            } else if args[i] == "-" || !args[i].starts_with("--") {
+                input = args[i].clone();
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        identify::run_identify(&input, &strategies_dir, output.as_deref());
+        return;
+    }
+
+    // Check for sensitivity mode
+    if args.get(1).map(|s| s.as_str()) == Some("sensitivity") {
+        let mut paytable_id: Option<String> = None;
+        let mut output_dir = "./strategies/sensitivity".to_string();
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--output" && i + 1 < args.len() {
+                output_dir = args[i + 1].clone();
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                paytable_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match paytable_id {
+            Some(id) => sensitivity::run_sensitivity(&id, &output_dir),
+            None => {
+                eprintln!("Usage: vp_calculator sensitivity <paytable-id> [--output DIR]");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for cross-play mode
+    if args.get(1).map(|s| s.as_str()) == Some("cross-play") {
+        let mut filters: Vec<String> = Vec::new();
+        let mut extra_ids: Vec<String> = Vec::new();
+        let mut strategies_dir = "./strategies".to_string();
+        let mut output_dir = "./strategies/crossplay".to_string();
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--strategies" && i + 1 < args.len() {
+                strategies_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--output" && i + 1 < args.len() {
+                output_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--also" && i + 1 < args.len() {
+                extra_ids.extend(args[i + 1].split(',').filter(|s| !s.is_empty()).map(|s| s.to_string()));
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                filters.push(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        crossplay::run_cross_play(&filters, &extra_ids, &strategies_dir, &output_dir);
+        return;
+    }
+
+    // Check for strategy diff mode
+    if args.get(1).map(|s| s.as_str()) == Some("strategy-diff") {
+        let mut ids: Vec<String> = Vec::new();
+        let mut strategies_dir = "./strategies".to_string();
+        let mut output_dir = "./strategies/diff".to_string();
+        let mut top = 25;
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--strategies" && i + 1 < args.len() {
+                strategies_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--output" && i + 1 < args.len() {
+                output_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--top" && i + 1 < args.len() {
+                top = args[i + 1].parse().unwrap_or(25);
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                ids.push(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match ids.as_slice() {
+            [a, b] => strategydiff::run_strategy_diff(a, b, &strategies_dir, top, &output_dir),
+            _ => {
+                eprintln!("Usage: vp_calculator strategy-diff <id-a> <id-b> [--strategies DIR] [--output DIR] [--top N]");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for sequential royal mode
+    if args.get(1).map(|s| s.as_str()) == Some("sequential") {
+        let mut paytable_id: Option<String> = None;
+        let mut output_dir = "./strategies/sequential".to_string();
+        let mut top = 25;
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--output" && i + 1 < args.len() {
+                output_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--top" && i + 1 < args.len() {
+                top = args[i + 1].parse().unwrap_or(25);
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                paytable_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match paytable_id {
+            Some(id) => sequential::run_sequential(&id, &output_dir, top),
+            None => {
+                eprintln!("Usage: vp_calculator sequential <paytable-id>-seq<N> [--output DIR] [--top N]");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for progressive mode
+    if args.get(1).map(|s| s.as_str()) == Some("progressive") {
+        let mut paytable_id: Option<String> = None;
+        let mut opts = progressive::ProgressiveOptions {
+            field: "royal_flush".to_string(),
+            grid: None,
+            hands: Vec::new(),
+            top: 25,
+            output_dir: "./strategies/progressive".to_string(),
+            write_strategies: true,
+        };
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--field" && i + 1 < args.len() {
+                opts.field = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--grid" && i + 1 < args.len() {
+                let parts: Vec<f64> = args[i + 1].split(':').filter_map(|p| p.parse().ok()).collect();
+                if parts.len() != 3 {
+                    eprintln!("--grid expects START:END:STEP, got '{}'", args[i + 1]);
+                    std::process::exit(1);
+                }
+                opts.grid = Some((parts[0], parts[1], parts[2]));
+                i += 2;
+            } else if args[i] == "--hands" && i + 1 < args.len() {
+                opts.hands = args[i + 1].split(',').map(|k| k.trim().to_string()).collect();
+                i += 2;
+            } else if args[i] == "--top" && i + 1 < args.len() {
+                opts.top = args[i + 1].parse().unwrap_or(25);
+                i += 2;
+            } else if args[i] == "--output" && i + 1 < args.len() {
+                opts.output_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--no-strategies" {
+                opts.write_strategies = false;
+                i += 1;
+            } else if !args[i].starts_with("--") {
+                paytable_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match paytable_id {
+            Some(id) => progressive::run_progressive(&id, &opts),
+            None => {
+                eprintln!("Usage: vp_calculator progressive <paytable-id> [--field NAME] [--grid START:END:STEP]");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for overlay mode
+    if args.get(1).map(|s| s.as_str()) == Some("overlay") {
+        let mut strategies_dir = "./strategies".to_string();
+        let mut paytable_id: Option<String> = None;
+        let mut overlay_ids: Vec<String> = Vec::new();
+        let mut overlay_files: Vec<String> = Vec::new();
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--strategies" && i + 1 < args.len() {
+                strategies_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--overlay" && i + 1 < args.len() {
+                overlay_ids.extend(args[i + 1].split(',').map(|s| s.trim().to_string()));
+                i += 2;
+            } else if args[i] == "--overlay-file" && i + 1 < args.len() {
+                overlay_files.push(args[i + 1].clone());
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                paytable_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match paytable_id {
+            Some(id) => overlay::run_overlay(&id, &strategies_dir, &overlay_ids, &overlay_files),
+            None => {
+                eprintln!("Usage: vp_calculator overlay <paytable-id> [--overlay IDS] [--overlay-file PATH]");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for WWW blend mode
+    if args.get(1).map(|s| s.as_str()) == Some("www-blend") {
+        let mut strategies_dir = "./strategies".to_string();
+        let mut base_id: Option<String> = None;
+        let mut odds: Option<[f64; 4]> = None;
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--strategies" && i + 1 < args.len() {
+                strategies_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--wilds" && i + 1 < args.len() {
+                let values: Vec<f64> = args[i + 1].split(',').filter_map(|v| v.trim().parse().ok()).collect();
+                match <[f64; 4]>::try_from(values) {
+                    Ok(o) => odds = Some(o),
+                    Err(_) => {
+                        eprintln!("--wilds expects four probabilities, e.g. 0.4,0.19,0.21,0.2");
+                        std::process::exit(1);
+                    }
+                }
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                base_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        match base_id {
+            Some(id) => www::run_www_blend(&id, &strategies_dir, odds),
+            None => {
+                eprintln!("Usage: vp_calculator www-blend <base-paytable-id> [--wilds P0,P1,P2,P3] [--strategies DIR]");
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Check for double-up mode
+    if args.get(1).map(|s| s.as_str()) == Some("double-up") {
+        let mut strategies_dir = "./strategies".to_string();
+        let mut paytable_id: Option<String> = None;
+        let mut policies: Vec<doubleup::DoubleUpPolicy> = Vec::new();
+        let mut rules = doubleup::DoubleUpRules::default();
+        let mut i = 2;
+        while i < args.len() {
+            if args[i] == "--strategies" && i + 1 < args.len() {
+                strategies_dir = args[i + 1].clone();
+                i += 2;
+            } else if args[i] == "--policy" && i + 1 < args.len() {
+                for spec in args[i + 1].split(',') {
+                    match doubleup::DoubleUpPolicy::parse(spec.trim()) {
+                        Ok(p) => policies.push(p),
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                i += 2;
+            } else if args[i] == "--max-doubles" && i + 1 < args.len() {
+                rules.max_doubles = args[i + 1].parse().unwrap_or_else(|_| {
+                    eprintln!("Invalid --max-doubles: {}", args[i + 1]);
+                    std::process::exit(1);
+                });
+                i += 2;
+            } else if args[i] == "--tie-prob" && i + 1 < args.len() {
+                rules.tie_probability = match args[i + 1].parse::<f64>() {
+                    Ok(p) if (0.0..1.0).contains(&p) => p,
+                    _ => {
+                        eprintln!("Invalid --tie-prob: {} (expected 0 <= P < 1)", args[i + 1]);
+                        std::process::exit(1);
+                    }
+                };
+                i += 2;
+            } else if !args[i].starts_with("--") {
+                paytable_id = Some(args[i].clone());
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+        if policies.is_empty() {
+            policies = vec![
+                doubleup::DoubleUpPolicy::StopAt(1),
+                doubleup::DoubleUpPolicy::StopAt(2),
+                doubleup::DoubleUpPolicy::Always,
+            ];
+        }
+        match paytable_id {
+            Some(id) => doubleup::run_double_up(&id, &strategies_dir, &policies, &rules),
+            None => {
+                eprintln!("Usage: vp_calculator double-up <paytable-id> [--policy P,...] [--max-doubles N] [--tie-prob P]");
+                std::process::exit(1);
+            }
+        }
         return;
     }
 
@@ -6213,6 +8857,7 @@ fn main() {
     let mut paytable_id = String::new();
     let mut no_upload = false;
     let mut output_dir = "../../supabase-uploads".to_string();
+    let mut coins = 5u8;
 
     let mut i = 1;
     while i < args.len() {
@@ -6224,6 +8869,11 @@ fn main() {
             if i < args.len() {
                 output_dir = args[i].clone();
             }
+        } else if arg == "--coins" {
+            i += 1;
+            if i < args.len() {
+                coins = parse_coins(&args[i]);
+            }
         } else if !arg.starts_with("--") {
             paytable_id = arg.clone();
         }
@@ -6231,11 +8881,19 @@ fn main() {
     }
 
     if paytable_id.is_empty() {
-        eprintln!("Usage: vp_calculator <paytable-id> [--no-upload] [--output DIR]");
+        eprintln!("Usage: vp_calculator <paytable-id> [--no-upload] [--output DIR] [--coins N]");
         eprintln!("Run 'vp_calculator list' to see available paytables");
         std::process::exit(1);
     }
 
+    paytable_id = match coin_paytable_id(&paytable_id, coins) {
+        Some(id) => id,
+        None => {
+            eprintln!("{} is only played at max coins", paytable_id);
+            std::process::exit(1);
+        }
+    };
+
     let paytable = match get_paytable(&paytable_id) {
         Some(pt) => pt,
         None => {
@@ -6244,6 +8902,10 @@ fn main() {
             std::process::exit(1);
         }
     };
+    if let Err(e) = paytable.validate() {
+        eprintln!("Invalid paytable: {}", e);
+        std::process::exit(1);
+    }
 
     println!("=== Video Poker Strategy Calculator ===\n");
     println!("Paytable: {} ({})", paytable.name, paytable.id);
@@ -0,0 +1,374 @@
+// ============================================================================
+// CATEGORY A OVERLAYS
+// ============================================================================
+//
+// Overlays such as Super Times Pay or Hot Roll change what a hand pays but not
+// which cards to hold (see docs/VARIANTS.md), so their return and variance follow
+// directly from the base game's hand distribution under its own optimal strategy.
+// Every figure below is per unit wagered, including the overlay's extra bet.
+
+use super::*;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MultiplierOdds {
+    pub multiplier: f64,
+    pub probability: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StreakAward {
+    /// Consecutive winning hands needed (the award is paid on this hand)
+    pub wins: u32,
+    /// Flat award in per-coin payout units
+    pub award: f64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OverlayDef {
+    pub id: String,
+    pub name: String,
+    /// Total wager as a multiple of the base game's wager (Super Times Pay: 6 coins / 5 = 1.2)
+    pub bet_cost: f64,
+    /// Random multiplier drawn independently of the cards; probabilities sum to 1
+    #[serde(default)]
+    pub multipliers: Vec<MultiplierOdds>,
+    /// Hand types (as named by HandType::name) the multiplier applies to; empty = every paying hand
+    #[serde(default)]
+    pub trigger_hands: Vec<String>,
+    /// Flat awards for runs of consecutive paying hands
+    #[serde(default)]
+    pub streak_awards: Vec<StreakAward>,
+    /// Lines sharing one deal and one hold, each drawing from its own deck (Spin Poker, N-play).
+    /// Return and hit frequency are per line; the standard deviation is per unit wagered
+    /// across all lines of a deal.
+    #[serde(default = "default_lines")]
+    pub lines: u32,
+}
+
+fn default_lines() -> u32 {
+    1
+}
+
+/// Built-in overlay definitions. Casino configurations vary; the odds here are the
+/// representative ones documented in docs/VARIANTS.md. Use --overlay-file for others.
+pub fn builtin_overlays() -> Vec<OverlayDef> {
+    let odds = |table: &[(f64, f64)]| -> Vec<MultiplierOdds> {
+        table.iter().map(|&(multiplier, probability)| MultiplierOdds { multiplier, probability }).collect()
+    };
+    // Two dice: sum 2..=12 with 1..6..1 ways out of 36
+    let dice: Vec<(f64, f64)> = (2..=12i32)
+        .map(|sum| (sum as f64, (6 - (sum - 7).abs()) as f64 / 36.0))
+        .collect();
+    let hot_roll_trigger = 1.0 / 6.0;
+    let mut hot_roll = vec![(1.0, 1.0 - hot_roll_trigger)];
+    hot_roll.extend(dice.iter().map(|&(m, p)| (m, p * hot_roll_trigger)));
+
+    vec![
+        OverlayDef {
+            id: "super-times-pay".to_string(),
+            name: "Super Times Pay".to_string(),
+            bet_cost: 1.2,
+            multipliers: odds(&[
+                (1.0, 0.9015),
+                (2.0, 0.05),
+                (3.0, 0.025),
+                (4.0, 0.0125),
+                (5.0, 0.006),
+                (8.0, 0.003),
+                (10.0, 0.002),
+            ]),
+            trigger_hands: Vec::new(),
+            streak_awards: Vec::new(),
+            lines: 1,
+        },
+        OverlayDef {
+            id: "hot-roll".to_string(),
+            name: "Hot Roll".to_string(),
+            bet_cost: 2.0,
+            multipliers: odds(&hot_roll),
+            trigger_hands: Vec::new(),
+            streak_awards: Vec::new(),
+            lines: 1,
+        },
+        OverlayDef {
+            id: "spin-poker".to_string(),
+            name: "Spin Poker (9 lines)".to_string(),
+            bet_cost: 1.0,
+            multipliers: Vec::new(),
+            trigger_hands: Vec::new(),
+            streak_awards: Vec::new(),
+            lines: 9,
+        },
+        OverlayDef {
+            id: "bonus-streak".to_string(),
+            name: "Bonus Streak".to_string(),
+            bet_cost: 1.1,
+            multipliers: Vec::new(),
+            trigger_hands: Vec::new(),
+            streak_awards: vec![
+                StreakAward { wins: 3, award: 1.0 },
+                StreakAward { wins: 4, award: 1.0 },
+                StreakAward { wins: 5, award: 2.0 },
+            ],
+            lines: 1,
+        },
+    ]
+}
+
+fn load_overlay_file(path: &str) -> Result<OverlayDef, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    serde_json::from_str(&contents).map_err(|e| format!("Failed to parse {}: {}", path, e))
+}
+
+impl OverlayDef {
+    fn validate(&self) -> Result<(), String> {
+        if self.bet_cost <= 0.0 {
+            return Err(format!("{}: bet_cost must be positive", self.id));
+        }
+        if self.lines == 0 {
+            return Err(format!("{}: lines must be at least 1", self.id));
+        }
+        if self.lines > 1 && (!self.multipliers.is_empty() || !self.streak_awards.is_empty()) {
+            return Err(format!("{}: multi-line overlays cannot also have multipliers or streak awards", self.id));
+        }
+        if !self.multipliers.is_empty() {
+            let total: f64 = self.multipliers.iter().map(|m| m.probability).sum();
+            if (total - 1.0).abs() > 1e-6 {
+                return Err(format!("{}: multiplier probabilities sum to {}, expected 1", self.id, total));
+            }
+            if self.multipliers.iter().any(|m| m.probability < 0.0 || m.multiplier < 0.0) {
+                return Err(format!("{}: multipliers and probabilities must be non-negative", self.id));
+            }
+        }
+        Ok(())
+    }
+
+    /// Multiplier odds, with an implicit 1x when the overlay has no table
+    fn multiplier_odds(&self) -> Vec<MultiplierOdds> {
+        if self.multipliers.is_empty() {
+            vec![MultiplierOdds { multiplier: 1.0, probability: 1.0 }]
+        } else {
+            self.multipliers.clone()
+        }
+    }
+
+    fn triggers(&self, hand_type: &str) -> bool {
+        self.trigger_hands.is_empty() || self.trigger_hands.iter().any(|h| h == hand_type)
+    }
+}
+
+#[derive(Serialize)]
+pub struct MultiplierStats {
+    pub multiplier: f64,
+    /// Probability a hand pays with this multiplier applied
+    pub hit_frequency: f64,
+    /// Return per unit wagered paid on hands at this multiplier (part of net_return, not a share of it)
+    pub return_contribution: f64,
+}
+
+#[derive(Serialize)]
+pub struct OverlayResult {
+    pub overlay_id: String,
+    pub base_return: f64,
+    pub base_std_dev: f64,
+    pub net_return: f64,
+    pub std_dev: f64,
+    pub hit_frequency: f64,
+    pub streak_return: f64,
+    pub multipliers: Vec<MultiplierStats>,
+}
+
+/// Variance of a deal's expected pay under the strategy's holds. Lines sharing a deal hold
+/// the same cards, so this part of a line's variance is common to all of them; the rest
+/// comes from the independent draws.
+fn shared_variance(paytable: &Paytable, holds: &HashMap<String, u8>, canonical_hands: &[(String, Hand, u64)]) -> f64 {
+    let deck = paytable.deck_cards();
+    let table = handtable::hand_table(paytable);
+    let (total, mean, mean_sq) = canonical_hands
+        .par_iter()
+        .map(|(key, hand, multiplicity)| {
+            let ev = calculate_hold_ev(hand, holds.get(key).copied().unwrap_or(0), paytable, &table, &deck);
+            let m = *multiplicity as f64;
+            (m, m * ev, m * ev * ev)
+        })
+        .reduce(|| (0.0, 0.0, 0.0), |a, b| (a.0 + b.0, a.1 + b.1, a.2 + b.2));
+    let mean = mean / total;
+    (mean_sq / total - mean * mean).max(0.0)
+}
+
+/// Net return, standard deviation and per-multiplier hit frequency of an overlay on top of
+/// a base distribution from compute_distribution (hand type -> (probability, payout, order)).
+/// `shared_variance` is the per-deal part of the base game's variance (see shared_variance);
+/// it only matters for overlays with more than one line.
+///
+/// Streak awards use the stationary probability that a hand is the k-th win in a row,
+/// (1 - q) * q^k for win rate q; the correlation between consecutive hands is ignored
+/// in the standard deviation.
+pub fn evaluate_overlay(
+    distribution: &HashMap<String, (f64, f64, usize)>,
+    overlay: &OverlayDef,
+    shared_variance: f64,
+) -> OverlayResult {
+    let odds = overlay.multiplier_odds();
+    let mean_mult: f64 = odds.iter().map(|m| m.multiplier * m.probability).sum();
+    let mean_mult_sq: f64 = odds.iter().map(|m| m.multiplier * m.multiplier * m.probability).sum();
+
+    let mut base_return = 0.0;
+    let mut base_second = 0.0;
+    let mut gross = 0.0;
+    let mut second = 0.0;
+    let mut win_prob = 0.0;
+    let mut triggered_prob = 0.0;
+    let mut triggered_pay = 0.0;
+
+    for (hand_type, &(prob, payout, _)) in distribution {
+        if payout <= 0.0 {
+            continue;
+        }
+        win_prob += prob;
+        base_return += prob * payout;
+        base_second += prob * payout * payout;
+        if overlay.triggers(hand_type) {
+            triggered_prob += prob;
+            triggered_pay += prob * payout;
+            gross += prob * payout * mean_mult;
+            second += prob * payout * payout * mean_mult_sq;
+        } else {
+            gross += prob * payout;
+            second += prob * payout * payout;
+        }
+    }
+
+    // Streak awards land on winning hands; treat the award as independent of which win it was
+    let mean_win_pay = if win_prob > 0.0 { gross / win_prob } else { 0.0 };
+    let mut streak_return = 0.0;
+    for award in &overlay.streak_awards {
+        let p = (1.0 - win_prob) * win_prob.powi(award.wins as i32);
+        streak_return += p * award.award;
+        second += p * (award.award * award.award + 2.0 * award.award * mean_win_pay);
+    }
+    gross += streak_return;
+
+    let cost = overlay.bet_cost;
+    let net_return = gross / cost;
+    let mut variance = second / (cost * cost) - net_return * net_return;
+    if overlay.lines > 1 {
+        // The draws average out across lines; the deal's expected pay doesn't
+        let shared = shared_variance / (cost * cost);
+        variance = (variance - shared) / overlay.lines as f64 + shared;
+    }
+
+    let multipliers = odds.iter()
+        .map(|m| MultiplierStats {
+            multiplier: m.multiplier,
+            hit_frequency: triggered_prob * m.probability,
+            return_contribution: triggered_pay * m.probability * m.multiplier / cost,
+        })
+        .collect();
+
+    OverlayResult {
+        overlay_id: overlay.id.clone(),
+        base_return,
+        base_std_dev: (base_second - base_return * base_return).max(0.0).sqrt(),
+        net_return,
+        std_dev: variance.max(0.0).sqrt(),
+        hit_frequency: win_prob,
+        streak_return: streak_return / cost,
+        multipliers,
+    }
+}
+
+pub fn run_overlay(paytable_id: &str, strategies_dir: &str, overlay_ids: &[String], overlay_files: &[String]) {
+    let paytable = match get_paytable(paytable_id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown paytable: {}", paytable_id);
+            std::process::exit(1);
+        }
+    };
+    // Overlay bets are priced off the base game's wager, which Quick Quads already raises
+    if paytable.quick_quads.is_some() {
+        eprintln!("Overlays are priced against the base game's wager; {} carries the Quick Quads side bet", paytable.id);
+        std::process::exit(1);
+    }
+
+    let mut overlays: Vec<OverlayDef> = builtin_overlays()
+        .into_iter()
+        .filter(|o| (overlay_ids.is_empty() && overlay_files.is_empty()) || overlay_ids.contains(&o.id))
+        .collect();
+    for id in overlay_ids {
+        if !overlays.iter().any(|o| &o.id == id) {
+            eprintln!("Unknown overlay: {}", id);
+            std::process::exit(1);
+        }
+    }
+    for path in overlay_files {
+        match load_overlay_file(path) {
+            Ok(o) => overlays.push(o),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+    for o in &overlays {
+        if let Err(e) = o.validate() {
+            eprintln!("Invalid overlay {}", e);
+            std::process::exit(1);
+        }
+    }
+
+    let filename = format!("strategy_{}.vpstrat2", paytable.id.replace("-", "_"));
+    let strat_path = Path::new(strategies_dir).join(&filename);
+    let holds = match read_vpstrat2_holds(&strat_path.to_string_lossy()) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", strat_path.to_string_lossy(), e);
+            std::process::exit(1);
+        }
+    };
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          OVERLAY RETURN CALCULATOR                               ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Paytable: {} ({})", paytable.name, paytable.id);
+    println!();
+
+    let canonical = canonical_hands_with_multiplicity_for_paytable(&paytable);
+    print!("  Computing base distribution ({} canonical hands)... ", canonical.len());
+    io::stdout().flush().unwrap();
+    let (distribution, _) = compute_distribution(&paytable, &holds, &canonical);
+    let shared = if overlays.iter().any(|o| o.lines > 1) {
+        print!("\n  Computing per-deal variance for multi-line overlays... ");
+        io::stdout().flush().unwrap();
+        let shared = shared_variance(&paytable, &holds, &canonical);
+        println!("done");
+        shared
+    } else {
+        0.0
+    };
+
+    for overlay in &overlays {
+        let result = evaluate_overlay(&distribution, overlay, shared);
+        println!();
+        println!("  {} — bet {:.2}x{}", overlay.name, overlay.bet_cost,
+            if overlay.lines > 1 { format!(", {} lines (std deviation per unit wagered on all lines)", overlay.lines) } else { String::new() });
+        println!("  ┌────────────────────┬──────────────┬──────────────┐");
+        println!("  │                    │ Base game    │ With overlay │");
+        println!("  ├────────────────────┼──────────────┼──────────────┤");
+        println!("  │ Return             │ {:>11.4}% │ {:>11.4}% │", result.base_return * 100.0, result.net_return * 100.0);
+        println!("  │ Std deviation      │ {:>12.4} │ {:>12.4} │", result.base_std_dev, result.std_dev);
+        println!("  │ Hit frequency      │ {:>11.4}% │ {:>11.4}% │", result.hit_frequency * 100.0, result.hit_frequency * 100.0);
+        println!("  └────────────────────┴──────────────┴──────────────┘");
+        if overlay.multipliers.len() > 1 {
+            println!("    Multiplier   Hit frequency    Return contribution");
+            for m in &result.multipliers {
+                println!("    {:>9}x   {:>12.6}%   {:>18.4}%", m.multiplier, m.hit_frequency * 100.0, m.return_contribution * 100.0);
+            }
+        }
+        if result.streak_return > 0.0 {
+            println!("    Streak awards: {:.4}% of wager", result.streak_return * 100.0);
+        }
+    }
+}
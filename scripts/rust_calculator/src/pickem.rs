@@ -0,0 +1,219 @@
+// ============================================================================
+// PICK'EM POKER
+// ============================================================================
+//
+// Two cards are dealt that stay in the hand, plus two stacks of three cards with
+// only the top card showing. The player picks a stack and the final hand is the two
+// cards plus that whole stack. The only decision is which top card to take; the two
+// cards under the chosen stack are unseen and come uniformly from the 48 cards left.
+//
+// A decision point is four visible cards: the two kept cards and the two stack tops.
+// Those are canonicalized here (not with the five-card generator) and written in the
+// usual strategy formats with 8-character keys, kept cards first. A "hold" is the set
+// of key positions that end up in the hand: 0b0111 takes the first stack, 0b1011 the
+// second.
+
+use super::*;
+
+/// Key positions of the two kept cards and each stack choice
+pub const PICKEM_FIRST_STACK: u8 = 0b0111;
+pub const PICKEM_SECOND_STACK: u8 = 0b1011;
+
+pub fn pickem_paytable_ids() -> Vec<&'static str> {
+    vec!["pickem-full-pay"]
+}
+
+pub fn get_pickem_paytable(id: &str) -> Option<Paytable> {
+    match id {
+        "pickem-full-pay" => Some(Paytable {
+            id: id.to_string(),
+            name: "Pick'em Poker Full Pay".to_string(),
+            game_family: GameFamily::PickEm,
+            royal_flush: 1200.0, straight_flush: 239.8, four_of_a_kind: 120.0,
+            full_house: 18.0, flush: 15.0, straight: 11.0,
+            three_of_a_kind: 5.0, two_pair: 3.0, high_pair: 2.0,
+            four_aces: None, four_2_4: None, four_5_k: None, four_jqk: None,
+            four_8s: None, four_7s: None,
+            four_aces_with_kicker: None, four_2_4_with_kicker: None,
+            four_aces_with_face: None, four_jqk_with_face: None,
+            four_deuces: None, wild_royal: None, five_of_a_kind: None,
+            five_aces: None, five_2_4: None, five_5_k: None, five_jqk: None, five_5_10: None, five_deuces: None,
+            sequential_royal: None,
+            min_pair_rank: 7, // Nines or better
+            deck: None,
+            joker_mode: JokerMode::Wild,
+            quick_quads: None,
+            www: None,
+        }),
+        _ => None,
+    }
+}
+
+/// Canonical form of a decision point: the smallest (kept pair, stack pair) over all
+/// suit permutations, each pair sorted. Returns the 8-character key and the cards.
+fn pickem_canonical(kept: [Card; 2], tops: [Card; 2], perms: &[Vec<u8>]) -> (String, [Card; 4]) {
+    let mut best: Option<[Card; 4]> = None;
+    for perm in perms {
+        let map = |c: Card| Card(c.rank() * 4 + perm[c.suit() as usize]);
+        let mut k = [map(kept[0]), map(kept[1])];
+        let mut t = [map(tops[0]), map(tops[1])];
+        k.sort();
+        t.sort();
+        let cards = [k[0], k[1], t[0], t[1]];
+        if best.is_none_or(|b| cards < b) {
+            best = Some(cards);
+        }
+    }
+    let cards = best.unwrap();
+    (hand_to_positional_key(&cards), cards)
+}
+
+/// Every decision point, as (key, [kept, kept, top, top], number of deals)
+fn generate_pickem_states() -> Vec<(String, [Card; 4], u64)> {
+    let perms: Vec<Vec<u8>> = (0..4u8).permutations(4).collect();
+    let mut counts: HashMap<String, ([Card; 4], u64)> = HashMap::new();
+
+    for kept in (0..52u8).combinations(2) {
+        for tops in (0..52u8).filter(|c| !kept.contains(c)).combinations(2) {
+            let (key, cards) = pickem_canonical(
+                [Card(kept[0]), Card(kept[1])],
+                [Card(tops[0]), Card(tops[1])],
+                &perms,
+            );
+            counts.entry(key).or_insert((cards, 0)).1 += 1;
+        }
+    }
+
+    let mut states: Vec<(String, [Card; 4], u64)> = counts.into_iter()
+        .map(|(key, (cards, count))| (key, cards, count))
+        .collect();
+    states.sort_by(|a, b| a.0.cmp(&b.0));
+    states
+}
+
+/// EV of taking the stack topped by `top`: the two hidden cards are any two of the
+/// cards not showing.
+fn stack_ev(kept: [Card; 2], top: Card, other_top: Card, paytable: &Paytable, table: &handtable::HandTable) -> f64 {
+    let unseen: Vec<Card> = (0..52u8).map(Card)
+        .filter(|&c| c != kept[0] && c != kept[1] && c != top && c != other_top)
+        .collect();
+
+    let mut total = 0.0;
+    let mut count = 0u64;
+    for hidden in unseen.iter().combinations(2) {
+        let hand: Hand = [kept[0], kept[1], top, *hidden[0], *hidden[1]];
+        total += paytable.payout(table.hand_type(&hand));
+        count += 1;
+    }
+    total / count as f64
+}
+
+pub fn run_pickem(paytable_id: &str, output_dir: &str) {
+    let paytable = match get_pickem_paytable(paytable_id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown Pick'em paytable: {}", paytable_id);
+            eprintln!("Available: {}", pickem_paytable_ids().join(", "));
+            std::process::exit(1);
+        }
+    };
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          PICK'EM POKER                                           ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Paytable: {} ({})", paytable.name, paytable.id);
+    println!();
+
+    print!("  Generating decision points... ");
+    io::stdout().flush().unwrap();
+    let states = generate_pickem_states();
+    let total_deals: u64 = states.iter().map(|(_, _, n)| n).sum();
+    println!("{} canonical, {} total deals", states.len(), total_deals);
+
+    let table = handtable::hand_table(&paytable);
+    let calc_start = Instant::now();
+    let processed = AtomicUsize::new(0);
+    let total = states.len();
+    let strategies: HashMap<String, StrategyEntry> = states
+        .par_iter()
+        .map(|(key, cards, _)| {
+            let kept = [cards[0], cards[1]];
+            let first = stack_ev(kept, cards[2], cards[3], &paytable, &table);
+            let second = stack_ev(kept, cards[3], cards[2], &paytable, &table);
+            let (hold, ev) = if second > first {
+                (PICKEM_SECOND_STACK, second)
+            } else {
+                (PICKEM_FIRST_STACK, first)
+            };
+
+            let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            if count.is_multiple_of(5000) || count == total {
+                let elapsed = calc_start.elapsed().as_secs_f64();
+                let rate = count as f64 / elapsed;
+                let remaining = (total - count) as f64 / rate;
+                print!("    Progress: {:>6}/{} ({:>3}%) | {:.0}/s | ~{}s left    \r",
+                    count, total, count * 100 / total, rate, remaining as u64);
+                io::stdout().flush().unwrap();
+            }
+
+            let mut hold_evs = HashMap::new();
+            hold_evs.insert(PICKEM_FIRST_STACK.to_string(), (first * 1000000.0).round() / 1000000.0);
+            hold_evs.insert(PICKEM_SECOND_STACK.to_string(), (second * 1000000.0).round() / 1000000.0);
+            (key.clone(), StrategyEntry {
+                hold,
+                ev: (ev * 1000000.0).round() / 1000000.0,
+                hold_evs,
+            })
+        })
+        .collect();
+    println!();
+    println!("  Done in {:.1}s", calc_start.elapsed().as_secs_f64());
+
+    let total_return: f64 = states.iter()
+        .map(|(key, _, n)| *n as f64 * strategies[key].ev)
+        .sum::<f64>() / total_deals as f64;
+    // Decision points where the choice is worth anything at all
+    let (close, close_weight) = states.iter()
+        .filter(|(key, _, _)| {
+            let evs = &strategies[key].hold_evs;
+            let first = evs[&PICKEM_FIRST_STACK.to_string()];
+            let second = evs[&PICKEM_SECOND_STACK.to_string()];
+            (first - second).abs() > 1e-9
+        })
+        .fold((0usize, 0u64), |(c, w), (_, _, n)| (c + 1, w + n));
+
+    println!();
+    println!("  Return: {:.4}%", total_return * 100.0);
+    println!("  Decisions that matter: {} of {} ({:.2}% of deals)",
+        close, states.len(), close_weight as f64 / total_deals as f64 * 100.0);
+
+    let binary_v1 = generate_binary_strategy(&strategies, 0);
+    let binary_v2 = generate_binary_strategy_v2(&strategies, 0);
+    let output = StrategyFile {
+        game: paytable.name.clone(),
+        paytable_id: paytable.id.clone(),
+        version: 1,
+        generated: Utc::now().to_rfc3339(),
+        hand_count: strategies.len(),
+        strategies,
+    };
+    let json_string = serde_json::to_string(&output).expect("Failed to serialize");
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(json_string.as_bytes()).expect("Failed to compress");
+    let compressed = encoder.finish().expect("Failed to finish compression");
+
+    println!("\nSaving locally...");
+    match save_locally(&compressed, &paytable.id, output_dir) {
+        Ok(path) => println!("  ✓ JSON.gz: {}", path),
+        Err(e) => eprintln!("  ✗ Failed to save JSON.gz: {}", e),
+    }
+    match save_binary_strategy(&binary_v1, &paytable.id, output_dir) {
+        Ok(path) => println!("  ✓ Binary v1: {}", path),
+        Err(e) => eprintln!("  ✗ Failed to save binary v1: {}", e),
+    }
+    match save_binary_strategy_v2(&binary_v2, &paytable.id, output_dir) {
+        Ok(path) => println!("  ✓ Binary v2: {}", path),
+        Err(e) => eprintln!("  ✗ Failed to save binary v2: {}", e),
+    }
+}
@@ -0,0 +1,415 @@
+// ============================================================================
+// PROGRESSIVE JACKPOT ANALYSIS
+// ============================================================================
+//
+// Treats one payout (normally the royal) as a progressive meter. Every hold's EV
+// is linear in a single payout, so each hold is reduced once to
+//
+//     ev(m) = base_ev + hit_prob * (m - base_value)
+//
+// where hit_prob is the chance the hold finishes as the metered hand. The grid
+// sweep, the break-even search and the hold-switch points all work off these
+// lines, so no draws are re-enumerated per meter value.
+
+use super::*;
+
+/// EV lines for every hold of one canonical hand.
+struct MeterHand {
+    key: String,
+    multiplicity: u64,
+    base_ev: [f64; 32],
+    hit_prob: [f64; 32],
+}
+
+impl MeterHand {
+    fn ev(&self, hold: usize, delta: f64) -> f64 {
+        self.base_ev[hold] + self.hit_prob[hold] * delta
+    }
+
+    /// Best hold at `delta` above the base meter. Ties go to the lowest mask, as in analyze_hand.
+    fn best_hold(&self, delta: f64) -> (u8, f64) {
+        let mut best_hold = 0u8;
+        let mut best_ev = f64::NEG_INFINITY;
+        for hold in 0..32 {
+            let ev = self.ev(hold, delta);
+            if ev > best_ev {
+                best_ev = ev;
+                best_hold = hold as u8;
+            }
+        }
+        (best_hold, best_ev)
+    }
+
+    /// Meter values in (lo, hi] where the best hold changes, as (meter, from, to).
+    /// Walks the upper envelope of the 32 EV lines from `lo` upwards.
+    fn hold_switches(&self, base_value: f64, lo: f64, hi: f64) -> Vec<(f64, u8, u8)> {
+        let mut switches = Vec::new();
+        let mut delta = lo - base_value;
+        let (mut current, _) = self.best_hold(delta);
+
+        loop {
+            let cur = current as usize;
+            let mut next: Option<(f64, usize)> = None;
+            for k in 0..32 {
+                let slope_gain = self.hit_prob[k] - self.hit_prob[cur];
+                if slope_gain <= 1e-15 {
+                    continue;
+                }
+                let crossing = (self.base_ev[cur] - self.base_ev[k]) / slope_gain;
+                if crossing < delta - 1e-9 {
+                    continue;
+                }
+                let better = match next {
+                    None => true,
+                    Some((d, j)) => crossing < d - 1e-9
+                        || ((crossing - d).abs() <= 1e-9 && self.hit_prob[k] > self.hit_prob[j]),
+                };
+                if better {
+                    next = Some((crossing, k));
+                }
+            }
+
+            match next {
+                Some((d, k)) if d + base_value <= hi => {
+                    switches.push((d + base_value, current, k as u8));
+                    current = k as u8;
+                    delta = d;
+                }
+                _ => break,
+            }
+        }
+
+        switches
+    }
+}
+
+/// Reduce every canonical hand to per-hold EV lines in the metered payout.
+fn compute_meter_lines(
+    paytable: &Paytable,
+    field: &str,
+    canonical_hands: &[(String, Hand, u64)],
+) -> Result<Vec<MeterHand>, String> {
+    let base_value = paytable.payout_field(field)
+        .ok_or_else(|| format!("Paytable '{}' has no '{}' payout", paytable.id, field))?;
+    // Score each draw at the catalog value and one unit above; the difference is the hit rate.
+    let mut bumped = paytable.clone();
+    bumped.set_payout_field(field, base_value + 1.0)?;
+    let deck = paytable.deck_cards();
+    let tables = [handtable::hand_table(paytable), handtable::hand_table(&bumped)];
+
+    let processed = AtomicUsize::new(0);
+    let total = canonical_hands.len();
+    let calc_start = Instant::now();
+
+    let lines = canonical_hands
+        .par_iter()
+        .map(|(key, hand, multiplicity)| {
+            let mut base_ev = [0.0; 32];
+            let mut hit_prob = [0.0; 32];
+            for hold in 0..32u8 {
+                let evs = calculate_hold_ev_multi(hand, hold, &[paytable, &bumped], &[&tables[0], &tables[1]], &deck);
+                base_ev[hold as usize] = evs[0];
+                hit_prob[hold as usize] = evs[1] - evs[0];
+            }
+
+            let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            if count.is_multiple_of(5000) || count == total {
+                let elapsed = calc_start.elapsed().as_secs_f64();
+                let rate = count as f64 / elapsed;
+                let remaining = (total - count) as f64 / rate;
+                print!("    Progress: {:>6}/{} ({:>3}%) | {:.0}/s | ~{}s left    \r",
+                    count, total, count * 100 / total, rate, remaining as u64);
+                io::stdout().flush().unwrap();
+            }
+
+            MeterHand { key: key.clone(), multiplicity: *multiplicity, base_ev, hit_prob }
+        })
+        .collect();
+    println!();
+
+    Ok(lines)
+}
+
+/// Overall return when every hand is played optimally at `meter`, per coin wagered.
+fn return_at(hands: &[MeterHand], base_value: f64, meter: f64, wagered: f64) -> f64 {
+    let delta = meter - base_value;
+    hands.iter()
+        .map(|h| h.multiplicity as f64 * h.best_hold(delta).1)
+        .sum::<f64>() / wagered
+}
+
+/// Smallest meter value at which optimal play returns 100%.
+/// Return is non-decreasing in the meter, so a bracketing bisection is enough.
+fn find_break_even(hands: &[MeterHand], base_value: f64, wagered: f64) -> Option<f64> {
+    if return_at(hands, base_value, 0.0, wagered) >= 1.0 {
+        return Some(0.0);
+    }
+
+    let mut lo = 0.0;
+    let mut hi = base_value.max(1.0);
+    while return_at(hands, base_value, hi, wagered) < 1.0 {
+        lo = hi;
+        hi *= 2.0;
+        if hi > base_value.max(1.0) * 1000.0 {
+            return None;
+        }
+    }
+
+    for _ in 0..60 {
+        let mid = (lo + hi) / 2.0;
+        if return_at(hands, base_value, mid, wagered) >= 1.0 {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Some(hi)
+}
+
+/// Held cards for a hold mask, read off the canonical key (bit i = i-th card of the key).
+fn key_hold_cards(key: &str, hold: u8) -> String {
+    let cards: Vec<&str> = (0..key.len() / 2)
+        .filter(|i| hold & (1 << i) != 0)
+        .map(|i| &key[i * 2..i * 2 + 2])
+        .collect();
+    if cards.is_empty() { "(discard all)".to_string() } else { cards.join(" ") }
+}
+
+/// Build the strategy map for one meter value, in the same shape generate_strategy_file produces.
+fn strategies_at(hands: &[MeterHand], base_value: f64, meter: f64) -> HashMap<String, StrategyEntry> {
+    let delta = meter - base_value;
+    hands.iter()
+        .map(|h| {
+            let (best_hold, best_ev) = h.best_hold(delta);
+            let hold_evs = (0..32)
+                .map(|hold| (hold.to_string(), (h.ev(hold, delta) * 1000000.0).round() / 1000000.0))
+                .collect();
+            (h.key.clone(), StrategyEntry {
+                hold: best_hold,
+                ev: (best_ev * 1000000.0).round() / 1000000.0,
+                hold_evs,
+            })
+        })
+        .collect()
+}
+
+#[derive(Serialize)]
+struct GridPoint {
+    meter: f64,
+    return_pct: f64,
+    changed_hands: usize,
+    changed_frequency: f64,
+}
+
+#[derive(Serialize)]
+struct HoldSwitch {
+    meter: f64,
+    from_hold: u8,
+    to_hold: u8,
+    from_cards: String,
+    to_cards: String,
+}
+
+#[derive(Serialize)]
+struct HandSwitches {
+    hand: String,
+    frequency: f64,
+    switches: Vec<HoldSwitch>,
+}
+
+#[derive(Serialize)]
+struct ProgressiveReport {
+    paytable_id: String,
+    field: String,
+    base_value: f64,
+    base_return_pct: f64,
+    break_even_meter: Option<f64>,
+    grid: Vec<GridPoint>,
+    hands: Vec<HandSwitches>,
+}
+
+pub struct ProgressiveOptions {
+    pub field: String,
+    /// (start, end, step) in per-coin payout units; None = base value to 3x base in steps of 0.2x (11 points)
+    pub grid: Option<(f64, f64, f64)>,
+    /// Canonical keys to report switch points for; empty = the most frequent switching hands
+    pub hands: Vec<String>,
+    pub top: usize,
+    pub output_dir: String,
+    pub write_strategies: bool,
+}
+
+pub fn run_progressive(paytable_id: &str, opts: &ProgressiveOptions) {
+    let paytable = match get_paytable(paytable_id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown paytable: {}", paytable_id);
+            std::process::exit(1);
+        }
+    };
+    let base_value = match paytable.payout_field(&opts.field) {
+        Some(v) => v,
+        None => {
+            eprintln!("Paytable '{}' has no '{}' payout", paytable_id, opts.field);
+            std::process::exit(1);
+        }
+    };
+
+    let (start, end, step) = opts.grid.unwrap_or((base_value, base_value * 3.0, base_value * 0.2));
+    if step <= 0.0 || end < start {
+        eprintln!("Invalid grid {}:{}:{}", start, end, step);
+        std::process::exit(1);
+    }
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          PROGRESSIVE METER ANALYSIS                              ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Paytable: {} ({})", paytable.name, paytable.id);
+    println!("Meter: {} (catalog value {})", opts.field, base_value);
+    println!("Grid: {} to {} step {}", start, end, step);
+    println!();
+
+    let canonical = canonical_hands_with_multiplicity_for_paytable(&paytable);
+    let total_dealt: f64 = canonical.iter().map(|(_, _, m)| *m as f64).sum();
+    // Payouts are per coin of the base game; side bets like Quick Quads cost extra
+    let wagered = total_dealt * paytable.bet_cost();
+
+    println!("  Computing hold EV lines ({} canonical hands)...", canonical.len());
+    let calc_start = Instant::now();
+    let hands = match compute_meter_lines(&paytable, &opts.field, &canonical) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("  Done in {:.1}s", calc_start.elapsed().as_secs_f64());
+
+    let base_return = return_at(&hands, base_value, base_value, wagered);
+    let base_holds: Vec<u8> = hands.iter().map(|h| h.best_hold(0.0).0).collect();
+
+    // Index the grid rather than accumulating the step, so fractional steps don't drift
+    let points = ((end - start) / step + 1e-9).floor() as usize + 1;
+    let meters: Vec<f64> = (0..points).map(|i| start + i as f64 * step).collect();
+
+    println!();
+    println!("  ┌──────────────┬──────────────┬────────────────┬──────────────┐");
+    println!("  │ Meter        │ Return       │ Changed hands  │ Dealt freq   │");
+    println!("  ├──────────────┼──────────────┼────────────────┼──────────────┤");
+    let mut grid = Vec::new();
+    for &meter in &meters {
+        let delta = meter - base_value;
+        let mut changed = 0usize;
+        let mut changed_weight = 0.0;
+        let mut total_ev = 0.0;
+        for (h, &base_hold) in hands.iter().zip(&base_holds) {
+            let (hold, ev) = h.best_hold(delta);
+            total_ev += h.multiplicity as f64 * ev;
+            if hold != base_hold {
+                changed += 1;
+                changed_weight += h.multiplicity as f64;
+            }
+        }
+        let point = GridPoint {
+            meter,
+            return_pct: total_ev / wagered * 100.0,
+            changed_hands: changed,
+            changed_frequency: changed_weight / total_dealt,
+        };
+        println!("  │ {:>12.2} │ {:>11.4}% │ {:>14} │ {:>12.6} │",
+            point.meter, point.return_pct, point.changed_hands, point.changed_frequency);
+        grid.push(point);
+
+        if opts.write_strategies {
+            let strategies = strategies_at(&hands, base_value, meter);
+            let grid_id = format!("{}-{}-{:.2}", paytable.id, opts.field, meter);
+            let binary = generate_binary_strategy_v2(&strategies, paytable.num_jokers());
+            if let Err(e) = save_binary_strategy_v2(&binary, &grid_id, &opts.output_dir) {
+                eprintln!("  ✗ Failed to save strategy for meter {}: {}", meter, e);
+            }
+        }
+    }
+    println!("  └──────────────┴──────────────┴────────────────┴──────────────┘");
+
+    let break_even = find_break_even(&hands, base_value, wagered);
+    println!();
+    println!("  Return at catalog value: {:.4}%", base_return * 100.0);
+    match break_even {
+        Some(v) if v <= 0.0 => println!("  Break-even meter: game returns 100%+ at any meter value"),
+        Some(v) => println!("  Break-even meter: {:.2} ({:.2}x catalog)", v, v / base_value),
+        None => println!("  Break-even meter: not reached below {:.0}", base_value.max(1.0) * 1000.0),
+    }
+
+    // Hold switch points across the grid range
+    let selected: Vec<&MeterHand> = if opts.hands.is_empty() {
+        let mut switching: Vec<&MeterHand> = hands.iter()
+            .filter(|h| !h.hold_switches(base_value, start, end).is_empty())
+            .collect();
+        switching.sort_by(|a, b| b.multiplicity.cmp(&a.multiplicity).then(a.key.cmp(&b.key)));
+        switching
+    } else {
+        opts.hands.iter()
+            .filter_map(|k| {
+                let found = hands.iter().find(|h| &h.key == k);
+                if found.is_none() {
+                    eprintln!("  ⚠ Canonical hand not found: {}", k);
+                }
+                found
+            })
+            .collect()
+    };
+
+    let mut hand_reports = Vec::new();
+    for h in &selected {
+        let switches = h.hold_switches(base_value, start, end);
+        hand_reports.push(HandSwitches {
+            hand: h.key.clone(),
+            frequency: h.multiplicity as f64 / total_dealt,
+            switches: switches.iter().map(|&(meter, from, to)| HoldSwitch {
+                meter,
+                from_hold: from,
+                to_hold: to,
+                from_cards: key_hold_cards(&h.key, from),
+                to_cards: key_hold_cards(&h.key, to),
+            }).collect(),
+        });
+    }
+
+    println!();
+    println!("  Hold switches between {} and {}:", start, end);
+    for report in hand_reports.iter().take(opts.top) {
+        if report.switches.is_empty() {
+            println!("    {}  no change in range", report.hand);
+        }
+        for s in &report.switches {
+            println!("    {}  at {:>10.2}: {} -> {}", report.hand, s.meter, s.from_cards, s.to_cards);
+        }
+    }
+    if hand_reports.len() > opts.top {
+        println!("    ... {} more hands in the JSON report", hand_reports.len() - opts.top);
+    }
+
+    let report = ProgressiveReport {
+        paytable_id: paytable.id.clone(),
+        field: opts.field.clone(),
+        base_value,
+        base_return_pct: base_return * 100.0,
+        break_even_meter: break_even,
+        grid,
+        hands: hand_reports,
+    };
+    let filename = format!("progressive_{}_{}.json", paytable.id.replace("-", "_"), opts.field);
+    let path = Path::new(&opts.output_dir).join(&filename);
+    let written = fs::create_dir_all(&opts.output_dir)
+        .map_err(|e| e.to_string())
+        .and_then(|_| serde_json::to_string_pretty(&report).map_err(|e| e.to_string()))
+        .and_then(|json| fs::write(&path, json).map_err(|e| e.to_string()));
+    match written {
+        Ok(()) => println!("\n  ✓ Report: {}", path.to_string_lossy()),
+        Err(e) => {
+            eprintln!("\n  ✗ Failed to write report: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+// ============================================================================
+// RETURN SENSITIVITY
+// ============================================================================
+//
+// How the return moves with each payout field. For a fixed strategy the return is
+// linear in every pay, with slope the chance of finishing with a hand the field pays
+// for, so the marginal figure is exact for any change while the strategy stays put.
+// Re-optimizing can only add to that: as in progressive.rs each hold's EV is a line
+// in the field's value, and a ±1 unit change takes the best hold of every hand at the
+// new value. One draw enumeration per hold tallies the finishing hand types, and all
+// fields are scored from the same tallies.
+
+use super::*;
+
+/// Slots for per-hand-type tallies, indexed by `HandType as usize` (NaturalRoyalFlush is last)
+const HAND_TYPE_SLOTS: usize = HandType::NaturalRoyalFlush as usize + 1;
+
+/// Payout fields scored through hand types; the sequential royal depends on card order
+fn scored_fields(paytable: &Paytable) -> Vec<(&'static str, f64)> {
+    paytable.payout_fields().into_iter()
+        .filter(|(name, _)| *name != "sequential_royal")
+        .filter_map(|(name, value)| value.map(|v| (name, v)))
+        .collect()
+}
+
+#[derive(Serialize)]
+pub struct FieldSensitivity {
+    pub field: String,
+    pub value: f64,
+    /// Chance per hand of finishing with a hand this field pays, under the catalog strategy
+    pub hit_frequency: f64,
+    /// Return change per unit of pay with the strategy held fixed (percentage points)
+    pub marginal_pct: f64,
+    /// Return change for +1 and -1 unit with the strategy re-optimized (percentage points)
+    pub reoptimized_up_pct: f64,
+    pub reoptimized_down_pct: f64,
+    /// Share of dealt hands whose best hold changes at +1 and -1
+    pub changed_up: f64,
+    pub changed_down: f64,
+}
+
+#[derive(Serialize)]
+pub struct SensitivityReport {
+    pub paytable_id: String,
+    pub return_pct: f64,
+    pub fields: Vec<FieldSensitivity>,
+}
+
+/// One canonical hand's contribution to every field's figures, before weighting
+struct HandSensitivity {
+    multiplicity: f64,
+    base_ev: f64,
+    /// Per field: (slope of the base best hold, best EV at +1, best EV at -1, hold changes at +1, at -1)
+    fields: Vec<(f64, f64, f64, bool, bool)>,
+}
+
+/// Best hold of a set of EV lines at `delta`. Ties go to the lowest mask, as in analyze_hand.
+fn best_at(base_ev: &[f64; 32], slope: &[f64; 32], delta: f64) -> (usize, f64) {
+    let mut best = (0, f64::NEG_INFINITY);
+    for hold in 0..32 {
+        let ev = base_ev[hold] + slope[hold] * delta;
+        if ev > best.1 {
+            best = (hold, ev);
+        }
+    }
+    best
+}
+
+fn hand_sensitivity(
+    hand: &Hand,
+    multiplicity: u64,
+    paytable: &Paytable,
+    table: &handtable::HandTable,
+    deltas: &[[f64; HAND_TYPE_SLOTS]],
+    full_deck: &[Card],
+) -> HandSensitivity {
+    let deck: Vec<Card> = full_deck.iter().filter(|c| !hand.contains(c)).cloned().collect();
+    let mut base_ev = [0.0; 32];
+    let mut slopes = vec![[0.0; 32]; deltas.len()];
+    let mut seen: [Option<HandType>; HAND_TYPE_SLOTS] = [None; HAND_TYPE_SLOTS];
+
+    for hold in 0..32u8 {
+        let (mut final_hand, held) = held_cards(hand, hold);
+        let mut counts = [0u32; HAND_TYPE_SLOTS];
+        let mut draws = 0u32;
+        for draw in deck.iter().combinations(5 - held) {
+            for (slot, &card) in final_hand[held..].iter_mut().zip(&draw) {
+                *slot = *card;
+            }
+            let hand_type = table.hand_type(&final_hand);
+            counts[hand_type as usize] += 1;
+            seen[hand_type as usize] = Some(hand_type);
+            draws += 1;
+        }
+        let h = hold as usize;
+        for (slot, &count) in counts.iter().enumerate().filter(|(_, &c)| c > 0) {
+            let p = count as f64 / draws as f64;
+            if let Some(hand_type) = seen[slot] {
+                base_ev[h] += p * paytable.payout(hand_type);
+            }
+            for (slope, delta) in slopes.iter_mut().zip(deltas) {
+                slope[h] += p * delta[slot];
+            }
+        }
+    }
+
+    let (base_hold, best_ev) = best_at(&base_ev, &[0.0; 32], 0.0);
+    let fields = slopes.iter()
+        .map(|slope| {
+            let (up_hold, up_ev) = best_at(&base_ev, slope, 1.0);
+            let (down_hold, down_ev) = best_at(&base_ev, slope, -1.0);
+            (slope[base_hold], up_ev, down_ev, up_hold != base_hold, down_hold != base_hold)
+        })
+        .collect();
+    HandSensitivity { multiplicity: multiplicity as f64, base_ev: best_ev, fields }
+}
+
+pub fn run_sensitivity(paytable_id: &str, output_dir: &str) {
+    let paytable = match get_paytable(paytable_id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown paytable: {}", paytable_id);
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = paytable.validate() {
+        eprintln!("Invalid paytable: {}", e);
+        std::process::exit(1);
+    }
+    if paytable.is_www() || paytable.game_family == GameFamily::PickEm {
+        eprintln!("Sensitivity needs five-card holds from a fixed deck; {} is not supported", paytable.id);
+        std::process::exit(1);
+    }
+    let fields = scored_fields(&paytable);
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          RETURN SENSITIVITY                                      ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Paytable: {} ({})", paytable.name, paytable.id);
+    println!();
+
+    let canonical = canonical_hands_with_multiplicity_for_paytable(&paytable);
+    let total_dealt: f64 = canonical.iter().map(|(_, _, m)| *m as f64).sum();
+    let table = handtable::hand_table(&paytable);
+    let deck = paytable.deck_cards();
+
+    // Pay change of every hand type when a field goes up one unit. Fields can feed
+    // several hand types (five deuces falls back to four deuces), so this goes through
+    // Paytable::payout rather than a field-to-hand mapping.
+    let all_types: Vec<HandType> = {
+        let mut seen = [false; HAND_TYPE_SLOTS];
+        let mut types = Vec::new();
+        for draw in deck.iter().combinations(5) {
+            let hand_type = table.hand_type(&[*draw[0], *draw[1], *draw[2], *draw[3], *draw[4]]);
+            if !seen[hand_type as usize] {
+                seen[hand_type as usize] = true;
+                types.push(hand_type);
+            }
+        }
+        types
+    };
+    let mut deltas = Vec::with_capacity(fields.len());
+    for &(name, value) in &fields {
+        let mut bumped = paytable.clone();
+        if let Err(e) = bumped.set_payout_field(name, value + 1.0) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        let mut delta = [0.0; HAND_TYPE_SLOTS];
+        for &hand_type in &all_types {
+            delta[hand_type as usize] = bumped.payout(hand_type) - paytable.payout(hand_type);
+        }
+        deltas.push(delta);
+    }
+
+    println!("  Tallying draws for every hold ({} canonical hands, {} fields)...", canonical.len(), fields.len());
+    let calc_start = Instant::now();
+    let processed = AtomicUsize::new(0);
+    let total = canonical.len();
+    let hands: Vec<HandSensitivity> = canonical
+        .par_iter()
+        .map(|(_, hand, multiplicity)| {
+            let result = hand_sensitivity(hand, *multiplicity, &paytable, &table, &deltas, &deck);
+            let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            if count.is_multiple_of(5000) || count == total {
+                let elapsed = calc_start.elapsed().as_secs_f64();
+                let rate = count as f64 / elapsed;
+                let remaining = (total - count) as f64 / rate;
+                print!("    Progress: {:>6}/{} ({:>3}%) | {:.0}/s | ~{}s left    \r",
+                    count, total, count * 100 / total, rate, remaining as u64);
+                io::stdout().flush().unwrap();
+            }
+            result
+        })
+        .collect();
+    println!();
+    println!("  Done in {:.1}s", calc_start.elapsed().as_secs_f64());
+
+    // Payouts are per coin of the base game; side bets like Quick Quads cost extra
+    let scale = 100.0 / (total_dealt * paytable.bet_cost());
+    let base_return: f64 = hands.iter().map(|h| h.multiplicity * h.base_ev).sum::<f64>() * scale;
+    let results: Vec<FieldSensitivity> = fields.iter().enumerate()
+        .map(|(i, &(name, value))| {
+            let sum = |f: &dyn Fn(&HandSensitivity) -> f64| hands.iter().map(|h| h.multiplicity * f(h)).sum::<f64>();
+            let changed = |up: bool| hands.iter()
+                .filter(|h| if up { h.fields[i].3 } else { h.fields[i].4 })
+                .map(|h| h.multiplicity)
+                .sum::<f64>() / total_dealt;
+            let marginal = sum(&|h| h.fields[i].0) * scale;
+            // Hit frequency: the slope is per unit of pay on a one-coin wager
+            let unit = deltas[i].iter().cloned().fold(0.0, f64::max).max(1e-12);
+            FieldSensitivity {
+                field: name.to_string(),
+                value,
+                hit_frequency: marginal / 100.0 * paytable.bet_cost() / unit,
+                marginal_pct: marginal,
+                reoptimized_up_pct: sum(&|h| h.fields[i].1) * scale - base_return,
+                reoptimized_down_pct: sum(&|h| h.fields[i].2) * scale - base_return,
+                changed_up: changed(true),
+                changed_down: changed(false),
+            }
+        })
+        .collect();
+
+    println!();
+    println!("  Return: {:.4}%", base_return);
+    println!();
+    println!("  ┌───────────────────────┬─────────┬─────────────┬─────────────┬─────────────┬─────────────┐");
+    println!("  │ Field                 │ Pay     │ Marginal    │ +1 re-opt   │ -1 re-opt   │ Hit freq    │");
+    println!("  ├───────────────────────┼─────────┼─────────────┼─────────────┼─────────────┼─────────────┤");
+    for r in &results {
+        println!("  │ {:<21} │ {:>7} │ {:>+10.4}% │ {:>+10.4}% │ {:>+10.4}% │ {:>10.6}% │",
+            r.field, r.value, r.marginal_pct, r.reoptimized_up_pct, r.reoptimized_down_pct, r.hit_frequency * 100.0);
+    }
+    println!("  └───────────────────────┴─────────┴─────────────┴─────────────┴─────────────┴─────────────┘");
+    println!("  Marginal: strategy held fixed. Re-opt: best strategy at the new pay.");
+
+    let report = SensitivityReport {
+        paytable_id: paytable.id.clone(),
+        return_pct: base_return,
+        fields: results,
+    };
+    if let Err(e) = fs::create_dir_all(output_dir) {
+        eprintln!("\n  ✗ Failed to create {}: {}", output_dir, e);
+        return;
+    }
+    let report_path = Path::new(output_dir).join(format!("sensitivity_{}.json", paytable.id.replace('-', "_")));
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => match fs::write(&report_path, json) {
+            Ok(_) => println!("\n  ✓ Report: {}", report_path.display()),
+            Err(e) => eprintln!("\n  ✗ Failed to write report: {}", e),
+        },
+        Err(e) => eprintln!("\n  ✗ Failed to serialize report: {}", e),
+    }
+}
@@ -0,0 +1,259 @@
+// ============================================================================
+// SEQUENTIAL ROYAL (POSITIONAL STRATEGY)
+// ============================================================================
+//
+// With a sequential royal bonus the best hold can depend on where the cards sit, so
+// strategy is keyed by positional hands (hand_to_positional_key) instead of canonical
+// ones. There are ~13M positional hands against 134,459 canonical ones, but the bonus
+// only moves EV for holds whose cards all sit in their sequential position — every
+// other hold scores exactly as it does unordered. So each canonical hand is solved
+// once, each of its 120 orderings is adjusted by the bonus, and only orderings whose
+// best hold changes are written out. Positional hands missing from the file play the
+// canonical strategy for the same paytable.
+
+use super::*;
+
+/// Order-sensitive results for one canonical hand
+struct HandOrders {
+    multiplicity: u64,
+    /// Canonical best EV with no sequential bonus
+    base_ev: f64,
+    /// Mean over orderings of the canonical hold's EV, bonus included
+    blind_ev: f64,
+    /// Mean over orderings of the best positional hold's EV
+    positional_ev: f64,
+    /// Orderings (out of 120) where the best hold differs from the canonical one
+    changed_orders: usize,
+    /// (positional key, positional entry, canonical hold in positional form, EV gain)
+    overrides: Vec<(String, StrategyEntry, u8, f64)>,
+}
+
+/// Map a positional hold mask back to the canonical hand's mask
+fn canonical_mask(perm: &[usize], positional: u8) -> u8 {
+    (0..5).filter(|i| positional & (1 << i) != 0).fold(0u8, |acc, i| acc | (1 << perm[i]))
+}
+
+fn positional_mask(perm: &[usize], canonical: u8) -> u8 {
+    (0..5).filter(|&i| canonical & (1 << perm[i]) != 0).fold(0u8, |acc, i| acc | (1 << i))
+}
+
+fn solve_orders(hand: &Hand, multiplicity: u64, paytable: &Paytable, table: &handtable::HandTable, deck: &[Card], bonus: f64) -> HandOrders {
+    let remaining = deck.len() - 5;
+    let mut evs = [0.0; 32];
+    let mut canonical_best = 0u8;
+    for hold in 0..32u8 {
+        evs[hold as usize] = calculate_hold_ev(hand, hold, paytable, table, deck);
+        if evs[hold as usize] > evs[canonical_best as usize] {
+            canonical_best = hold;
+        }
+    }
+
+    let mut orders = HandOrders {
+        multiplicity,
+        base_ev: evs[canonical_best as usize],
+        blind_ev: 0.0,
+        positional_ev: 0.0,
+        changed_orders: 0,
+        overrides: Vec::new(),
+    };
+    let mut seen: HashSet<String> = HashSet::new();
+
+    for perm in (0..5).permutations(5) {
+        let ordered: Hand = [hand[perm[0]], hand[perm[1]], hand[perm[2]], hand[perm[3]], hand[perm[4]]];
+        let ev_of = |positional: u8| {
+            evs[canonical_mask(&perm, positional) as usize]
+                + bonus * sequential_royal_probability(&ordered, positional, remaining)
+        };
+
+        let blind_hold = positional_mask(&perm, canonical_best);
+        let blind_ev = ev_of(blind_hold);
+
+        // Only holds made entirely of cards in their sequential position can gain
+        let in_place = (0..5)
+            .filter(|&i| !ordered[i].is_joker() && ordered[i].rank() == SEQUENTIAL_ROYAL_RANKS[i])
+            .fold(0u8, |acc, i| acc | (1 << i));
+        let mut best = (blind_hold, blind_ev);
+        let mut sub = in_place;
+        loop {
+            let ev = ev_of(sub);
+            if ev > best.1 + 1e-12 {
+                best = (sub, ev);
+            }
+            if sub == 0 {
+                break;
+            }
+            sub = (sub - 1) & in_place;
+        }
+
+        orders.blind_ev += blind_ev / 120.0;
+        orders.positional_ev += best.1 / 120.0;
+        if best.0 != blind_hold {
+            orders.changed_orders += 1;
+            let key = hand_to_positional_key(&ordered);
+            if seen.insert(key.clone()) {
+                let hold_evs = (0..32u8)
+                    .map(|hold| (hold.to_string(), (ev_of(hold) * 1000000.0).round() / 1000000.0))
+                    .collect();
+                orders.overrides.push((key, StrategyEntry {
+                    hold: best.0,
+                    ev: (best.1 * 1000000.0).round() / 1000000.0,
+                    hold_evs,
+                }, blind_hold, best.1 - blind_ev));
+            }
+        }
+    }
+
+    orders
+}
+
+#[derive(Serialize)]
+struct PositionalChange {
+    hand: String,
+    positional_hold: String,
+    canonical_hold: String,
+    ev_gain: f64,
+}
+
+#[derive(Serialize)]
+struct SequentialReport {
+    paytable_id: String,
+    sequential_royal: f64,
+    base_return_pct: f64,
+    position_blind_return_pct: f64,
+    positional_return_pct: f64,
+    positional_hands: usize,
+    positional_frequency: f64,
+    largest_changes: Vec<PositionalChange>,
+}
+
+/// Held cards for a positional hold, read off the positional key
+fn hold_cards(key: &str, hold: u8) -> String {
+    let cards: Vec<&str> = (0..key.len() / 2)
+        .filter(|i| hold & (1 << i) != 0)
+        .map(|i| &key[i * 2..i * 2 + 2])
+        .collect();
+    if cards.is_empty() { "(discard all)".to_string() } else { cards.join(" ") }
+}
+
+pub fn run_sequential(paytable_id: &str, output_dir: &str, top: usize) {
+    let paytable = match get_paytable(paytable_id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown paytable: {}", paytable_id);
+            std::process::exit(1);
+        }
+    };
+    let pay = match paytable.sequential_royal {
+        Some(pay) => pay,
+        None => {
+            eprintln!("Paytable '{}' has no sequential royal; use '{}-seq<N>'", paytable.id, paytable.id);
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = paytable.validate() {
+        eprintln!("Invalid paytable: {}", e);
+        std::process::exit(1);
+    }
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          SEQUENTIAL ROYAL ANALYSIS                               ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Paytable: {} ({})", paytable.name, paytable.id);
+    println!("Royal: {} | Sequential royal (T-J-Q-K-A): {}", paytable.royal_flush, pay);
+    println!();
+
+    let canonical = canonical_hands_with_multiplicity_for_paytable(&paytable);
+    let total_dealt: f64 = canonical.iter().map(|(_, _, m)| *m as f64).sum();
+    let deck = paytable.deck_cards();
+    let table = handtable::hand_table(&paytable);
+    let bonus = pay - paytable.royal_flush;
+
+    println!("  Solving {} canonical hands x 120 orderings...", canonical.len());
+    let calc_start = Instant::now();
+    let processed = AtomicUsize::new(0);
+    let total = canonical.len();
+    let results: Vec<HandOrders> = canonical
+        .par_iter()
+        .map(|(_, hand, multiplicity)| {
+            let orders = solve_orders(hand, *multiplicity, &paytable, &table, &deck, bonus);
+            let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            if count.is_multiple_of(5000) || count == total {
+                let elapsed = calc_start.elapsed().as_secs_f64();
+                let rate = count as f64 / elapsed;
+                let remaining = (total - count) as f64 / rate;
+                print!("    Progress: {:>6}/{} ({:>3}%) | {:.0}/s | ~{}s left    \r",
+                    count, total, count * 100 / total, rate, remaining as u64);
+                io::stdout().flush().unwrap();
+            }
+            orders
+        })
+        .collect();
+    println!();
+    println!("  Done in {:.1}s", calc_start.elapsed().as_secs_f64());
+
+    let weighted = |f: fn(&HandOrders) -> f64| {
+        results.iter().map(|r| r.multiplicity as f64 * f(r)).sum::<f64>() / total_dealt
+    };
+    let base_return = weighted(|r| r.base_ev);
+    let blind_return = weighted(|r| r.blind_ev);
+    let positional_return = weighted(|r| r.positional_ev);
+    let changed_frequency = weighted(|r| r.changed_orders as f64 / 120.0);
+
+    let mut changes: Vec<(String, StrategyEntry, u8, f64)> = results.into_iter()
+        .flat_map(|r| r.overrides)
+        .collect();
+    changes.sort_by(|a, b| b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0)));
+
+    println!();
+    println!("  Return without the bonus:        {:.4}%", base_return * 100.0);
+    println!("  Return, canonical strategy:      {:.4}%", blind_return * 100.0);
+    println!("  Return, positional strategy:     {:.4}%", positional_return * 100.0);
+    println!("  Positional hands that change hold: {} ({:.6} of deals)", changes.len(), changed_frequency);
+
+    let largest_changes: Vec<PositionalChange> = changes.iter()
+        .take(top)
+        .map(|(key, entry, canonical_hold, gain)| PositionalChange {
+            hand: key.clone(),
+            positional_hold: hold_cards(key, entry.hold),
+            canonical_hold: hold_cards(key, *canonical_hold),
+            ev_gain: *gain,
+        })
+        .collect();
+    if !largest_changes.is_empty() {
+        println!();
+        println!("  Largest EV gains from holding by position:");
+        for c in &largest_changes {
+            println!("    {}  hold {:<16} instead of {:<16} +{:.4}", c.hand, c.positional_hold, c.canonical_hold, c.ev_gain);
+        }
+    }
+
+    let strategies: HashMap<String, StrategyEntry> = changes.into_iter()
+        .map(|(key, entry, _, _)| (key, entry))
+        .collect();
+    let positional_id = format!("{}-positional", paytable.id);
+    let binary = generate_binary_strategy_v2(&strategies, paytable.num_jokers());
+    match save_binary_strategy_v2(&binary, &positional_id, output_dir) {
+        Ok(path) => println!("\n  ✓ Positional strategy: {} ({} hands)", path, strategies.len()),
+        Err(e) => eprintln!("\n  ✗ Failed to save positional strategy: {}", e),
+    }
+
+    let report = SequentialReport {
+        paytable_id: paytable.id.clone(),
+        sequential_royal: pay,
+        base_return_pct: base_return * 100.0,
+        position_blind_return_pct: blind_return * 100.0,
+        positional_return_pct: positional_return * 100.0,
+        positional_hands: strategies.len(),
+        positional_frequency: changed_frequency,
+        largest_changes,
+    };
+    let report_path = Path::new(output_dir).join(format!("sequential_{}.json", paytable.id.replace('-', "_")));
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => match fs::write(&report_path, json) {
+            Ok(_) => println!("  ✓ Report: {}", report_path.display()),
+            Err(e) => eprintln!("  ✗ Failed to write report: {}", e),
+        },
+        Err(e) => eprintln!("  ✗ Failed to serialize report: {}", e),
+    }
+}
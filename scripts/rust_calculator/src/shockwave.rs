@@ -0,0 +1,421 @@
+// ============================================================================
+// SHOCKWAVE
+// ============================================================================
+//
+// Four of a kind starts a shockwave: the next N hands pay every line times a
+// multiplier. With retrigger on, four of a kind during the shockwave restarts
+// the count at N. The game is a Markov chain over states 0 (normal) and 1..=N
+// (shockwave hands remaining), and the hold decides both the payout and the
+// chance of moving to state N.
+//
+// Each hold is reduced once to its expected pay and its four of a kind chance;
+// everything else is scalar work on those lines. The solver is average-reward
+// policy iteration: for a fixed strategy per state, solve for the long-run return
+// g and each state's relative value h(s) (h(0) = 0); then re-pick every hold by
+//
+//     mult(s) * pay + quad_prob * (h(N) - h(next(s)))
+//
+// which is the hold's payout plus what a trigger is worth over carrying on. Repeat
+// until no hold changes. h(N) is the value of triggering from the normal state.
+
+use super::*;
+
+/// Policy iteration normally settles in a handful of rounds; this bounds a cycling policy
+const MAX_ITERATIONS: usize = 50;
+
+/// Per-hold expected pay and four of a kind probability for one canonical hand.
+struct HoldLines {
+    key: String,
+    multiplicity: u64,
+    pay: [f64; 32],
+    quad: [f64; 32],
+}
+
+pub struct ShockwaveOptions {
+    /// Hands in a shockwave
+    pub hands: usize,
+    /// Multiplier on every pay during a shockwave
+    pub multiplier: f64,
+    /// Four of a kind during a shockwave restarts it
+    pub retrigger: bool,
+    pub output_dir: String,
+    pub write_strategies: bool,
+}
+
+/// The paytable with every four of a kind line paying 1 and everything else 0, so its
+/// hold EVs are the chance of finishing with four of a kind.
+fn quad_indicator(paytable: &Paytable) -> Result<Paytable, String> {
+    let mut indicator = paytable.clone();
+    for (name, value) in paytable.payout_fields() {
+        if value.is_some() {
+            indicator.set_payout_field(name, if name.starts_with("four_") { 1.0 } else { 0.0 })?;
+        }
+    }
+    Ok(indicator)
+}
+
+fn compute_hold_lines(paytable: &Paytable, canonical_hands: &[(String, Hand, u64)]) -> Result<Vec<HoldLines>, String> {
+    let indicator = quad_indicator(paytable)?;
+    let deck = paytable.deck_cards();
+    let tables = [handtable::hand_table(paytable), handtable::hand_table(&indicator)];
+
+    let processed = AtomicUsize::new(0);
+    let total = canonical_hands.len();
+    let calc_start = Instant::now();
+
+    let lines = canonical_hands
+        .par_iter()
+        .map(|(key, hand, multiplicity)| {
+            let mut pay = [0.0; 32];
+            let mut quad = [0.0; 32];
+            for hold in 0..32u8 {
+                let evs = calculate_hold_ev_multi(hand, hold, &[paytable, &indicator], &[&tables[0], &tables[1]], &deck);
+                pay[hold as usize] = evs[0];
+                quad[hold as usize] = evs[1];
+            }
+
+            let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            if count.is_multiple_of(5000) || count == total {
+                let elapsed = calc_start.elapsed().as_secs_f64();
+                let rate = count as f64 / elapsed;
+                let remaining = (total - count) as f64 / rate;
+                print!("    Progress: {:>6}/{} ({:>3}%) | {:.0}/s | ~{}s left    \r",
+                    count, total, count * 100 / total, rate, remaining as u64);
+                io::stdout().flush().unwrap();
+            }
+
+            HoldLines { key: key.clone(), multiplicity: *multiplicity, pay, quad }
+        })
+        .collect();
+    println!();
+
+    Ok(lines)
+}
+
+/// State transitions and payouts of the shockwave chain
+struct Chain {
+    hands: usize,
+    multiplier: f64,
+    retrigger: bool,
+}
+
+impl Chain {
+    fn states(&self) -> usize {
+        self.hands + 1
+    }
+
+    fn mult(&self, state: usize) -> f64 {
+        if state == 0 { 1.0 } else { self.multiplier }
+    }
+
+    /// Next state when the hand is not four of a kind
+    fn next(&self, state: usize) -> usize {
+        state.saturating_sub(1)
+    }
+
+    fn can_trigger(&self, state: usize) -> bool {
+        state == 0 || self.retrigger
+    }
+
+    /// Hold value in `state` given relative state values `h`
+    fn hold_value(&self, state: usize, h: &[f64], pay: f64, quad: f64) -> f64 {
+        let trigger = if self.can_trigger(state) { quad * (h[self.hands] - h[self.next(state)]) } else { 0.0 };
+        self.mult(state) * pay + trigger
+    }
+}
+
+/// Solve a small dense linear system by Gaussian elimination with partial pivoting.
+fn solve_linear(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().partial_cmp(&a[j][col].abs()).unwrap())?;
+        if a[pivot][col].abs() < 1e-15 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let f = row[col] / pivot_row[col];
+            for (x, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= f * p;
+            }
+            b[col + 1 + offset] -= f * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let s: f64 = (row + 1..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - s) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Long-run return and relative state values for a fixed strategy. `pay[s]` and
+/// `trigger[s]` are the per-hand expected pay and trigger chance in state s.
+/// Returns (g, h) with h[0] = 0.
+fn evaluate_policy(chain: &Chain, pay: &[f64], trigger: &[f64]) -> Option<(f64, Vec<f64>)> {
+    let n = chain.states();
+    // Unknowns: x[0] = g, x[s] = h(s) for s in 1..n
+    let mut a = vec![vec![0.0; n]; n];
+    let mut b = vec![0.0; n];
+    for s in 0..n {
+        a[s][0] = 1.0;
+        if s > 0 {
+            a[s][s] += 1.0;
+        }
+        let t = if chain.can_trigger(s) { trigger[s] } else { 0.0 };
+        a[s][chain.hands] -= t;
+        let next = chain.next(s);
+        if next > 0 {
+            a[s][next] -= 1.0 - t;
+        }
+        b[s] = pay[s];
+    }
+    let x = solve_linear(a, b)?;
+    let mut h = x.clone();
+    h[0] = 0.0;
+    Some((x[0], h))
+}
+
+/// Long-run share of hands played in each state for a fixed strategy.
+fn stationary(chain: &Chain, trigger: &[f64]) -> Option<Vec<f64>> {
+    let n = chain.states();
+    // pi = pi P, with the last balance equation replaced by sum(pi) = 1
+    let mut a = vec![vec![0.0; n]; n];
+    for s in 0..n {
+        let t = if chain.can_trigger(s) { trigger[s] } else { 0.0 };
+        a[chain.hands][s] += t;
+        a[chain.next(s)][s] += 1.0 - t;
+    }
+    for (s, row) in a.iter_mut().enumerate() {
+        row[s] -= 1.0;
+    }
+    let mut b = vec![0.0; n];
+    a[n - 1] = vec![1.0; n];
+    b[n - 1] = 1.0;
+    solve_linear(a, b)
+}
+
+#[derive(Serialize)]
+struct StateReport {
+    state: String,
+    hands_remaining: usize,
+    share_of_hands: f64,
+    return_pct: f64,
+    relative_value: f64,
+    changed_hands: usize,
+    changed_frequency: f64,
+}
+
+#[derive(Serialize)]
+struct ShockwaveReport {
+    paytable_id: String,
+    hands: usize,
+    multiplier: f64,
+    retrigger: bool,
+    base_return_pct: f64,
+    overall_return_pct: f64,
+    trigger_value: f64,
+    iterations: usize,
+    /// False if the solver hit MAX_ITERATIONS with holds still changing
+    converged: bool,
+    states: Vec<StateReport>,
+}
+
+fn state_id(paytable_id: &str, state: usize) -> String {
+    if state == 0 {
+        format!("{}-shockwave-normal", paytable_id)
+    } else {
+        format!("{}-shockwave-{}", paytable_id, state)
+    }
+}
+
+pub fn run_shockwave(paytable_id: &str, opts: &ShockwaveOptions) {
+    let paytable = match get_paytable(paytable_id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown paytable: {}", paytable_id);
+            std::process::exit(1);
+        }
+    };
+    if opts.hands == 0 || opts.multiplier <= 0.0 {
+        eprintln!("Shockwave needs at least 1 hand and a positive multiplier");
+        std::process::exit(1);
+    }
+    let chain = Chain { hands: opts.hands, multiplier: opts.multiplier, retrigger: opts.retrigger };
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          SHOCKWAVE ANALYSIS                                      ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Paytable: {} ({})", paytable.name, paytable.id);
+    println!("Shockwave: {} hands at {}x, retrigger {}", chain.hands, chain.multiplier,
+        if chain.retrigger { "on" } else { "off" });
+    println!();
+
+    let canonical = canonical_hands_with_multiplicity_for_paytable(&paytable);
+    let total_dealt: f64 = canonical.iter().map(|(_, _, m)| *m as f64).sum();
+    // Pays are per coin of the base game; side bets like Quick Quads cost extra, so
+    // returns are divided by the bet cost while the solver works in base-game units
+    let cost = paytable.bet_cost();
+
+    println!("  Computing hold pay and four of a kind lines ({} canonical hands)...", canonical.len());
+    let calc_start = Instant::now();
+    let lines = match compute_hold_lines(&paytable, &canonical) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("  Done in {:.1}s", calc_start.elapsed().as_secs_f64());
+
+    let states = chain.states();
+    let best = |line: &HoldLines, state: usize, h: &[f64], current: u8| -> u8 {
+        let mut best_hold = current;
+        let mut best_value = chain.hold_value(state, h, line.pay[current as usize], line.quad[current as usize]);
+        for hold in 0..32 {
+            let value = chain.hold_value(state, h, line.pay[hold], line.quad[hold]);
+            if value > best_value + 1e-12 {
+                best_value = value;
+                best_hold = hold as u8;
+            }
+        }
+        best_hold
+    };
+
+    // Start from the plain strategy in every state
+    let base_holds: Vec<u8> = lines.iter().map(|l| best(l, 0, &vec![0.0; states], 0)).collect();
+    let mut holds: Vec<Vec<u8>> = base_holds.iter().map(|&b| vec![b; states]).collect();
+    let base_return = lines.iter().zip(&base_holds)
+        .map(|(l, &b)| l.multiplicity as f64 * l.pay[b as usize])
+        .sum::<f64>() / (total_dealt * cost);
+
+    let mut iterations = 0;
+    let (g, h, trigger, converged) = loop {
+        iterations += 1;
+        let mut pay = vec![0.0; states];
+        let mut trigger = vec![0.0; states];
+        for (line, hs) in lines.iter().zip(&holds) {
+            let w = line.multiplicity as f64 / total_dealt;
+            for s in 0..states {
+                pay[s] += w * chain.mult(s) * line.pay[hs[s] as usize];
+                trigger[s] += w * line.quad[hs[s] as usize];
+            }
+        }
+        let (g, h) = match evaluate_policy(&chain, &pay, &trigger) {
+            Some(v) => v,
+            None => {
+                eprintln!("Shockwave chain could not be solved");
+                std::process::exit(1);
+            }
+        };
+
+        let mut changed = 0usize;
+        for (line, hs) in lines.iter().zip(holds.iter_mut()) {
+            for (s, hold) in hs.iter_mut().enumerate() {
+                let next = best(line, s, &h, *hold);
+                if next != *hold {
+                    *hold = next;
+                    changed += 1;
+                }
+            }
+        }
+        println!("  Iteration {}: return {:.6}%, trigger value {:.4}, {} holds changed",
+            iterations, g / cost * 100.0, h[chain.hands], changed);
+        if changed == 0 {
+            break (g, h, trigger, true);
+        }
+        if iterations >= MAX_ITERATIONS {
+            eprintln!("  Warning: policy iteration stopped after {} iterations with {} holds still changing;",
+                MAX_ITERATIONS, changed);
+            eprintln!("  the strategies and return below may not be optimal");
+            break (g, h, trigger, false);
+        }
+    };
+
+    let share = stationary(&chain, &trigger).unwrap_or_else(|| vec![f64::NAN; states]);
+
+    println!();
+    println!("  Return without shockwave:   {:.4}%", base_return * 100.0);
+    println!("  Return with shockwave:      {:.4}%", g / cost * 100.0);
+    println!("  Value of a trigger:         {:.4} units", h[chain.hands]);
+    println!();
+    println!("  ┌──────────────┬──────────────┬──────────────┬────────────────┬──────────────┐");
+    println!("  │ State        │ Share        │ Return       │ Changed hands  │ Dealt freq   │");
+    println!("  ├──────────────┼──────────────┼──────────────┼────────────────┼──────────────┤");
+
+    let mut state_reports = Vec::new();
+    for s in 0..states {
+        let mut ret = 0.0;
+        let mut changed = 0usize;
+        let mut changed_weight = 0.0;
+        for ((line, hs), &base) in lines.iter().zip(&holds).zip(&base_holds) {
+            ret += line.multiplicity as f64 * chain.mult(s) * line.pay[hs[s] as usize];
+            if hs[s] != base {
+                changed += 1;
+                changed_weight += line.multiplicity as f64;
+            }
+        }
+        let report = StateReport {
+            state: if s == 0 { "normal".to_string() } else { format!("shockwave {}", s) },
+            hands_remaining: s,
+            share_of_hands: share[s],
+            return_pct: ret / (total_dealt * cost) * 100.0,
+            relative_value: h[s],
+            changed_hands: changed,
+            changed_frequency: changed_weight / total_dealt,
+        };
+        println!("  │ {:<12} │ {:>12.6} │ {:>11.4}% │ {:>14} │ {:>12.6} │",
+            report.state, report.share_of_hands, report.return_pct, report.changed_hands, report.changed_frequency);
+        state_reports.push(report);
+
+        if opts.write_strategies {
+            let strategies: HashMap<String, StrategyEntry> = lines.iter().zip(&holds)
+                .map(|(line, hs)| {
+                    let value = |hold: usize| chain.hold_value(s, &h, line.pay[hold], line.quad[hold]);
+                    let hold_evs = (0..32)
+                        .map(|hold| (hold.to_string(), (value(hold) * 1000000.0).round() / 1000000.0))
+                        .collect();
+                    (line.key.clone(), StrategyEntry {
+                        hold: hs[s],
+                        ev: (value(hs[s] as usize) * 1000000.0).round() / 1000000.0,
+                        hold_evs,
+                    })
+                })
+                .collect();
+            let binary = generate_binary_strategy_v2(&strategies, paytable.num_jokers());
+            if let Err(e) = save_binary_strategy_v2(&binary, &state_id(&paytable.id, s), &opts.output_dir) {
+                eprintln!("  ✗ Failed to save strategy for state {}: {}", s, e);
+            }
+        }
+    }
+    println!("  └──────────────┴──────────────┴──────────────┴────────────────┴──────────────┘");
+
+    let report = ShockwaveReport {
+        paytable_id: paytable.id.clone(),
+        hands: chain.hands,
+        multiplier: chain.multiplier,
+        retrigger: chain.retrigger,
+        base_return_pct: base_return * 100.0,
+        overall_return_pct: g / cost * 100.0,
+        trigger_value: h[chain.hands],
+        iterations,
+        converged,
+        states: state_reports,
+    };
+    let path = Path::new(&opts.output_dir).join(format!("shockwave_{}.json", paytable.id.replace('-', "_")));
+    if let Err(e) = fs::create_dir_all(&opts.output_dir) {
+        eprintln!("  ✗ Failed to create {}: {}", opts.output_dir, e);
+        return;
+    }
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => match fs::write(&path, json) {
+            Ok(_) => println!("\n  ✓ Report: {}", path.display()),
+            Err(e) => eprintln!("\n  ✗ Failed to write report: {}", e),
+        },
+        Err(e) => eprintln!("\n  ✗ Failed to serialize report: {}", e),
+    }
+}
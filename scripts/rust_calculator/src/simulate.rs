@@ -0,0 +1,329 @@
+// ============================================================================
+// MONTE CARLO SESSION SIMULATOR
+// ============================================================================
+//
+// Plays sessions hand by hand with the best holds from a .vpstrat2 file. Each hand deals
+// five cards; on multi-line play every line keeps the held cards and draws replacements
+// from its own shuffle of the remaining cards, as Triple Play and the other N-play
+// machines do. Every session has its own RNG stream derived from the seed, so results
+// are the same however rayon schedules the sessions. Amounts are in bets: one line's wager.
+
+use super::*;
+
+/// Cumulative results are sampled at this many points along each session
+const TRAJECTORY_POINTS: usize = 20;
+/// Sessions whose full trajectories go in the report
+const SAMPLE_TRAJECTORIES: usize = 10;
+
+pub struct SimulateOptions {
+    pub sessions: usize,
+    pub hands: usize,
+    pub lines: usize,
+    pub seed: u64,
+    pub strategies_dir: String,
+    pub output_dir: String,
+}
+
+/// SplitMix64: a small, fast generator that is plenty for shuffling cards
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    /// Independent stream for one session
+    fn for_session(seed: u64, session: u64) -> SplitMix64 {
+        let mut rng = SplitMix64(seed);
+        rng.0 ^= SplitMix64(session.wrapping_add(0x632B_E59B_D9B4_E019)).next_u64();
+        rng
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in 0..n
+    fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+}
+
+struct SessionResult {
+    /// Net result at the end of the session
+    net: f64,
+    /// Lowest net result reached during the session
+    low: f64,
+    /// Most consecutive hands returning less than they cost
+    longest_losing_streak: usize,
+    /// Net result after each TRAJECTORY_POINTS-th of the session
+    trajectory: Vec<f64>,
+    paid: f64,
+}
+
+#[derive(Serialize)]
+pub struct Percentiles {
+    pub p1: f64,
+    pub p5: f64,
+    pub p10: f64,
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub p99: f64,
+}
+
+impl Percentiles {
+    fn of(values: &mut [f64]) -> Percentiles {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let at = |q: f64| values[((values.len() - 1) as f64 * q).round() as usize];
+        Percentiles {
+            p1: at(0.01),
+            p5: at(0.05),
+            p10: at(0.10),
+            p25: at(0.25),
+            p50: at(0.50),
+            p75: at(0.75),
+            p90: at(0.90),
+            p95: at(0.95),
+            p99: at(0.99),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TrajectoryPoint {
+    pub hands: usize,
+    pub p10: f64,
+    pub p50: f64,
+    pub p90: f64,
+}
+
+#[derive(Serialize)]
+pub struct SimulationReport {
+    pub paytable_id: String,
+    pub sessions: usize,
+    pub hands_per_session: usize,
+    pub lines: usize,
+    pub seed: u64,
+    pub expected_return_pct: f64,
+    pub simulated_return_pct: f64,
+    /// Net result per session, in bets
+    pub mean_net: f64,
+    pub net: Percentiles,
+    /// Lowest point reached per session, in bets
+    pub low: Percentiles,
+    pub probability_of_loss: f64,
+    pub longest_losing_streak_max: usize,
+    pub longest_losing_streak_median: usize,
+    pub trajectory: Vec<TrajectoryPoint>,
+    /// Trajectories of the first sessions, sampled at the same points
+    pub sample_trajectories: Vec<Vec<f64>>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn play_session(
+    session: u64,
+    opts: &SimulateOptions,
+    paytable: &Paytable,
+    table: &handtable::HandTable,
+    holds: &HashMap<String, u8>,
+    full_deck: &[Card],
+    checkpoints: &[usize],
+) -> SessionResult {
+    let mut rng = SplitMix64::for_session(opts.seed, session);
+    let mut deck = full_deck.to_vec();
+    let n = deck.len();
+    let wager = paytable.bet_cost();
+    let mut net = 0.0;
+    let mut low: f64 = 0.0;
+    let mut paid = 0.0;
+    let mut streak = 0;
+    let mut longest_losing_streak = 0;
+    let mut trajectory = Vec::with_capacity(checkpoints.len());
+    let mut next_checkpoint = 0;
+
+    for hand in 1..=opts.hands {
+        // A partial Fisher-Yates shuffle deals from any deck order
+        for i in 0..5 {
+            let j = i + rng.below(n - i);
+            deck.swap(i, j);
+        }
+        // Card order is the order canonical hands are generated in, so hold bit i is dealt[i]
+        let mut dealt: Hand = [deck[0], deck[1], deck[2], deck[3], deck[4]];
+        dealt.sort();
+        let hold = holds.get(&hand_to_canonical_key(&dealt)).copied().unwrap_or(0);
+        let (held_hand, held) = held_cards(&dealt, hold);
+
+        let mut pay = 0.0;
+        for _ in 0..opts.lines {
+            let mut final_hand = held_hand;
+            for k in 0..5 - held {
+                let j = 5 + k + rng.below(n - 5 - k);
+                deck.swap(5 + k, j);
+                final_hand[held + k] = deck[5 + k];
+            }
+            pay += paytable.payout(table.hand_type(&final_hand));
+        }
+        let result = pay / wager - opts.lines as f64;
+        net += result;
+        paid += pay / wager;
+        low = low.min(net);
+        if result < 0.0 {
+            streak += 1;
+            longest_losing_streak = longest_losing_streak.max(streak);
+        } else {
+            streak = 0;
+        }
+        if next_checkpoint < checkpoints.len() && hand == checkpoints[next_checkpoint] {
+            trajectory.push(net);
+            next_checkpoint += 1;
+        }
+    }
+    SessionResult { net, low, longest_losing_streak, trajectory, paid }
+}
+
+pub fn run_simulate(paytable_id: &str, opts: &SimulateOptions) {
+    let paytable = match get_paytable(paytable_id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown paytable: {}", paytable_id);
+            std::process::exit(1);
+        }
+    };
+    // Hands are played canonically, so a sequential royal would never be paid
+    if paytable.deck.is_some() || paytable.game_family == GameFamily::PickEm || paytable.sequential_royal.is_some() {
+        eprintln!("Simulation is not supported for {}", paytable.id);
+        std::process::exit(1);
+    }
+    if opts.sessions == 0 || opts.hands == 0 || opts.lines == 0 || opts.lines > 100 {
+        eprintln!("Need at least one session and hand, and 1 to 100 lines");
+        std::process::exit(1);
+    }
+
+    let filename = format!("strategy_{}.vpstrat2", paytable.id.replace("-", "_"));
+    let strat_path = Path::new(&opts.strategies_dir).join(&filename);
+    let entries = match read_vpstrat2_entries(&strat_path.to_string_lossy()) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", strat_path.to_string_lossy(), e);
+            std::process::exit(1);
+        }
+    };
+    let holds: HashMap<String, u8> = entries.iter().map(|(key, &(hold, _, _))| (key.clone(), hold)).collect();
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          MONTE CARLO SESSION SIMULATOR                           ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Paytable: {} ({})", paytable.name, paytable.id);
+    println!("{} sessions x {} hands x {} line{} | seed {}", opts.sessions, opts.hands, opts.lines,
+        if opts.lines == 1 { "" } else { "s" }, opts.seed);
+    println!();
+
+    let canonical = canonical_hands_with_multiplicity_for_paytable(&paytable);
+    let expected_return = match strategy_return(&paytable, &entries, &canonical) {
+        Ok((ret, _)) => ret,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let table = handtable::hand_table(&paytable);
+    let full_deck = paytable.deck_cards();
+    let points = TRAJECTORY_POINTS.min(opts.hands);
+    let checkpoints: Vec<usize> = (1..=points).map(|i| opts.hands * i / points).collect();
+
+    let sim_start = Instant::now();
+    let processed = AtomicUsize::new(0);
+    let results: Vec<SessionResult> = (0..opts.sessions as u64)
+        .into_par_iter()
+        .map(|session| {
+            let result = play_session(session, opts, &paytable, &table, &holds, &full_deck, &checkpoints);
+            let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
+            if count.is_multiple_of(1000) || count == opts.sessions {
+                let elapsed = sim_start.elapsed().as_secs_f64();
+                let rate = count as f64 / elapsed;
+                let remaining = (opts.sessions - count) as f64 / rate;
+                print!("    Progress: {:>7}/{} ({:>3}%) | {:.0} sessions/s | ~{}s left    \r",
+                    count, opts.sessions, count * 100 / opts.sessions, rate, remaining as u64);
+                io::stdout().flush().unwrap();
+            }
+            result
+        })
+        .collect();
+    println!();
+    println!("  Done in {:.1}s", sim_start.elapsed().as_secs_f64());
+
+    let sessions = results.len() as f64;
+    let wagered = sessions * (opts.hands * opts.lines) as f64;
+    let simulated_return = results.iter().map(|r| r.paid).sum::<f64>() / wagered;
+    let mean_net = results.iter().map(|r| r.net).sum::<f64>() / sessions;
+    let probability_of_loss = results.iter().filter(|r| r.net < 0.0).count() as f64 / sessions;
+    let net = Percentiles::of(&mut results.iter().map(|r| r.net).collect::<Vec<_>>());
+    let low = Percentiles::of(&mut results.iter().map(|r| r.low).collect::<Vec<_>>());
+    let mut streaks: Vec<usize> = results.iter().map(|r| r.longest_losing_streak).collect();
+    streaks.sort_unstable();
+    let trajectory: Vec<TrajectoryPoint> = checkpoints.iter().enumerate()
+        .map(|(i, &hands)| {
+            let at = Percentiles::of(&mut results.iter().map(|r| r.trajectory[i]).collect::<Vec<_>>());
+            TrajectoryPoint { hands, p10: at.p10, p50: at.p50, p90: at.p90 }
+        })
+        .collect();
+
+    println!();
+    println!("  Return: {:.4}% simulated, {:.4}% expected", simulated_return * 100.0, expected_return * 100.0);
+    println!("  Probability of a losing session: {:.2}%", probability_of_loss * 100.0);
+    println!("  Longest losing streak: {} hands (median session {})", streaks[streaks.len() - 1], streaks[streaks.len() / 2]);
+    println!();
+    println!("  Session result in bets (mean {:+.1}):", mean_net);
+    println!("  ┌──────────┬────────────┬────────────┐");
+    println!("  │          │ Net result │ Low point  │");
+    println!("  ├──────────┼────────────┼────────────┤");
+    for (label, n, l) in [
+        ("1%", net.p1, low.p1), ("5%", net.p5, low.p5), ("10%", net.p10, low.p10),
+        ("25%", net.p25, low.p25), ("Median", net.p50, low.p50), ("75%", net.p75, low.p75),
+        ("90%", net.p90, low.p90), ("95%", net.p95, low.p95), ("99%", net.p99, low.p99),
+    ] {
+        println!("  │ {:<8} │ {:>10.1} │ {:>10.1} │", label, n, l);
+    }
+    println!("  └──────────┴────────────┴────────────┘");
+    println!();
+    println!("  Bankroll trajectory in bets (10th / median / 90th percentile):");
+    for point in &trajectory {
+        println!("    after {:>8} hands  {:>10.1} {:>10.1} {:>10.1}", point.hands, point.p10, point.p50, point.p90);
+    }
+
+    let report = SimulationReport {
+        paytable_id: paytable.id.clone(),
+        sessions: opts.sessions,
+        hands_per_session: opts.hands,
+        lines: opts.lines,
+        seed: opts.seed,
+        expected_return_pct: expected_return * 100.0,
+        simulated_return_pct: simulated_return * 100.0,
+        mean_net,
+        net,
+        low,
+        probability_of_loss,
+        longest_losing_streak_max: streaks[streaks.len() - 1],
+        longest_losing_streak_median: streaks[streaks.len() / 2],
+        trajectory,
+        sample_trajectories: results.iter().take(SAMPLE_TRAJECTORIES).map(|r| r.trajectory.clone()).collect(),
+    };
+    if let Err(e) = fs::create_dir_all(&opts.output_dir) {
+        eprintln!("\n  ✗ Failed to create {}: {}", opts.output_dir, e);
+        return;
+    }
+    let report_path = Path::new(&opts.output_dir).join(format!(
+        "simulate_{}_{}line.json", paytable.id.replace('-', "_"), opts.lines));
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => match fs::write(&report_path, json) {
+            Ok(_) => println!("\n  ✓ Report: {}", report_path.display()),
+            Err(e) => eprintln!("\n  ✗ Failed to write report: {}", e),
+        },
+        Err(e) => eprintln!("\n  ✗ Failed to serialize report: {}", e),
+    }
+}
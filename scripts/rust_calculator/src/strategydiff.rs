@@ -0,0 +1,312 @@
+// ============================================================================
+// STRATEGY DIFF
+// ============================================================================
+//
+// Compares the optimal strategies of two paytables on the same deck, hand by hand.
+// Every canonical hand whose best hold differs is scored on paytable B: the cost is B's
+// EV for B's best hold minus B's EV for A's best hold, read straight from B's strategy
+// file. Hands are pooled by the change in hold class ("Low pair → 4 to an outside
+// straight") and weighted by how often they are dealt, so the sum over the groups is
+// the return lost by playing A's strategy on B.
+
+use super::*;
+
+const EXAMPLES_PER_CHANGE: usize = 3;
+
+#[derive(Serialize)]
+pub struct DiffExample {
+    pub hand: String,
+    pub hold_a: String,
+    pub hold_b: String,
+    /// EV lost on B by holding A's cards
+    pub cost: f64,
+}
+
+#[derive(Serialize)]
+pub struct HoldChange {
+    /// Class of A's best hold
+    pub from: String,
+    /// Class of B's best hold
+    pub to: String,
+    pub canonical_hands: usize,
+    /// Share of dealt hands
+    pub frequency: f64,
+    /// Return lost on B, per unit wagered
+    pub cost: f64,
+    /// The costliest hands
+    pub examples: Vec<DiffExample>,
+}
+
+#[derive(Serialize)]
+pub struct StrategyDiffReport {
+    pub paytable_a: String,
+    pub paytable_b: String,
+    pub return_b: f64,
+    pub return_a_on_b: f64,
+    pub cost: f64,
+    /// Hands where the best holds differ and A's hold costs more than the EV encoding step
+    pub changed_hands: usize,
+    pub changed_frequency: f64,
+    /// Hands where the best holds differ but are worth the same on B
+    pub tied_hands: usize,
+    pub changes: Vec<HoldChange>,
+}
+
+/// A hand whose best hold changes: (cost on B, dealt weight, key, A's hold, B's hold)
+type ChangedHand<'a> = (f64, f64, &'a str, u8, u8);
+
+/// Held cards for a hold mask, read off the canonical key (bit i = i-th card of the key).
+fn key_hold_cards(key: &str, hold: u8) -> String {
+    let cards: Vec<&str> = (0..key.len() / 2)
+        .filter(|i| hold & (1 << i) != 0)
+        .map(|i| &key[i * 2..i * 2 + 2])
+        .collect();
+    if cards.is_empty() { "(discard all)".to_string() } else { cards.join(" ") }
+}
+
+fn is_wild_card(paytable: &Paytable, card: Card) -> bool {
+    card.is_joker() || (paytable.is_deuces_wild() && card.rank() == 0)
+}
+
+/// Whether distinct ranks fit one five-rank straight window (ace high or low)
+fn fits_straight(ranks: &[u8]) -> bool {
+    (0..=8u8).any(|low| ranks.iter().all(|&r| r >= low && r < low + 5))
+        || ranks.iter().all(|&r| r == 12 || r <= 3)
+}
+
+/// What a set of natural (non-wild) cards makes or draws to
+fn natural_class(paytable: &Paytable, naturals: &[Card]) -> String {
+    let mut counts = [0usize; 13];
+    for c in naturals {
+        counts[c.rank() as usize] += 1;
+    }
+    let top = counts.iter().cloned().max().unwrap_or(0);
+    let pairs = counts.iter().filter(|&&n| n == 2).count();
+    let kicker = |group: usize| if naturals.len() > group { " + kicker" } else { "" };
+    match top {
+        4 => return format!("Four of a kind{}", kicker(4)),
+        3 => return format!("Three of a kind{}", kicker(3)),
+        2 if pairs == 2 => return format!("Two pair{}", kicker(4)),
+        2 => {
+            let rank = counts.iter().position(|&n| n == 2).unwrap_or(0) as u8;
+            let pair = match paytable.min_pair_rank {
+                0 => "Pair",
+                min if rank >= min => "High pair",
+                _ => "Low pair",
+            };
+            return format!("{}{}", pair, kicker(2));
+        }
+        _ => {}
+    }
+
+    let n = naturals.len();
+    let ranks: Vec<u8> = naturals.iter().map(|c| c.rank()).collect();
+    let suited = naturals.iter().all(|c| c.suit() == naturals[0].suit());
+    let straight = fits_straight(&ranks);
+    let royal = ranks.iter().all(|&r| r >= 8);
+    let high = |r: u8| if paytable.min_pair_rank > 0 { r >= paytable.min_pair_rank } else { r >= 9 };
+    if n >= 2 && suited && straight && royal {
+        return format!("{} to a royal flush", n);
+    }
+    if n >= 3 && suited && straight {
+        return format!("{} to a straight flush", n);
+    }
+    if n >= 3 && suited {
+        return format!("{} to a flush", n);
+    }
+    if n == 4 && straight {
+        let (lo, hi) = (ranks.iter().min().cloned().unwrap_or(0), ranks.iter().max().cloned().unwrap_or(0));
+        return if hi - lo == 3 && hi < 12 {
+            "4 to an outside straight".to_string()
+        } else {
+            "4 to an inside straight".to_string()
+        };
+    }
+    if n == 3 && straight {
+        return "3 to a straight".to_string();
+    }
+    let high_cards = ranks.iter().filter(|&&r| high(r)).count();
+    match (n, high_cards) {
+        (1, 1) => "1 high card".to_string(),
+        (_, h) if h == n => format!("{} {}high cards", n, if suited { "suited " } else { "unsuited " }),
+        (1, 0) => "1 low card".to_string(),
+        _ => format!("{} cards, {} high", n, high_cards),
+    }
+}
+
+/// Class of a hold, for grouping strategy changes: the wild count plus what the
+/// natural cards make or draw to. Pat hands are named by the hand they make on `paytable`.
+fn hold_class(paytable: &Paytable, hand: &Hand, hold: u8) -> String {
+    let (cards, held) = held_cards(hand, hold);
+    if held == 0 {
+        return "Discard all".to_string();
+    }
+    if held == 5 {
+        let hand_type = handtable::hand_table(paytable).hand_type(&cards);
+        return if paytable.payout(hand_type) > 0.0 {
+            format!("Pat {}", hand_type.name())
+        } else {
+            "Pat non-paying hand".to_string()
+        };
+    }
+    let naturals: Vec<Card> = cards[..held].iter().filter(|&&c| !is_wild_card(paytable, c)).cloned().collect();
+    let wilds = held - naturals.len();
+    match (wilds, naturals.is_empty()) {
+        (0, _) => natural_class(paytable, &naturals),
+        (w, true) => format!("{} wild only", w),
+        (w, false) => format!("{} wild + {}", w, natural_class(paytable, &naturals)),
+    }
+}
+
+fn load_evs(paytable: &Paytable, strategies_dir: &str) -> StrategyEvs {
+    let filename = format!("strategy_{}.vpstrat2", paytable.id.replace("-", "_"));
+    let strat_path = Path::new(strategies_dir).join(&filename);
+    match read_vpstrat2_evs(&strat_path.to_string_lossy()) {
+        Ok(evs) => evs,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", strat_path.to_string_lossy(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn run_strategy_diff(id_a: &str, id_b: &str, strategies_dir: &str, top: usize, output_dir: &str) {
+    let [paytable_a, paytable_b] = [id_a, id_b].map(|id| match get_paytable(id) {
+        Some(pt) => pt,
+        None => {
+            eprintln!("Unknown paytable: {}", id);
+            std::process::exit(1);
+        }
+    });
+    if paytable_a.num_jokers() != paytable_b.num_jokers() || paytable_a.deck.is_some() || paytable_b.deck.is_some() {
+        eprintln!("{} and {} are not dealt from the same standard deck", paytable_a.id, paytable_b.id);
+        std::process::exit(1);
+    }
+    let evs_a = load_evs(&paytable_a, strategies_dir);
+    let evs_b = load_evs(&paytable_b, strategies_dir);
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          STRATEGY DIFF                                           ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Strategy A: {} ({})", paytable_a.name, paytable_a.id);
+    println!("Played on B: {} ({})", paytable_b.name, paytable_b.id);
+    println!();
+
+    let canonical = canonical_hands_with_multiplicity_for_paytable(&paytable_b);
+    let total_dealt: f64 = canonical.iter().map(|(_, _, m)| *m as f64).sum();
+    // EVs are per coin of the base game; side bets like Quick Quads cost extra
+    let wagered = total_dealt * paytable_b.bet_cost();
+
+    let mut return_b = 0.0;
+    let mut return_a_on_b = 0.0;
+    let mut tied_hands = 0;
+    let mut missing = 0;
+    let mut groups: HashMap<(String, String), Vec<ChangedHand>> = HashMap::new();
+    for (key, hand, multiplicity) in &canonical {
+        let (Some(&(hold_a, _, _)), Some(&(hold_b, ref ev_b, step))) = (evs_a.get(key), evs_b.get(key)) else {
+            missing += 1;
+            continue;
+        };
+        let m = *multiplicity as f64;
+        return_b += m * ev_b[hold_b as usize];
+        return_a_on_b += m * ev_b[hold_a as usize];
+        if hold_a == hold_b {
+            continue;
+        }
+        let cost = ev_b[hold_b as usize] - ev_b[hold_a as usize];
+        // Both EVs are stored to within half a step
+        if cost <= step {
+            tied_hands += 1;
+            continue;
+        }
+        let change = (hold_class(&paytable_b, hand, hold_a), hold_class(&paytable_b, hand, hold_b));
+        groups.entry(change).or_default().push((cost, m, key, hold_a, hold_b));
+    }
+    if missing > 0 {
+        eprintln!("{} canonical hands are missing from the strategy files", missing);
+        std::process::exit(1);
+    }
+    return_b /= wagered;
+    return_a_on_b /= wagered;
+
+    let mut changes: Vec<HoldChange> = groups.into_iter()
+        .map(|((from, to), mut hands)| {
+            hands.sort_by(|a, b| (b.0 * b.1).total_cmp(&(a.0 * a.1)).then(a.2.cmp(b.2)));
+            let weight: f64 = hands.iter().map(|h| h.1).sum();
+            let examples = hands.iter().take(EXAMPLES_PER_CHANGE)
+                .map(|&(cost, _, key, hold_a, hold_b)| DiffExample {
+                    hand: key.to_string(),
+                    hold_a: key_hold_cards(key, hold_a),
+                    hold_b: key_hold_cards(key, hold_b),
+                    cost,
+                })
+                .collect();
+            HoldChange {
+                from,
+                to,
+                canonical_hands: hands.len(),
+                frequency: weight / total_dealt,
+                cost: hands.iter().map(|h| h.0 * h.1).sum::<f64>() / wagered,
+                examples,
+            }
+        })
+        .collect();
+    changes.sort_by(|a, b| b.cost.total_cmp(&a.cost).then(a.from.cmp(&b.from)).then(a.to.cmp(&b.to)));
+
+    let changed_hands: usize = changes.iter().map(|c| c.canonical_hands).sum();
+    let changed_frequency: f64 = changes.iter().map(|c| c.frequency).sum();
+    println!("  Return on B, B's strategy:       {:.4}%", return_b * 100.0);
+    println!("  Return on B, A's strategy:       {:.4}%", return_a_on_b * 100.0);
+    println!("  Cost of playing A's strategy:    {:.4}%", (return_b - return_a_on_b) * 100.0);
+    println!("  Hands that change hold: {} canonical ({:.4}% of deals), {} more tie on B",
+        changed_hands, changed_frequency * 100.0, tied_hands);
+
+    if !changes.is_empty() {
+        println!();
+        println!("  ┌──────────────────────────────────────────────────────────────┬────────┬──────────┬──────────┐");
+        println!("  │ A holds → B holds                                            │  Hands │ Dealt    │ Cost     │");
+        println!("  ├──────────────────────────────────────────────────────────────┼────────┼──────────┼──────────┤");
+        for c in changes.iter().take(top) {
+            let label = format!("{} → {}", c.from, c.to);
+            println!("  │ {:<60} │ {:>6} │ {:>7.4}% │ {:>7.4}% │",
+                label, c.canonical_hands, c.frequency * 100.0, c.cost * 100.0);
+        }
+        println!("  └──────────────────────────────────────────────────────────────┴────────┴──────────┴──────────┘");
+        if changes.len() > top {
+            println!("  ({} more changes in the report)", changes.len() - top);
+        }
+        println!();
+        println!("  Costliest hands:");
+        for c in changes.iter().take(top.min(10)) {
+            if let Some(e) = c.examples.first() {
+                println!("    {}  hold {:<16} instead of {:<16} -{:.4}", e.hand, e.hold_a, e.hold_b, e.cost);
+            }
+        }
+    }
+
+    let report = StrategyDiffReport {
+        paytable_a: paytable_a.id.clone(),
+        paytable_b: paytable_b.id.clone(),
+        return_b,
+        return_a_on_b,
+        cost: return_b - return_a_on_b,
+        changed_hands,
+        changed_frequency,
+        tied_hands,
+        changes,
+    };
+    if let Err(e) = fs::create_dir_all(output_dir) {
+        eprintln!("\n  ✗ Failed to create {}: {}", output_dir, e);
+        return;
+    }
+    let report_path = Path::new(output_dir).join(format!(
+        "diff_{}_vs_{}.json", paytable_a.id.replace('-', "_"), paytable_b.id.replace('-', "_")));
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => match fs::write(&report_path, json) {
+            Ok(_) => println!("\n  ✓ Report: {}", report_path.display()),
+            Err(e) => eprintln!("\n  ✗ Failed to write report: {}", e),
+        },
+        Err(e) => eprintln!("\n  ✗ Failed to serialize report: {}", e),
+    }
+}
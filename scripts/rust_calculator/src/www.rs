@@ -0,0 +1,140 @@
+// ============================================================================
+// WILD WILD WILD — BLENDED RETURN
+// ============================================================================
+//
+// Each WWW paytable ("www-{base}-{N}w") is solved with a fixed number of added
+// wilds, but in play N is drawn before every deal. The player sees N before holding,
+// so each state keeps its own optimal strategy and the game is a mixture of the four
+// per-N distributions weighted by the wild-count odds.
+
+use super::*;
+
+/// One wild-count state of the mixture
+#[derive(Serialize)]
+struct WildState {
+    wilds: u8,
+    probability: f64,
+    return_pct: f64,
+    std_dev: f64,
+    /// Share of the blended return paid in this state
+    return_share: f64,
+}
+
+pub fn run_www_blend(base_id: &str, strategies_dir: &str, odds: Option<[f64; 4]>) {
+    let odds = match odds.or_else(|| www_wild_distribution(base_id)) {
+        Some(o) => o,
+        None => {
+            eprintln!("No known WWW wild-count distribution for '{}'; pass --wilds P0,P1,P2,P3", base_id);
+            std::process::exit(1);
+        }
+    };
+    let total: f64 = odds.iter().sum();
+    if (total - 1.0).abs() > 1e-6 || odds.iter().any(|p| *p < 0.0) {
+        eprintln!("Wild-count probabilities must be non-negative and sum to 1 (got {})", total);
+        std::process::exit(1);
+    }
+
+    // Load every state up front so a missing strategy fails before any work
+    let mut states: Vec<(u8, f64, Paytable, HashMap<String, u8>)> = Vec::new();
+    for (n, &probability) in odds.iter().enumerate() {
+        let id = format!("www-{}-{}w", base_id, n);
+        let paytable = match get_paytable(&id) {
+            Some(pt) => pt,
+            None => {
+                eprintln!("Unknown paytable: {}", id);
+                std::process::exit(1);
+            }
+        };
+        if probability == 0.0 {
+            continue;
+        }
+        let filename = format!("strategy_{}.vpstrat2", id.replace("-", "_"));
+        let strat_path = Path::new(strategies_dir).join(&filename);
+        match read_vpstrat2_holds(&strat_path.to_string_lossy()) {
+            Ok(holds) => states.push((n as u8, probability, paytable, holds)),
+            Err(e) => {
+                eprintln!("Failed to load {}: {}", strat_path.to_string_lossy(), e);
+                eprintln!("Generate it first with: vp_calculator {} --no-upload", id);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    println!("╔══════════════════════════════════════════════════════════════════╗");
+    println!("║          WILD WILD WILD BLENDED RETURN                           ║");
+    println!("╚══════════════════════════════════════════════════════════════════╝");
+    println!();
+    println!("Base paytable: {}", base_id);
+    println!("Wild-count odds: {}", odds.iter().enumerate()
+        .map(|(n, p)| format!("{}w {:.2}%", n, p * 100.0))
+        .collect::<Vec<_>>()
+        .join(" | "));
+    println!();
+
+    // hand type -> (blended probability, payout per state)
+    let mut hand_types: HashMap<String, (f64, [Option<f64>; 4])> = HashMap::new();
+    let mut results: Vec<WildState> = Vec::new();
+    let mut blended_return = 0.0;
+    let mut blended_second = 0.0;
+
+    for (wilds, probability, paytable, holds) in &states {
+        let canonical = canonical_hands_with_multiplicity_for_paytable(paytable);
+        print!("  {}w: computing distribution ({} canonical hands)... ", wilds, canonical.len());
+        io::stdout().flush().unwrap();
+        let (distribution, state_return) = compute_distribution(paytable, holds, &canonical);
+
+        let second: f64 = distribution.values().map(|(prob, payout, _)| prob * payout * payout).sum();
+        for (name, &(prob, payout, _)) in &distribution {
+            let entry = hand_types.entry(name.clone()).or_insert((0.0, [None; 4]));
+            entry.0 += probability * prob;
+            entry.1[*wilds as usize] = Some(payout);
+        }
+        blended_return += probability * state_return;
+        blended_second += probability * second;
+        results.push(WildState {
+            wilds: *wilds,
+            probability: *probability,
+            return_pct: state_return * 100.0,
+            std_dev: (second - state_return * state_return).max(0.0).sqrt(),
+            return_share: 0.0,
+        });
+    }
+    for r in &mut results {
+        r.return_share = if blended_return > 0.0 {
+            r.probability * r.return_pct / 100.0 / blended_return
+        } else {
+            0.0
+        };
+    }
+    let variance = (blended_second - blended_return * blended_return).max(0.0);
+
+    println!();
+    println!("  ┌────────┬──────────┬───────────┬──────────┬──────────────┐");
+    println!("  │ Wilds  │ Odds     │ Return    │ Std dev  │ Return share │");
+    println!("  ├────────┼──────────┼───────────┼──────────┼──────────────┤");
+    for r in &results {
+        println!("  │ {:>6} │ {:>7.3}% │ {:>8.4}% │ {:>8.4} │ {:>11.3}% │",
+            r.wilds, r.probability * 100.0, r.return_pct, r.std_dev, r.return_share * 100.0);
+    }
+    println!("  ├────────┼──────────┼───────────┼──────────┼──────────────┤");
+    println!("  │ Blend  │ {:>7.3}% │ {:>8.4}% │ {:>8.4} │ {:>11.3}% │",
+        total * 100.0, blended_return * 100.0, variance.sqrt(), 100.0);
+    println!("  └────────┴──────────┴───────────┴──────────┴──────────────┘");
+    println!("  Blended variance: {:.4}", variance);
+
+    let mut rows: Vec<(String, f64, [Option<f64>; 4])> = hand_types.into_iter()
+        .map(|(name, (prob, pays))| (name, prob, pays))
+        .collect();
+    rows.sort_by(|a, b| {
+        let top = |pays: &[Option<f64>; 4]| pays.iter().flatten().fold(0.0f64, |m, p| m.max(*p));
+        top(&b.2).partial_cmp(&top(&a.2)).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(&b.0))
+    });
+    println!();
+    println!("  {:<28} {:>14}   Pay at 0w/1w/2w/3w", "Hand", "Blended prob");
+    for (name, prob, pays) in &rows {
+        let pays: Vec<String> = pays.iter()
+            .map(|p| p.map_or("-".to_string(), |v| format!("{}", v)))
+            .collect();
+        println!("  {:<28} {:>14.10}   {}", name, prob, pays.join("/"));
+    }
+}
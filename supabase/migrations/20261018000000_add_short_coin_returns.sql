@@ -0,0 +1,32 @@
+-- Short-coin returns: strategies computed for 1-4 coin play are stored under
+-- "{paytable_id}-{N}coin". Tag each return row with its max-coin paytable and
+-- coin count so the client can show the return given up by short-coining.
+
+ALTER TABLE paytable_returns
+    ADD COLUMN IF NOT EXISTS base_paytable_id TEXT,
+    ADD COLUMN IF NOT EXISTS coins INTEGER NOT NULL DEFAULT 5;
+
+UPDATE paytable_returns
+    SET base_paytable_id = paytable_id
+    WHERE base_paytable_id IS NULL;
+
+ALTER TABLE paytable_returns
+    ALTER COLUMN base_paytable_id SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_paytable_returns_base
+    ON paytable_returns (base_paytable_id);
+
+-- Return at each coin count next to the max-coin return for the same paytable
+CREATE OR REPLACE VIEW paytable_short_coin_costs AS
+SELECT
+    short.base_paytable_id,
+    short.coins,
+    short.calculated_return_pct,
+    max_coin.calculated_return_pct AS max_coin_return_pct,
+    max_coin.calculated_return_pct - short.calculated_return_pct AS return_cost_pct
+FROM paytable_returns short
+JOIN paytable_returns max_coin
+    ON max_coin.paytable_id = short.base_paytable_id
+WHERE short.coins < 5;
+
+GRANT SELECT ON paytable_short_coin_costs TO anon, authenticated;
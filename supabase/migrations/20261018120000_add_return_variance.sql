@@ -0,0 +1,14 @@
+-- Volatility alongside each paytable's return: variance and standard deviation of
+-- the amount returned per unit wagered, coefficient of variation, hit frequency,
+-- hands per natural royal, and the chance of losing a bankroll of ror_bankroll
+-- bets within ror_hands hands under optimal play.
+
+ALTER TABLE paytable_returns
+    ADD COLUMN IF NOT EXISTS variance DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS std_dev DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS coefficient_of_variation DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS hit_frequency DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS hands_per_royal DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS risk_of_ruin DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS ror_bankroll DOUBLE PRECISION,
+    ADD COLUMN IF NOT EXISTS ror_hands INTEGER;